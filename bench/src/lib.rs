@@ -38,6 +38,24 @@ mod vec {
                 black_box(V::from_bytes(v.as_slice(), bin_proto::ByteOrder::BigEndian)).unwrap();
             })
         }
+
+        #[bench]
+        fn bench_read_replace(b: &mut Bencher) {
+            let mut v = vec![255u8];
+            v.extend((0..255).collect::<Vec<_>>());
+            let mut value = V::from_bytes(v.as_slice(), bin_proto::ByteOrder::BigEndian).unwrap();
+            b.iter(|| {
+                black_box(value.read_replace(
+                    &mut bin_proto::bitstream_io::BitReader::endian(
+                        v.as_slice(),
+                        bin_proto::bitstream_io::BigEndian,
+                    ),
+                    bin_proto::ByteOrder::BigEndian,
+                    &mut (),
+                ))
+                .unwrap();
+            })
+        }
     }
 
     mod bench_deku {
@@ -2,27 +2,109 @@ use proc_macro2::{Span, TokenStream};
 use syn::{parse::Parser, punctuated::Punctuated, spanned::Spanned, token::Add, Error, Result};
 
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Attrs {
     pub discriminant_type: Option<syn::Type>,
     pub discriminant: Option<syn::Expr>,
+    pub discriminant_mask: Option<syn::Expr>,
+    pub discriminant_base: Option<i64>,
+    pub inner_tag: bool,
+    pub added_in: Option<u32>,
+    pub removed_in: Option<u32>,
     pub ctx: Option<syn::Type>,
     pub ctx_bounds: Option<Punctuated<syn::TypeParamBound, Add>>,
+    pub crate_path: Option<syn::Path>,
     pub write_value: Option<syn::Expr>,
     pub bits: Option<u32>,
+    pub pad_bits: Option<u32>,
+    pub align_before: bool,
+    pub read_skip: Option<u32>,
     pub flexible_array_member: bool,
+    pub present_if_remaining: bool,
+    pub present_if: Option<syn::Expr>,
+    pub remaining: bool,
+    pub count: Option<u32>,
+    pub length: Option<syn::Expr>,
     pub tag: Option<Tag>,
+    pub tag_adjust: Option<i64>,
+    pub tag_scale: Option<u32>,
+    pub condition: Option<syn::Expr>,
+    pub default: Option<syn::Expr>,
+    pub checksum: Option<syn::Type>,
+    pub lsb0: bool,
+    pub record_offsets: bool,
+    pub total_bits: Option<u32>,
+    pub partial_read: bool,
+    pub pod: bool,
+    pub header: Option<Punctuated<syn::Ident, syn::Token![,]>>,
+    pub builder: Option<syn::Path>,
+    pub byte_order: Option<ByteOrder>,
+    pub float_policy: Option<FloatPolicy>,
+}
+
+#[derive(Clone, Copy)]
+pub enum FloatPolicy {
+    RejectNan,
+    RejectNonFinite,
+}
+
+#[derive(Clone, Copy)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+    Native,
 }
 
 pub enum Tag {
     External(syn::Expr),
     Prepend {
-        typ: syn::Type,
+        typ: Box<syn::Type>,
         write_value: syn::Expr,
     },
 }
 
 impl Attrs {
-    #[allow(clippy::too_many_lines)]
+    pub fn validate_struct(&self, span: Span) -> Result<()> {
+        unexpected(
+            span,
+            "struct",
+            &[
+                ("discriminant_type", self.discriminant_type.is_some()),
+                ("discriminant", self.discriminant.is_some()),
+                ("discriminant_mask", self.discriminant_mask.is_some()),
+                ("discriminant_base", self.discriminant_base.is_some()),
+                ("inner_tag", self.inner_tag),
+                ("added_in", self.added_in.is_some()),
+                ("removed_in", self.removed_in.is_some()),
+                ("write_value", self.write_value.is_some()),
+                ("bits", self.bits.is_some()),
+                ("pad_bits", self.pad_bits.is_some()),
+                ("align_before", self.align_before),
+                ("read_skip", self.read_skip.is_some()),
+                ("flexible_array_member", self.flexible_array_member),
+                ("present_if_remaining", self.present_if_remaining),
+                ("present_if", self.present_if.is_some()),
+                ("remaining", self.remaining),
+                ("count", self.count.is_some()),
+                ("length", self.length.is_some()),
+                ("tag", self.tag.is_some()),
+                ("tag_adjust", self.tag_adjust.is_some()),
+                ("tag_scale", self.tag_scale.is_some()),
+                ("condition", self.condition.is_some()),
+                ("default", self.default.is_some()),
+                ("checksum", self.checksum.is_some()),
+                ("float_policy", self.float_policy.is_some()),
+            ],
+        )?;
+        exclusive(
+            span,
+            &[
+                ("ctx", self.ctx.is_some()),
+                ("ctx_bounds", self.ctx_bounds.is_some()),
+            ],
+        )
+    }
+
     pub fn validate_enum(&self, span: Span) -> Result<()> {
         if self.discriminant_type.is_none() {
             return Err(Error::new(
@@ -30,109 +112,164 @@ impl Attrs {
                 "expected discriminant_type attribute for enum",
             ));
         }
-        if self.discriminant.is_some() {
-            return Err(Error::new(
-                span,
-                "unexpected discriminant attribute for enum",
-            ));
-        }
-        if self.ctx.is_some() && self.ctx_bounds.is_some() {
-            return Err(Error::new(
-                span,
-                "cannot specify ctx and ctx_bounds simultaneously",
-            ));
-        }
-        if self.write_value.is_some() {
-            return Err(Error::new(
-                span,
-                "unexpected write_value attribute for enum",
-            ));
-        }
-        if self.flexible_array_member {
-            return Err(Error::new(
-                span,
-                "unexpected flexible_array_member attribute for enum",
-            ));
-        }
-        if self.tag.is_some() {
-            return Err(Error::new(span, "unexpected tag attribute for enum"));
-        }
-        Ok(())
+        unexpected(
+            span,
+            "enum",
+            &[
+                ("discriminant", self.discriminant.is_some()),
+                ("inner_tag", self.inner_tag),
+                ("added_in", self.added_in.is_some()),
+                ("removed_in", self.removed_in.is_some()),
+                ("write_value", self.write_value.is_some()),
+                ("pad_bits", self.pad_bits.is_some()),
+                ("align_before", self.align_before),
+                ("read_skip", self.read_skip.is_some()),
+                ("flexible_array_member", self.flexible_array_member),
+                ("present_if_remaining", self.present_if_remaining),
+                ("present_if", self.present_if.is_some()),
+                ("remaining", self.remaining),
+                ("count", self.count.is_some()),
+                ("length", self.length.is_some()),
+                ("tag", self.tag.is_some()),
+                ("tag_adjust", self.tag_adjust.is_some()),
+                ("tag_scale", self.tag_scale.is_some()),
+                ("condition", self.condition.is_some()),
+                ("default", self.default.is_some()),
+                ("checksum", self.checksum.is_some()),
+                ("bitfield_packing", self.lsb0),
+                ("record_offsets", self.record_offsets),
+                ("total_bits", self.total_bits.is_some()),
+                ("partial_read", self.partial_read),
+                ("pod", self.pod),
+                ("header", self.header.is_some()),
+                ("builder", self.builder.is_some()),
+                ("float_policy", self.float_policy.is_some()),
+            ],
+        )?;
+        exclusive(
+            span,
+            &[
+                ("ctx", self.ctx.is_some()),
+                ("ctx_bounds", self.ctx_bounds.is_some()),
+            ],
+        )
     }
 
     pub fn validate_variant(&self, span: Span) -> Result<()> {
-        if self.discriminant_type.is_some() {
-            return Err(Error::new(
-                span,
-                "unexpected discriminant_type attribute for variant",
-            ));
-        }
-        if self.ctx.is_some() {
-            return Err(Error::new(span, "unexpected ctx attribute for variant"));
-        }
-        if self.ctx_bounds.is_some() {
-            return Err(Error::new(
-                span,
-                "unexpected ctx_bounds attribute for variant",
-            ));
-        }
-        if self.write_value.is_some() {
-            return Err(Error::new(
-                span,
-                "unexpected write_value attribute for variant",
-            ));
-        }
-        if self.bits.is_some() {
-            return Err(Error::new(span, "unexpected bits attribute for variant"));
-        }
-        if self.flexible_array_member {
-            return Err(Error::new(
-                span,
-                "unexpected flexible_array_member attribute for variant",
-            ));
-        }
-        if self.tag.is_some() {
-            return Err(Error::new(span, "unexpected tag attribute for variant"));
-        }
-        Ok(())
+        unexpected(
+            span,
+            "variant",
+            &[
+                ("discriminant_type", self.discriminant_type.is_some()),
+                ("discriminant_mask", self.discriminant_mask.is_some()),
+                ("discriminant_base", self.discriminant_base.is_some()),
+                ("ctx", self.ctx.is_some()),
+                ("ctx_bounds", self.ctx_bounds.is_some()),
+                ("crate", self.crate_path.is_some()),
+                ("write_value", self.write_value.is_some()),
+                ("bits", self.bits.is_some()),
+                ("pad_bits", self.pad_bits.is_some()),
+                ("align_before", self.align_before),
+                ("read_skip", self.read_skip.is_some()),
+                ("flexible_array_member", self.flexible_array_member),
+                ("present_if_remaining", self.present_if_remaining),
+                ("present_if", self.present_if.is_some()),
+                ("remaining", self.remaining),
+                ("count", self.count.is_some()),
+                ("length", self.length.is_some()),
+                ("tag", self.tag.is_some()),
+                ("tag_adjust", self.tag_adjust.is_some()),
+                ("tag_scale", self.tag_scale.is_some()),
+                ("condition", self.condition.is_some()),
+                ("default", self.default.is_some()),
+                ("checksum", self.checksum.is_some()),
+                ("bitfield_packing", self.lsb0),
+                ("record_offsets", self.record_offsets),
+                ("total_bits", self.total_bits.is_some()),
+                ("partial_read", self.partial_read),
+                ("pod", self.pod),
+                ("header", self.header.is_some()),
+                ("builder", self.builder.is_some()),
+                ("byte_order", self.byte_order.is_some()),
+                ("float_policy", self.float_policy.is_some()),
+            ],
+        )
     }
 
-    pub fn validate_field(&self, span: Span) -> Result<()> {
-        if self.discriminant_type.is_some() {
-            return Err(Error::new(
-                span,
-                "unexpected discriminant_type attribute for field",
-            ));
-        }
-        if self.discriminant.is_some() {
+    pub fn validate_field(&self, ty: &syn::Type, span: Span) -> Result<()> {
+        unexpected(
+            span,
+            "field",
+            &[
+                ("discriminant_type", self.discriminant_type.is_some()),
+                ("discriminant", self.discriminant.is_some()),
+                ("discriminant_mask", self.discriminant_mask.is_some()),
+                ("discriminant_base", self.discriminant_base.is_some()),
+                ("inner_tag", self.inner_tag),
+                ("added_in", self.added_in.is_some()),
+                ("removed_in", self.removed_in.is_some()),
+                ("ctx", self.ctx.is_some()),
+                ("ctx_bounds", self.ctx_bounds.is_some()),
+                ("crate", self.crate_path.is_some()),
+                ("bitfield_packing", self.lsb0),
+                ("record_offsets", self.record_offsets),
+                ("total_bits", self.total_bits.is_some()),
+                ("partial_read", self.partial_read),
+                ("pod", self.pod),
+                ("header", self.header.is_some()),
+                ("builder", self.builder.is_some()),
+            ],
+        )?;
+        exclusive(
+            span,
+            &[
+                ("bits", self.bits.is_some()),
+                ("count", self.count.is_some()),
+                ("flexible_array_member", self.flexible_array_member),
+                ("length", self.length.is_some()),
+                ("present_if_remaining", self.present_if_remaining),
+                ("present_if", self.present_if.is_some()),
+                ("remaining", self.remaining),
+                ("tag", self.tag.is_some()),
+            ],
+        )?;
+        requires(
+            span,
+            &[
+                (
+                    "default",
+                    self.default.is_some(),
+                    "condition",
+                    self.condition.is_some(),
+                ),
+                (
+                    "tag_adjust",
+                    self.tag_adjust.is_some(),
+                    "tag",
+                    self.tag.is_some(),
+                ),
+                (
+                    "tag_scale",
+                    self.tag_scale.is_some(),
+                    "tag",
+                    self.tag.is_some(),
+                ),
+            ],
+        )?;
+        if self.condition.is_some() && self.default.is_none() && !is_option(ty) {
             return Err(Error::new(
                 span,
-                "unexpected discriminant attribute for field",
+                "condition attribute requires a default attribute, unless the field is an Option",
             ));
         }
-        if self.ctx.is_some() {
-            return Err(Error::new(span, "unexpected ctx attribute for variant"));
-        }
-        if self.ctx_bounds.is_some() {
-            return Err(Error::new(
-                span,
-                "unexpected ctx_bounds attribute for variant",
-            ));
-        }
-        if [
-            self.bits.is_some(),
-            self.flexible_array_member,
-            self.tag.is_some(),
-        ]
-        .iter()
-        .filter(|b| **b)
-        .count()
-            > 1
+        if self
+            .read_skip
+            .map_or(false, |read_skip| read_skip.checked_mul(8).is_none())
         {
-            return Err(Error::new(
-                span,
-                "bits, flexible_array_member, and tag are mutually-exclusive attributes",
-            ));
+            return Err(Error::new(span, "read_skip is too large"));
+        }
+        if self.tag_scale == Some(0) {
+            return Err(Error::new(span, "tag_scale must not be zero"));
         }
         Ok(())
     }
@@ -143,6 +280,92 @@ impl Attrs {
             .map(|ctx| quote!(#ctx))
             .unwrap_or(quote!(__Ctx))
     }
+
+    /// The scale and offset from a prefix to the tag, set by the `tag_scale`
+    /// and `tag_adjust` attributes, if either is present.
+    pub fn tag_transform(&self) -> Option<(u32, i64)> {
+        if self.tag_scale.is_none() && self.tag_adjust.is_none() {
+            return None;
+        }
+        Some((self.tag_scale.unwrap_or(1), self.tag_adjust.unwrap_or(0)))
+    }
+
+    /// The `FloatPolicy` set by the `float_policy` attribute, if any.
+    pub fn float_policy(&self) -> Option<TokenStream> {
+        self.float_policy.map(|policy| match policy {
+            FloatPolicy::RejectNan => quote!(__bin_proto::FloatPolicy::RejectNan),
+            FloatPolicy::RejectNonFinite => quote!(__bin_proto::FloatPolicy::RejectNonFinite),
+        })
+    }
+
+    /// Shadows `__byte_order` with the byte order set by the `byte_order`
+    /// attribute, if any.
+    pub fn byte_order_override(&self) -> TokenStream {
+        match self.byte_order {
+            Some(ByteOrder::LittleEndian) => {
+                quote!(let __byte_order = __bin_proto::ByteOrder::LittleEndian;)
+            }
+            Some(ByteOrder::BigEndian) => {
+                quote!(let __byte_order = __bin_proto::ByteOrder::BigEndian;)
+            }
+            Some(ByteOrder::Native) => quote!(let __byte_order = __bin_proto::ByteOrder::NATIVE;),
+            None => quote!(),
+        }
+    }
+}
+
+/// Fails on the first attribute in `attrs` that is present but does not apply
+/// to a `kind`.
+fn unexpected(span: Span, kind: &str, attrs: &[(&str, bool)]) -> Result<()> {
+    if let Some((name, _)) = attrs.iter().find(|(_, present)| *present) {
+        return Err(Error::new(
+            span,
+            format!("unexpected {name} attribute for {kind}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Fails if more than one of `attrs` is present.
+fn exclusive(span: Span, attrs: &[(&str, bool)]) -> Result<()> {
+    if attrs.iter().filter(|(_, present)| *present).count() > 1 {
+        let names: Vec<_> = attrs.iter().map(|(name, _)| *name).collect();
+        let (last, init) = names.split_last().expect("at least two attributes");
+        let joined = match init {
+            [only] => format!("{only} and {last}"),
+            _ => format!("{}, and {last}", init.join(", ")),
+        };
+        return Err(Error::new(
+            span,
+            format!("{joined} are mutually-exclusive attributes"),
+        ));
+    }
+    Ok(())
+}
+
+/// Fails on the first attribute in `attrs` that is present without the
+/// attribute it depends on.
+fn requires(span: Span, attrs: &[(&str, bool, &str, bool)]) -> Result<()> {
+    if let Some((name, _, required, _)) = attrs
+        .iter()
+        .find(|(_, present, _, required)| *present && !*required)
+    {
+        return Err(Error::new(
+            span,
+            format!("{name} attribute requires a {required} attribute"),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `ty` is spelled as an `Option`, which conditional fields default to
+/// `None`.
+fn is_option(ty: &syn::Type) -> bool {
+    matches!(
+        ty,
+        syn::Type::Path(syn::TypePath { qself: None, path })
+            if path.segments.last().map_or(false, |segment| segment.ident == "Option")
+    )
 }
 
 impl TryFrom<&[syn::Attribute]> for Attrs {
@@ -172,6 +395,12 @@ impl TryFrom<&[syn::Attribute]> for Attrs {
                         .get_ident()
                     {
                         Some(ident) => match ident.to_string().as_str() {
+                            "crate" => {
+                                attribs.crate_path = Some(meta_name_value_to_parse(name_value)?);
+                            }
+                            "builder" => {
+                                attribs.builder = Some(meta_name_value_to_parse(name_value)?);
+                            }
                             "discriminant_type" => {
                                 attribs.discriminant_type =
                                     Some(meta_name_value_to_parse(name_value)?);
@@ -179,12 +408,42 @@ impl TryFrom<&[syn::Attribute]> for Attrs {
                             "discriminant" => {
                                 attribs.discriminant = Some(meta_name_value_to_parse(name_value)?);
                             }
+                            "discriminant_mask" => {
+                                attribs.discriminant_mask =
+                                    Some(meta_name_value_to_parse(name_value)?);
+                            }
+                            "discriminant_base" => {
+                                attribs.discriminant_base =
+                                    Some(meta_name_value_to_i64(name_value)?);
+                            }
                             "ctx" => attribs.ctx = Some(meta_name_value_to_parse(name_value)?),
                             "ctx_bounds" => {
                                 attribs.ctx_bounds =
                                     Some(meta_name_value_to_punctuated(name_value)?);
                             }
                             "bits" => attribs.bits = Some(meta_name_value_to_u32(name_value)?),
+                            "count" => attribs.count = Some(meta_name_value_to_u32(name_value)?),
+                            "total_bits" => {
+                                attribs.total_bits = Some(meta_name_value_to_u32(name_value)?);
+                            }
+                            "tag_adjust" => {
+                                attribs.tag_adjust = Some(meta_name_value_to_i64(name_value)?);
+                            }
+                            "tag_scale" => {
+                                attribs.tag_scale = Some(meta_name_value_to_u32(name_value)?);
+                            }
+                            "added_in" => {
+                                attribs.added_in = Some(meta_name_value_to_u32(name_value)?);
+                            }
+                            "removed_in" => {
+                                attribs.removed_in = Some(meta_name_value_to_u32(name_value)?);
+                            }
+                            "pad_bits" => {
+                                attribs.pad_bits = Some(meta_name_value_to_u32(name_value)?);
+                            }
+                            "read_skip" => {
+                                attribs.read_skip = Some(meta_name_value_to_u32(name_value)?);
+                            }
                             "write_value" => {
                                 attribs.write_value = Some(meta_name_value_to_parse(name_value)?);
                             }
@@ -192,6 +451,32 @@ impl TryFrom<&[syn::Attribute]> for Attrs {
                                 attribs.tag =
                                     Some(Tag::External(meta_name_value_to_parse(name_value)?));
                             }
+                            "length" => {
+                                attribs.length = Some(meta_name_value_to_parse(name_value)?);
+                            }
+                            "present_if" => {
+                                attribs.present_if = Some(meta_name_value_to_parse(name_value)?);
+                            }
+                            "condition" => {
+                                attribs.condition = Some(meta_name_value_to_parse(name_value)?);
+                            }
+                            "default" => {
+                                attribs.default = Some(meta_name_value_to_parse(name_value)?);
+                            }
+                            "checksum" => {
+                                attribs.checksum = Some(meta_name_value_to_parse(name_value)?);
+                            }
+                            "bitfield_packing" => {
+                                attribs.lsb0 = meta_name_value_to_lsb0(name_value)?;
+                            }
+                            "float_policy" => {
+                                attribs.float_policy =
+                                    Some(meta_name_value_to_float_policy(name_value)?);
+                            }
+                            "byte_order" => {
+                                attribs.byte_order =
+                                    Some(meta_name_value_to_byte_order(name_value)?);
+                            }
                             _ => return Err(Error::new(ident.span(), "unrecognised attribute")),
                         },
                         None => return Err(Error::new(meta.span(), "failed to parse attribute")),
@@ -199,6 +484,13 @@ impl TryFrom<&[syn::Attribute]> for Attrs {
                     syn::NestedMeta::Meta(syn::Meta::Path(path)) => match path.get_ident() {
                         Some(ident) => match ident.to_string().as_str() {
                             "flexible_array_member" => attribs.flexible_array_member = true,
+                            "align_before" => attribs.align_before = true,
+                            "present_if_remaining" => attribs.present_if_remaining = true,
+                            "remaining" => attribs.remaining = true,
+                            "inner_tag" => attribs.inner_tag = true,
+                            "record_offsets" => attribs.record_offsets = true,
+                            "partial_read" => attribs.partial_read = true,
+                            "pod" => attribs.pod = true,
                             _ => return Err(Error::new(ident.span(), "unrecognised attribute")),
                         },
                         None => {
@@ -208,6 +500,26 @@ impl TryFrom<&[syn::Attribute]> for Attrs {
                             ));
                         }
                     },
+                    syn::NestedMeta::Meta(syn::Meta::List(list))
+                        if list.path.is_ident("header") =>
+                    {
+                        for nested in &list.nested {
+                            match nested {
+                                syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                                    if name_value.path.is_ident("fields") =>
+                                {
+                                    attribs.header =
+                                        Some(meta_name_value_to_punctuated(name_value)?);
+                                }
+                                _ => {
+                                    return Err(Error::new(nested.span(), "unrecognized attribute"))
+                                }
+                            }
+                        }
+                        if attribs.header.is_none() {
+                            return Err(Error::new(list.span(), "Header lacks fields."));
+                        }
+                    }
                     syn::NestedMeta::Meta(syn::Meta::List(list)) => {
                         let mut typ = None;
                         let mut write_value = None;
@@ -244,7 +556,7 @@ impl TryFrom<&[syn::Attribute]> for Attrs {
                         match (typ, write_value) {
                             (Some(typ), Some(value)) => {
                                 attribs.tag = Some(Tag::Prepend {
-                                    typ,
+                                    typ: Box::new(typ),
                                     write_value: value,
                                 });
                             }
@@ -252,7 +564,7 @@ impl TryFrom<&[syn::Attribute]> for Attrs {
                         }
                     }
                     _ => return Err(Error::new(meta_list.span(), "unrecognised attribute")),
-                };
+                }
             }
         }
         Ok(attribs)
@@ -261,7 +573,8 @@ impl TryFrom<&[syn::Attribute]> for Attrs {
 
 fn meta_name_value_to_parse<T: syn::parse::Parse>(name_value: &syn::MetaNameValue) -> Result<T> {
     match name_value.lit {
-        syn::Lit::Str(ref s) => syn::parse_str::<T>(s.value().as_str())
+        syn::Lit::Str(ref s) => s
+            .parse::<T>()
             .map_err(|e| Error::new(name_value.span(), format!("Failed to parse: {e}"))),
 
         _ => Err(Error::new(name_value.span(), "Expected string")),
@@ -277,6 +590,58 @@ fn meta_name_value_to_u32(name_value: &syn::MetaNameValue) -> Result<u32> {
     }
 }
 
+fn meta_name_value_to_i64(name_value: &syn::MetaNameValue) -> Result<i64> {
+    match name_value.lit {
+        syn::Lit::Int(ref i) => i
+            .base10_parse()
+            .map_err(|e| Error::new(name_value.span(), format!("Failed to parse i64: {e}"))),
+        _ => Err(Error::new(name_value.span(), "Expected integer")),
+    }
+}
+
+fn meta_name_value_to_lsb0(name_value: &syn::MetaNameValue) -> Result<bool> {
+    match name_value.lit {
+        syn::Lit::Str(ref s) => match s.value().as_str() {
+            "msb0" => Ok(false),
+            "lsb0" => Ok(true),
+            _ => Err(Error::new(
+                name_value.span(),
+                "Expected \"msb0\" or \"lsb0\"",
+            )),
+        },
+        _ => Err(Error::new(name_value.span(), "Expected string")),
+    }
+}
+
+fn meta_name_value_to_float_policy(name_value: &syn::MetaNameValue) -> Result<FloatPolicy> {
+    match name_value.lit {
+        syn::Lit::Str(ref s) => match s.value().as_str() {
+            "reject_nan" => Ok(FloatPolicy::RejectNan),
+            "reject_nonfinite" => Ok(FloatPolicy::RejectNonFinite),
+            _ => Err(Error::new(
+                name_value.span(),
+                "Expected \"reject_nan\" or \"reject_nonfinite\"",
+            )),
+        },
+        _ => Err(Error::new(name_value.span(), "Expected string")),
+    }
+}
+
+fn meta_name_value_to_byte_order(name_value: &syn::MetaNameValue) -> Result<ByteOrder> {
+    match name_value.lit {
+        syn::Lit::Str(ref s) => match s.value().as_str() {
+            "le" => Ok(ByteOrder::LittleEndian),
+            "be" => Ok(ByteOrder::BigEndian),
+            "native" => Ok(ByteOrder::Native),
+            _ => Err(Error::new(
+                name_value.span(),
+                "Expected \"le\", \"be\" or \"native\"",
+            )),
+        },
+        _ => Err(Error::new(name_value.span(), "Expected string")),
+    }
+}
+
 fn meta_name_value_to_punctuated<T: syn::parse::Parse, P: syn::parse::Parse>(
     name_value: &syn::MetaNameValue,
 ) -> Result<Punctuated<T, P>> {
@@ -1,11 +1,14 @@
-use crate::{attr::Attrs, codegen, plan};
+use crate::{attr::Attrs, codegen, codegen::trait_impl::with_crate_path, plan};
 use proc_macro2::{Span, TokenStream};
 
+/// Mirrors `bin_proto::MAX_EXPECTED_DISCRIMINANTS`.
+const MAX_EXPECTED_DISCRIMINANTS: usize = 16;
+
 pub fn read_discriminant(attribs: &Attrs) -> TokenStream {
     if let Some(bits) = attribs.bits {
-        quote!(::bin_proto::BitFieldRead::read(__io_reader, __byte_order, __ctx, #bits))
+        quote!(__bin_proto::BitFieldRead::read(__io_reader, __byte_order, __ctx, #bits))
     } else {
-        quote!(::bin_proto::ProtocolRead::read(
+        quote!(__bin_proto::ProtocolRead::read(
             __io_reader,
             __byte_order,
             __ctx
@@ -13,11 +16,60 @@ pub fn read_discriminant(attribs: &Attrs) -> TokenStream {
     }
 }
 
-pub fn write_discriminant(attribs: &Attrs) -> TokenStream {
+/// Generates an inherent `read_discriminant`, which reads only the
+/// discriminant of the enum.
+pub fn read_discriminant_impl(
+    ast: &syn::DeriveInput,
+    plan: &plan::Enum,
+    attribs: &Attrs,
+) -> TokenStream {
+    let name = &ast.ident;
+    let discriminant_ty = &plan.discriminant_ty;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let read_discriminant = read_discriminant(attribs);
+    let byte_order = attribs.byte_order_override();
+    let read_trait = if attribs.bits.is_some() {
+        quote!(__bin_proto::BitFieldRead)
+    } else {
+        quote!(__bin_proto::ProtocolRead)
+    };
+    let (ctx_generics, ctx_ty) = if let Some(ctx) = &attribs.ctx {
+        (quote!(), quote!(#ctx))
+    } else {
+        let bounds = attribs.ctx_bounds.iter();
+        (quote!(<__Ctx #( : #bounds )*>), quote!(__Ctx))
+    };
+
+    with_crate_path(
+        attribs,
+        &quote!(
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Reads the discriminant of this enum, without reading the rest of
+            /// it.
+            #[allow(unused)]
+            pub fn read_discriminant #ctx_generics (
+                __io_reader: &mut dyn __bin_proto::BitRead,
+                __byte_order: __bin_proto::ByteOrder,
+                __ctx: &mut #ctx_ty,
+            ) -> __bin_proto::Result<#discriminant_ty>
+            where
+                #discriminant_ty: #read_trait<#ctx_ty>,
+            {
+                #byte_order
+                #read_discriminant
+            }
+        }
+        ),
+    )
+}
+
+pub fn write_discriminant(plan: &plan::Enum, attribs: &Attrs) -> TokenStream {
+    let inner_tag_check = inner_tag_check(plan);
     let write_tag = if let Some(bits) = attribs.bits {
-        quote!(::bin_proto::BitFieldWrite::write(&__tag, __io_writer, __byte_order, __ctx, #bits))
+        quote!(__bin_proto::BitFieldWrite::write(&__tag, __io_writer, __byte_order, __ctx, #bits))
     } else {
-        quote!(::bin_proto::ProtocolWrite::write(
+        quote!(__bin_proto::ProtocolWrite::write(
             &__tag,
             __io_writer,
             __byte_order,
@@ -25,30 +77,84 @@ pub fn write_discriminant(attribs: &Attrs) -> TokenStream {
         ))
     };
     quote!({
-        let __tag = <Self as ::bin_proto::Discriminable>::discriminant(self);
+        #inner_tag_check
+        let __tag = <Self as __bin_proto::Discriminable>::discriminant(self);
         #write_tag?;
     })
 }
 
-pub fn write_variant_fields(plan: &plan::Enum) -> TokenStream {
+/// Checks that the discriminants of inner enums do not overlap the bits
+/// selected by the discriminant mask, which would corrupt the shared tag.
+fn inner_tag_check(plan: &plan::Enum) -> TokenStream {
+    let discriminant_ty = &plan.discriminant_ty;
+    let mask = match &plan.discriminant_mask {
+        Some(mask) => mask,
+        None => return TokenStream::new(),
+    };
+    let checks: Vec<_> = plan
+        .variants
+        .iter()
+        .filter(|variant| variant.inner_tag)
+        .map(|variant| {
+            let variant_name = &variant.ident;
+            let fields_pattern = bind_fields_pattern(variant_name, &variant.fields);
+            let binding = field_binding(&variant.fields);
+            quote!(
+                Self::#fields_pattern => {
+                    let __inner = ::std::convert::Into::<#discriminant_ty>::into(
+                        __bin_proto::Discriminable::discriminant(#binding),
+                    );
+                    if __inner & (#mask) != 0 {
+                        return ::std::result::Result::Err(__bin_proto::Error::DiscriminantOverflow);
+                    }
+                }
+            )
+        })
+        .collect();
+    if checks.is_empty() {
+        return TokenStream::new();
+    }
+    quote!(
+        #[allow(unreachable_patterns)]
+        match self {
+            #(#checks)*
+            _ => {}
+        }
+    )
+}
+
+pub fn write_variant_fields(plan: &plan::Enum, attribs: &Attrs) -> TokenStream {
     let variant_match_branches: Vec<_> = plan
         .variants
         .iter()
         .map(|variant| {
             let variant_name = &variant.ident;
             let fields_pattern = bind_fields_pattern(variant_name, &variant.fields);
-            let writes = codegen::writes(&variant.fields, false);
+            let version_check = version_check(variant);
+            let writes = if variant.inner_tag {
+                let binding = field_binding(&variant.fields);
+                quote!(__bin_proto::UntaggedWrite::write(#binding, __io_writer, __byte_order, __ctx)?;)
+            } else {
+                codegen::writes(&variant.fields, attribs, false)
+            };
 
             quote!(Self :: #fields_pattern => {
+                #version_check
                 #writes
             })
         })
         .collect();
 
+    // An enum without variants cannot be constructed, so there is nothing to
+    // write.
+    if variant_match_branches.is_empty() {
+        return quote!(match *self {});
+    }
     quote!(
         match *self {
             #(#variant_match_branches,)*
         }
+        Ok(())
     )
 }
 
@@ -70,9 +176,22 @@ pub fn variant_discriminant(plan: &plan::Enum, attribs: &Attrs) -> TokenStream {
                     const _: () = ::std::assert!(#discriminant_expr < (1 as #discriminant_ty) << #field_width, #error_message);
                     #discriminant_expr
                 )
+            } else if plan.has_string_discriminant() {
+                quote!(::std::string::String::from(#discriminant_expr))
             } else {
                 quote!(#discriminant_expr)
             };
+            let write_variant = if variant.inner_tag {
+                let binding = field_binding(&variant.fields);
+                quote!(
+                    (#write_variant)
+                        | ::std::convert::Into::<#discriminant_ty>::into(
+                            __bin_proto::Discriminable::discriminant(#binding),
+                        )
+                )
+            } else {
+                write_variant
+            };
 
             quote!(Self :: #fields_pattern => {
                 #write_variant
@@ -84,34 +203,149 @@ pub fn variant_discriminant(plan: &plan::Enum, attribs: &Attrs) -> TokenStream {
     })
 }
 
-pub fn read_variant_fields(plan: &plan::Enum, attribs: &Attrs) -> TokenStream {
+pub fn variant_name(plan: &plan::Enum) -> TokenStream {
+    let variant_match_branches = plan.variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        let name = variant_name.to_string();
+        quote!(Self::#variant_name { .. } => #name)
+    });
+    quote!(match *self {
+        #(#variant_match_branches,)*
+    })
+}
+
+/// Matches `__discriminant` against the discriminants of the variants, reading
+/// the fields of the matching variant.
+pub fn match_discriminant(plan: &plan::Enum, attribs: &Attrs) -> TokenStream {
+    let ctx_ty = attribs.ctx_ty();
     let discriminant_match_branches = plan.variants.iter().map(|variant| {
         let variant_name = &variant.ident;
-        let discriminant_literal = &variant.discriminant_value;
-        let (reader, initializer) = codegen::reads(&variant.fields, attribs);
+        let discriminant_value = &variant.discriminant_value;
+        let pattern = if plan::is_literal_discriminant(discriminant_value) {
+            quote!(#discriminant_value)
+        } else {
+            quote!(__value if __value == (#discriminant_value))
+        };
+        let (reader, initializer) = if variant.inner_tag {
+            let mask = &plan.discriminant_mask;
+            let read = quote!(__bin_proto::TaggedRead::<_, #ctx_ty>::read(
+                __io_reader,
+                __byte_order,
+                __ctx,
+                __discriminant & !(#mask),
+            ));
+            let read = if variant
+                .fields
+                .iter()
+                .any(|field| codegen::is_recursive(&field.ty, &plan.ident))
+            {
+                codegen::read_recursive(&read)
+            } else {
+                read
+            };
+            let read = quote!(#read?);
+            match variant.fields {
+                syn::Fields::Named(ref fields_named) => {
+                    let field_name = &fields_named.named[0].ident;
+                    (quote!(), quote!({ #field_name: #read }))
+                }
+                _ => (quote!(), quote!((#read))),
+            }
+        } else {
+            codegen::reads(&variant.fields, &plan.ident, attribs)
+        };
+
+        let version_check = version_check(variant);
 
         quote!(
-            #discriminant_literal => {
+            #pattern => {
+                #version_check
                 #reader
-                Self::#variant_name #initializer
+                Ok(Self::#variant_name #initializer)
             }
         )
     });
 
+    let discriminant = if let Some(mask) = &plan.discriminant_mask {
+        quote!(__discriminant & (#mask))
+    } else {
+        quote!(__discriminant)
+    };
+
+    let enum_name = plan.ident.to_string();
+    let expected = expected_discriminants(plan);
+
+    quote!(
+        match #discriminant {
+            #(#discriminant_match_branches,)*
+            unknown_discriminant => Err(__bin_proto::Error::UnknownEnumDiscriminant {
+                enum_name: #enum_name,
+                discriminant: ::std::format!("{:?}", unknown_discriminant),
+                expected: #expected,
+            }),
+        }
+    )
+}
+
+/// The discriminants of the variants, rendered for
+/// `Error::UnknownEnumDiscriminant`.
+fn expected_discriminants(plan: &plan::Enum) -> TokenStream {
+    let listed = plan
+        .variants
+        .iter()
+        .take(MAX_EXPECTED_DISCRIMINANTS)
+        .map(|variant| &variant.discriminant_value);
+    let ellipsis = (plan.variants.len() > MAX_EXPECTED_DISCRIMINANTS)
+        .then(|| quote!(::std::string::String::from("...")));
+    quote!(::std::vec![
+        #( ::std::format!("{:?}", #listed), )*
+        #ellipsis
+    ])
+}
+
+pub fn read_variant_fields(plan: &plan::Enum, attribs: &Attrs) -> TokenStream {
+    let discriminant_ty = &plan.discriminant_ty;
+    let match_discriminant = match_discriminant(plan, attribs);
     quote!(
         {
-            match __tag.try_into().map_err(|_| ::bin_proto::Error::TagConvert)? {
-                #(#discriminant_match_branches,)*
-                unknown_discriminant => {
-                    return Err(::bin_proto::Error::UnknownEnumDiscriminant(
-                        ::std::format!("{:?}", unknown_discriminant),
-                    ));
-                },
-            }
+            let __discriminant: #discriminant_ty =
+                __tag.try_into().map_err(|_| __bin_proto::Error::TagConvert)?;
+            #match_discriminant
+        }
+    )
+}
+
+/// Rejects a variant that is outside the range of protocol versions it is
+/// supported in.
+fn version_check(variant: &plan::EnumVariant) -> TokenStream {
+    if variant.added_in.is_none() && variant.removed_in.is_none() {
+        return quote!();
+    }
+    let added_in = variant.added_in.iter();
+    let removed_in = variant.removed_in.iter();
+    let variant_name = variant.ident.to_string();
+    quote!(
+        let __version = __bin_proto::ProtocolVersion::version(&*__ctx);
+        if !::std::ops::RangeBounds::contains(&( #( #added_in )* .. #( #removed_in )* ), &__version) {
+            return ::std::result::Result::Err(__bin_proto::Error::VersionMismatch {
+                variant: #variant_name,
+                version: __version,
+            });
         }
     )
 }
 
+/// The binding of the single field of a variant, as bound by
+/// `bind_fields_pattern`.
+fn field_binding(fields: &syn::Fields) -> TokenStream {
+    if let syn::Fields::Named(ref fields_named) = *fields {
+        let field_name = &fields_named.named[0].ident;
+        quote!(#field_name)
+    } else {
+        quote!(field_0)
+    }
+}
+
 pub fn bind_fields_pattern(parent_name: &syn::Ident, fields: &syn::Fields) -> TokenStream {
     match *fields {
         syn::Fields::Named(ref fields_named) => {
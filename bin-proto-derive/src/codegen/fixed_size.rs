@@ -0,0 +1,149 @@
+use crate::{attr::Attrs, codegen::trait_impl::with_crate_path, plan};
+use proc_macro2::TokenStream;
+use syn::parse_quote;
+
+/// The size of a list of fields, as per-field size expressions and the types
+/// those expressions require to be `FixedSize`.
+struct FieldsSize {
+    sizes: Vec<TokenStream>,
+    bounds: Vec<syn::Type>,
+}
+
+/// Returns `None` if any field is not known to be fixed size from its
+/// attributes alone. Fields whose types are not fixed size are handled by the
+/// bounds of the generated impl instead.
+fn fields_size(fields: &syn::Fields) -> Option<FieldsSize> {
+    let mut sizes = Vec::new();
+    let mut bounds = Vec::new();
+    for field in fields {
+        let attribs = Attrs::try_from(field.attrs.as_slice()).ok()?;
+        if attribs.tag.is_some()
+            || attribs.count.is_some()
+            || attribs.length.is_some()
+            || attribs.flexible_array_member
+            || attribs.present_if_remaining
+            || attribs.present_if.is_some()
+            || attribs.remaining
+            || attribs.condition.is_some()
+            || attribs.align_before
+        {
+            return None;
+        }
+
+        let ty = &field.ty;
+        let mut size = if let Some(bits) = attribs.bits {
+            let bits = bits as usize;
+            quote!(#bits)
+        } else {
+            bounds.push(ty.clone());
+            quote!(<#ty as __bin_proto::FixedSize>::SIZE_BITS)
+        };
+        if let Some(pad_bits) = attribs.pad_bits {
+            let pad_bits = pad_bits as usize;
+            size = quote!(#pad_bits + #size);
+        }
+        if let Some(read_skip) = attribs.read_skip {
+            let bits = read_skip as usize * 8;
+            size = quote!(#bits + #size);
+        }
+        if let Some(checksum) = &attribs.checksum {
+            let output: syn::Type = parse_quote!(<#checksum as __bin_proto::Checksum>::Output);
+            size = quote!(#size + <#output as __bin_proto::FixedSize>::SIZE_BITS);
+            bounds.push(output);
+        }
+        sizes.push(size);
+    }
+    Some(FieldsSize { sizes, bounds })
+}
+
+fn impl_fixed_size(
+    ast: &syn::DeriveInput,
+    attribs: &Attrs,
+    size: &TokenStream,
+    bounds: &[syn::Type],
+) -> TokenStream {
+    let name = &ast.ident;
+    let mut generics = ast.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for bound in bounds {
+        // The higher-ranked lifetime keeps bounds on concrete types from being
+        // rejected as trivially unsatisfiable, so that the impl simply does not
+        // apply to variable-size types.
+        where_clause
+            .predicates
+            .push(parse_quote!(for<'__a> #bound: __bin_proto::FixedSize));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    with_crate_path(
+        attribs,
+        &quote!(
+            #[automatically_derived]
+            impl #impl_generics __bin_proto::FixedSize for #name #ty_generics #where_clause {
+                const SIZE_BITS: usize = #size;
+            }
+        ),
+    )
+}
+
+pub fn struct_fixed_size(
+    ast: &syn::DeriveInput,
+    strukt: &syn::DataStruct,
+    attribs: &Attrs,
+) -> TokenStream {
+    if attribs.lsb0 {
+        return TokenStream::new();
+    }
+    match fields_size(&strukt.fields) {
+        Some(FieldsSize { sizes, bounds }) => {
+            impl_fixed_size(ast, attribs, &quote!(0 #(+ #sizes)*), &bounds)
+        }
+        None => TokenStream::new(),
+    }
+}
+
+pub fn enum_fixed_size(ast: &syn::DeriveInput, plan: &plan::Enum, attribs: &Attrs) -> TokenStream {
+    if plan.variants.iter().any(|variant| variant.inner_tag) {
+        return TokenStream::new();
+    }
+    let sizes = match plan
+        .variants
+        .iter()
+        .map(|variant| fields_size(&variant.fields))
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(sizes) => sizes,
+        None => return TokenStream::new(),
+    };
+
+    // Variants can only be compared syntactically, so all variants must have
+    // the same field types and attributes.
+    let variant_size = |size: &FieldsSize| {
+        size.sizes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+    let mut sizes = sizes.into_iter();
+    let FieldsSize {
+        sizes: fields_sizes,
+        mut bounds,
+    } = match sizes.next() {
+        Some(first) if sizes.all(|size| variant_size(&size) == variant_size(&first)) => first,
+        _ => return TokenStream::new(),
+    };
+
+    let discriminant_size = if let Some(bits) = attribs.bits {
+        let bits = bits as usize;
+        quote!(#bits)
+    } else {
+        let discriminant_ty = &plan.discriminant_ty;
+        bounds.push(discriminant_ty.clone());
+        quote!(<#discriminant_ty as __bin_proto::FixedSize>::SIZE_BITS)
+    };
+    impl_fixed_size(
+        ast,
+        attribs,
+        &quote!(#discriminant_size #(+ #fields_sizes)*),
+        &bounds,
+    )
+}
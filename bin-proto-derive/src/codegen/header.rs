@@ -0,0 +1,185 @@
+use crate::{attr::Attrs, codegen::trait_impl::with_crate_path, Operation};
+use proc_macro2::TokenStream;
+use syn::{punctuated::Punctuated, spanned::Spanned, Error, Result};
+
+/// Generates the `<Name>Header` struct for `#[protocol(header(fields =
+/// "..."))]`, which holds and reads only the leading `fields` of the struct,
+/// and an inherent `read_header` that parses it.
+pub fn header(
+    ast: &syn::DeriveInput,
+    strukt: &syn::DataStruct,
+    attribs: &Attrs,
+    fields: &Punctuated<syn::Ident, syn::Token![,]>,
+) -> TokenStream {
+    match try_header(ast, strukt, attribs, fields) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn try_header(
+    ast: &syn::DeriveInput,
+    strukt: &syn::DataStruct,
+    attribs: &Attrs,
+    fields: &Punctuated<syn::Ident, syn::Token![,]>,
+) -> Result<TokenStream> {
+    let named = leading_fields(ast, strukt, attribs, fields)?;
+
+    let vis = &ast.vis;
+    let name = &ast.ident;
+    let header_name = format_ident!("{}Header", name);
+    let prefix: Punctuated<syn::Field, syn::Token![,]> =
+        named.named.iter().take(fields.len()).cloned().collect();
+
+    let header_ast = syn::DeriveInput {
+        attrs: without_header(&ast.attrs),
+        vis: vis.clone(),
+        ident: header_name.clone(),
+        generics: syn::Generics::default(),
+        data: syn::Data::Struct(syn::DataStruct {
+            struct_token: strukt.struct_token,
+            fields: syn::Fields::Named(syn::FieldsNamed {
+                brace_token: named.brace_token,
+                named: prefix.clone(),
+            }),
+            semi_token: None,
+        }),
+    };
+    let read_impl = match &header_ast.data {
+        syn::Data::Struct(header_strukt) => {
+            crate::impl_for_struct(&header_ast, header_strukt, Operation::Read)
+        }
+        _ => unreachable!(),
+    };
+
+    let definitions = prefix.iter().map(|field| {
+        let docs = field.attrs.iter().filter(|attr| attr.path.is_ident("doc"));
+        let vis = &field.vis;
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote!(#(#docs)* #vis #ident: #ty)
+    });
+    let doc = format!("The leading fields of [`{name}`], as read by [`{name}::read_header`].");
+    let (ctx_generics, ctx_ty) = if let Some(ctx) = &attribs.ctx {
+        (quote!(), quote!(#ctx))
+    } else {
+        let bounds = attribs.ctx_bounds.iter();
+        (quote!(<__Ctx #( : #bounds )*>), quote!(__Ctx))
+    };
+
+    let read_header = with_crate_path(
+        attribs,
+        &quote!(
+            #[automatically_derived]
+            impl #name {
+            /// Reads the header of this struct from the start of `bytes`,
+            /// without reading the rest of it.
+            #[allow(unused)]
+            #vis fn read_header #ctx_generics (
+                bytes: &[u8],
+                byte_order: __bin_proto::ByteOrder,
+                ctx: &mut #ctx_ty,
+            ) -> __bin_proto::Result<#header_name>
+            where
+                #header_name: __bin_proto::ProtocolRead<#ctx_ty>,
+            {
+                    __bin_proto::ProtocolRead::from_bytes_ctx(bytes, byte_order, ctx)
+                }
+            }
+        ),
+    );
+
+    Ok(quote!(
+        #[doc = #doc]
+        #vis struct #header_name {
+            #(#definitions),*
+        }
+
+        #read_impl
+        #read_header
+    ))
+}
+
+/// Checks that `fields` are the leading fields of the struct, and returns its
+/// fields.
+fn leading_fields<'a>(
+    ast: &syn::DeriveInput,
+    strukt: &'a syn::DataStruct,
+    attribs: &Attrs,
+    fields: &Punctuated<syn::Ident, syn::Token![,]>,
+) -> Result<&'a syn::FieldsNamed> {
+    if !ast.generics.params.is_empty() {
+        return Err(Error::new(
+            ast.generics.span(),
+            "header is unsupported on generic structs",
+        ));
+    }
+    if attribs.pod {
+        return Err(Error::new(
+            ast.ident.span(),
+            "header cannot be combined with pod",
+        ));
+    }
+    let named = match &strukt.fields {
+        syn::Fields::Named(named) => named,
+        _ => {
+            return Err(Error::new(
+                ast.ident.span(),
+                "header requires a struct with named fields",
+            ))
+        }
+    };
+    if fields.is_empty() {
+        return Err(Error::new(fields.span(), "header lacks fields"));
+    }
+    for (i, ident) in fields.iter().enumerate() {
+        match named.named.iter().nth(i) {
+            Some(field) if field.ident.as_ref() == Some(ident) => {}
+            Some(field) => {
+                return Err(Error::new(
+                    ident.span(),
+                    format!(
+                        "header fields must be the leading fields of the struct, in order; \
+                         expected `{}`",
+                        field.ident.as_ref().expect("named field"),
+                    ),
+                ))
+            }
+            None => {
+                return Err(Error::new(
+                    ident.span(),
+                    "header has more fields than struct",
+                ))
+            }
+        }
+    }
+    Ok(named)
+}
+
+/// Removes `header(...)` and `builder` from `#[protocol(...)]` attributes, so
+/// that the header struct neither gets a header of its own nor is built like
+/// the struct.
+fn without_header(attrs: &[syn::Attribute]) -> Vec<syn::Attribute> {
+    attrs
+        .iter()
+        .map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(mut list)) if list.path.is_ident("protocol") => {
+                list.nested = list
+                    .nested
+                    .into_iter()
+                    .filter(|nested| match nested {
+                        syn::NestedMeta::Meta(syn::Meta::List(list)) => {
+                            !list.path.is_ident("header")
+                        }
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) => {
+                            !name_value.path.is_ident("builder")
+                        }
+                        _ => true,
+                    })
+                    .collect();
+                syn::parse_quote!(#[#list])
+            }
+            _ => attr.clone(),
+        })
+        .collect()
+}
@@ -1,27 +1,52 @@
 pub mod enums;
+pub mod fixed_size;
+pub mod header;
+pub mod read_replace;
 pub mod trait_impl;
+pub mod validate;
 
 use crate::attr::{Attrs, Tag};
 use proc_macro2::TokenStream;
-use syn::spanned::Spanned;
+use syn::{
+    spanned::Spanned,
+    visit::Visit,
+    visit_mut::{self, VisitMut},
+    Error, Result,
+};
 
-pub fn reads(fields: &syn::Fields, attrs: &Attrs) -> (TokenStream, TokenStream) {
+pub fn reads(
+    fields: &syn::Fields,
+    container: &syn::Ident,
+    attrs: &Attrs,
+) -> (TokenStream, TokenStream) {
     match *fields {
-        syn::Fields::Named(ref fields) => read_named_fields(fields, attrs),
-        syn::Fields::Unnamed(ref fields) => (quote!(), read_unnamed_fields(fields, attrs)),
+        syn::Fields::Named(ref fields) => read_named_fields(fields, container, attrs),
+        syn::Fields::Unnamed(ref fields) => read_unnamed_fields(fields, container, attrs),
         syn::Fields::Unit => (quote!(), quote!()),
     }
 }
 
-pub fn writes(fields: &syn::Fields, self_prefix: bool) -> TokenStream {
+pub fn writes(fields: &syn::Fields, attrs: &Attrs, self_prefix: bool) -> TokenStream {
     match *fields {
-        syn::Fields::Named(ref fields) => write_named_fields(fields, self_prefix),
-        syn::Fields::Unnamed(ref fields) => write_unnamed_fields(fields, self_prefix),
+        syn::Fields::Named(ref fields) => write_named_fields(fields, attrs, self_prefix),
+        syn::Fields::Unnamed(ref fields) => write_unnamed_fields(fields, attrs, self_prefix),
         syn::Fields::Unit => quote!(),
     }
 }
 
-fn read_named_fields(fields_named: &syn::FieldsNamed, attrs: &Attrs) -> (TokenStream, TokenStream) {
+fn read_named_fields(
+    fields_named: &syn::FieldsNamed,
+    container: &syn::Ident,
+    attrs: &Attrs,
+) -> (TokenStream, TokenStream) {
+    if let Err(e) = validate_tag_order(fields_named.named.iter()) {
+        let field_names = fields_named.named.iter().map(|field| &field.ident);
+        return (
+            e.to_compile_error(),
+            quote!({ #( #field_names: ::std::unreachable!() ),* }),
+        );
+    }
+
     let fields: Vec<_> = fields_named
         .named
         .iter()
@@ -29,10 +54,20 @@ fn read_named_fields(fields_named: &syn::FieldsNamed, attrs: &Attrs) -> (TokenSt
             let field_name = &field.ident;
             let field_ty = &field.ty;
 
-            let read = read(field, attrs);
+            let read = read(
+                field,
+                &field_name.as_ref().unwrap().to_string(),
+                container,
+                attrs,
+            );
+
+            let buffer = buffer_remaining(field);
+            let count = count_field_read(attrs);
 
             quote!(
+                #buffer
                 let #field_name : #field_ty = #read?;
+                #count
             )
         })
         .collect();
@@ -53,112 +88,754 @@ fn read_named_fields(fields_named: &syn::FieldsNamed, attrs: &Attrs) -> (TokenSt
     )
 }
 
-fn read(field: &syn::Field, parent_attribs: &Attrs) -> TokenStream {
+/// Counts a field that has been read, for `Error::PartialRead`.
+fn count_field_read(attrs: &Attrs) -> TokenStream {
+    if attrs.partial_read {
+        quote!(__fields_read += 1;)
+    } else {
+        quote!()
+    }
+}
+
+/// Fields after one sized by the remaining length are read from a buffer of the
+/// remaining bytes. A `length` that does not refer to `remaining` is read
+/// directly, without draining the rest of the input.
+fn buffer_remaining(field: &syn::Field) -> TokenStream {
+    match Attrs::try_from(field.attrs.as_slice()) {
+        Ok(Attrs {
+            length: Some(length),
+            ..
+        }) if refers_to_remaining(&length) => quote!(
+            let (__remaining, mut __buffered) =
+                __bin_proto::buffer_remaining(__io_reader, __byte_order)?;
+            let __io_reader: &mut dyn __bin_proto::BitRead = &mut *__buffered;
+        ),
+        _ => quote!(),
+    }
+}
+
+/// Whether a `length` expression refers to the number of remaining bytes.
+fn refers_to_remaining(length: &syn::Expr) -> bool {
+    let mut visitor = PathIdents(Vec::new());
+    visitor.visit_expr(length);
+    visitor.0.into_iter().any(|ident| ident == "remaining")
+}
+
+/// The tag of a field with `#[protocol(length = "...")]`.
+fn length_tag(length: &syn::Expr) -> TokenStream {
+    if refers_to_remaining(length) {
+        quote!({
+            let remaining: usize = __remaining;
+            #length
+        })
+    } else {
+        quote!({ #length })
+    }
+}
+
+/// The locals that `reads` reads the fields into, in order.
+pub fn bindings(fields: &syn::Fields) -> Vec<syn::Ident> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| binding(field, index))
+        .collect()
+}
+
+/// The name a field is bound to while reading, and while writing an enum
+/// variant: its own name, or `field_<index>` for unnamed fields.
+fn binding(field: &syn::Field, index: usize) -> syn::Ident {
+    field
+        .ident
+        .clone()
+        .unwrap_or_else(|| format_ident!("field_{}", index))
+}
+
+/// Ensures that external tags and conditions only refer to fields that have
+/// already been read.
+fn validate_tag_order<'a>(fields: impl Iterator<Item = &'a syn::Field> + Clone) -> Result<()> {
+    let names: Vec<_> = fields
+        .clone()
+        .enumerate()
+        .map(|(index, field)| binding(field, index))
+        .collect();
+
+    for (index, field) in fields.enumerate() {
+        let attribs = Attrs::try_from(field.attrs.as_slice())?;
+        let tag = match attribs.tag {
+            Some(Tag::External(ref expr)) => Some(expr),
+            _ => None,
+        };
+        for expr in tag
+            .into_iter()
+            .chain(attribs.length.as_ref())
+            .chain(attribs.present_if.as_ref())
+            .chain(attribs.condition.as_ref())
+        {
+            let mut visitor = PathIdents(Vec::new());
+            visitor.visit_expr(expr);
+            if let Some(ident) = visitor
+                .0
+                .into_iter()
+                .find(|ident| names[index..].contains(*ident))
+            {
+                return Err(Error::new(
+                    expr.span(),
+                    format!("field '{ident}' must precede the field that refers to it"),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Ensures that fields read until eof are the last field.
+pub fn validate_trailing_fields(fields: &syn::Fields) -> Result<()> {
+    let count = fields.len();
+    for (index, field) in fields.iter().enumerate() {
+        let attribs = Attrs::try_from(field.attrs.as_slice())?;
+        if index + 1 == count {
+            break;
+        }
+        if attribs.flexible_array_member {
+            return Err(Error::new(
+                field.span(),
+                "flexible_array_member field must be the last field",
+            ));
+        }
+        if attribs.present_if_remaining {
+            return Err(Error::new(
+                field.span(),
+                "present_if_remaining field must be the last field",
+            ));
+        }
+        if attribs.remaining {
+            return Err(Error::new(
+                field.span(),
+                "remaining field must be the last field",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `ty` refers to the type being derived, as in `Box<Self>` or
+/// `Vec<Node>` in `Node`.
+pub fn is_recursive(ty: &syn::Type, container: &syn::Ident) -> bool {
+    struct Refers<'a> {
+        container: &'a syn::Ident,
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for Refers<'_> {
+        fn visit_path_segment(&mut self, segment: &'ast syn::PathSegment) {
+            self.found |= segment.ident == *self.container || segment.ident == "Self";
+            syn::visit::visit_path_segment(self, segment);
+        }
+    }
+
+    let mut refers = Refers {
+        container,
+        found: false,
+    };
+    refers.visit_type(ty);
+    refers.found
+}
+
+/// Whether any field of the derived type is recursive, in which case its ctx
+/// must implement `DepthCtx`.
+pub fn has_recursive_fields(ast: &syn::DeriveInput) -> bool {
+    let recursive = |field: &syn::Field| is_recursive(&field.ty, &ast.ident);
+    match &ast.data {
+        syn::Data::Struct(strukt) => strukt.fields.iter().any(recursive),
+        syn::Data::Enum(e) => e
+            .variants
+            .iter()
+            .flat_map(|variant| &variant.fields)
+            .any(recursive),
+        syn::Data::Union(_) => false,
+    }
+}
+
+/// Reads a recursive field one level deeper, so that `DepthCtx` can limit
+/// how deeply input nests.
+pub fn read_recursive(read: &TokenStream) -> TokenStream {
+    quote!(__bin_proto::read_recursive(__ctx, |__ctx| #read))
+}
+
+struct PathIdents<'ast>(Vec<&'ast syn::Ident>);
+
+impl<'ast> Visit<'ast> for PathIdents<'ast> {
+    fn visit_expr_path(&mut self, expr_path: &'ast syn::ExprPath) {
+        if let Some(ident) = expr_path.path.get_ident() {
+            self.0.push(ident);
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn read(
+    field: &syn::Field,
+    name: &str,
+    container: &syn::Ident,
+    parent_attribs: &Attrs,
+) -> TokenStream {
     let attribs = match Attrs::try_from(field.attrs.as_slice()) {
         Ok(attribs) => attribs,
         Err(e) => return e.to_compile_error(),
     };
-    if let Err(e) = attribs.validate_field(field.span()) {
+    if let Err(e) = attribs.validate_field(&field.ty, field.span()) {
         return e.to_compile_error();
-    };
+    }
 
     let ctx_ty = parent_attribs.ctx_ty();
+    let byte_order = attribs.byte_order_override();
+
+    let checksummed = |read: TokenStream| {
+        if let Some(checksum) = &attribs.checksum {
+            quote!(<#checksum as __bin_proto::Checksum>::read_checksummed(
+                __io_reader,
+                __byte_order,
+                __ctx,
+                |__io_reader, __ctx| #read,
+            ))
+        } else {
+            read
+        }
+    };
+
+    if parent_attribs.lsb0 && attribs.bits.is_some() && attribs.checksum.is_some() {
+        return Error::new(
+            field.span(),
+            "checksum cannot be applied to lsb0-packed bitfields",
+        )
+        .to_compile_error();
+    }
+    if parent_attribs.lsb0 && attribs.bits.is_some() && attribs.align_before {
+        return Error::new(
+            field.span(),
+            "align_before cannot be applied to lsb0-packed bitfields",
+        )
+        .to_compile_error();
+    }
+    if parent_attribs.lsb0 && attribs.bits.is_some() && attribs.read_skip.is_some() {
+        return Error::new(
+            field.span(),
+            "read_skip cannot be applied to lsb0-packed bitfields",
+        )
+        .to_compile_error();
+    }
 
-    if let Some(field_width) = attribs.bits {
-        quote!(::bin_proto::BitFieldRead::<#ctx_ty>::read(__io_reader, __byte_order, __ctx, #field_width))
+    let read = if let (Some(field_width), true) = (attribs.bits, parent_attribs.lsb0) {
+        quote!(__lsb0.read::<#ctx_ty, _>(__io_reader, __byte_order, __ctx, #field_width))
+    } else if let Some(field_width) = attribs.bits {
+        checksummed(
+            quote!(__bin_proto::BitFieldRead::<#ctx_ty>::read(__io_reader, __byte_order, __ctx, #field_width)),
+        )
     } else if attribs.flexible_array_member {
-        quote!(::bin_proto::FlexibleArrayMemberRead::read(
+        let ty = &field.ty;
+        checksummed(quote_spanned!(ty.span()=>
+            <#ty as __bin_proto::FlexibleArrayMemberRead<#ctx_ty>>::read(
+                __io_reader,
+                __byte_order,
+                __ctx
+            )
+        ))
+    } else if attribs.present_if_remaining {
+        checksummed(quote!(__bin_proto::read_if_remaining(
             __io_reader,
             __byte_order,
             __ctx
-        ))
-    } else if let Some(tag) = attribs.tag {
+        )))
+    } else if attribs.remaining {
+        checksummed(quote!(__bin_proto::RemainingRead::read(__io_reader)))
+    } else if let Some(present_if) = &attribs.present_if {
+        checksummed(quote!(__bin_proto::TaggedRead::<bool, #ctx_ty>::read(
+            __io_reader,
+            __byte_order,
+            __ctx,
+            #present_if,
+        )))
+    } else if let Some(count) = attribs.count {
+        let count = count as usize;
+        checksummed(
+            quote!(__bin_proto::TaggedRead::<_, #ctx_ty>::read(__io_reader, __byte_order, __ctx, #count)),
+        )
+    } else if let Some(length) = &attribs.length {
+        let length = length_tag(length);
+        checksummed(quote!(__bin_proto::TaggedRead::<_, #ctx_ty>::read(
+            __io_reader,
+            __byte_order,
+            __ctx,
+            #length
+        )))
+    } else if let Some(tag) = &attribs.tag {
         match tag {
             Tag::External(tag) => {
-                quote!(::bin_proto::TaggedRead::<_, #ctx_ty>::read(__io_reader, __byte_order, __ctx, #tag))
+                if let Some((scale, adjust)) = attribs.tag_transform() {
+                    checksummed(quote!(__bin_proto::TaggedRead::<usize, #ctx_ty>::read(
+                        __io_reader,
+                        __byte_order,
+                        __ctx,
+                        __bin_proto::adjust_tag(#tag, #scale, #adjust)?,
+                    )))
+                } else {
+                    checksummed(
+                        quote!(__bin_proto::TaggedRead::<_, #ctx_ty>::read(__io_reader, __byte_order, __ctx, #tag)),
+                    )
+                }
             }
             Tag::Prepend {
                 typ,
                 write_value: _,
             } => {
-                quote!({
-                    let __tag = ::bin_proto::ProtocolRead::<#ctx_ty>::read(__io_reader, __byte_order, __ctx)?;
-                    ::bin_proto::TaggedRead::<#typ, #ctx_ty>::read(__io_reader, __byte_order, __ctx, __tag)
-                })
+                if let Some((scale, adjust)) = attribs.tag_transform() {
+                    let read = checksummed(quote!(__bin_proto::TaggedRead::<usize, #ctx_ty>::read(
+                        __io_reader,
+                        __byte_order,
+                        __ctx,
+                        __tag,
+                    )));
+                    quote!({
+                        let __tag: #typ = __bin_proto::ProtocolRead::<#ctx_ty>::read(__io_reader, __byte_order, __ctx)?;
+                        let __tag = __bin_proto::adjust_tag(__tag, #scale, #adjust)?;
+                        #read
+                    })
+                } else {
+                    let read = checksummed(
+                        quote!(__bin_proto::TaggedRead::<#typ, #ctx_ty>::read(__io_reader, __byte_order, __ctx, __tag)),
+                    );
+                    quote!({
+                        let __tag = __bin_proto::ProtocolRead::<#ctx_ty>::read(__io_reader, __byte_order, __ctx)?;
+                        #read
+                    })
+                }
             }
         }
     } else {
-        quote!(::bin_proto::ProtocolRead::<#ctx_ty>::read(__io_reader, __byte_order, __ctx))
+        checksummed(
+            quote!(__bin_proto::ProtocolRead::<#ctx_ty>::read(__io_reader, __byte_order, __ctx)),
+        )
+    };
+
+    let read = if let Some(policy) = attribs.float_policy() {
+        let ty = &field.ty;
+        quote!((#read).and_then(|__value: #ty| {
+            __bin_proto::CheckFloat::check_float(&__value, #policy, #name)?;
+            ::std::result::Result::Ok(__value)
+        }))
+    } else {
+        read
+    };
+
+    let read = match attribs.pad_bits {
+        Some(pad_bits) if parent_attribs.lsb0 && attribs.bits.is_some() => quote!({
+            __lsb0.pad(#pad_bits);
+            #read
+        }),
+        Some(pad_bits) => quote!({
+            __bin_proto::BitRead::skip(__io_reader, #pad_bits)?;
+            #read
+        }),
+        None => read,
+    };
+
+    let read = if attribs.align_before {
+        quote!({
+            __bin_proto::BitRead::byte_align(__io_reader);
+            #read
+        })
+    } else {
+        read
+    };
+
+    let read = if let Some(condition) = attribs.condition {
+        let default = attribs.default.map_or_else(
+            || quote!(::std::option::Option::None),
+            |default| quote!(#default),
+        );
+        quote!(
+            (if #condition {
+                #read
+            } else {
+                ::std::result::Result::Ok(#default)
+            })
+        )
+    } else {
+        read
+    };
+
+    let read = if let Some(read_skip) = attribs.read_skip {
+        let bits = read_skip * 8;
+        quote!({
+            __bin_proto::BitRead::skip(__io_reader, #bits)?;
+            #read
+        })
+    } else {
+        read
+    };
+
+    let read = if attribs.byte_order.is_some() {
+        quote!({
+            #byte_order
+            #read
+        })
+    } else {
+        read
+    };
+
+    let read = if parent_attribs.lsb0 && attribs.bits.is_none() {
+        quote!({
+            __lsb0.finish(__io_reader)?;
+            #read
+        })
+    } else {
+        read
+    };
+
+    if is_recursive(&field.ty, container) {
+        read_recursive(&read)
+    } else {
+        read
     }
 }
 
-fn write(field: &syn::Field, field_name: &TokenStream) -> TokenStream {
+#[allow(clippy::too_many_lines)]
+fn write(
+    field: &syn::Field,
+    field_name: &TokenStream,
+    name: &str,
+    siblings: &[(syn::Ident, syn::Member)],
+    parent_attribs: &Attrs,
+) -> TokenStream {
     let attribs = match Attrs::try_from(field.attrs.as_slice()) {
         Ok(attribs) => attribs,
         Err(e) => return e.to_compile_error(),
     };
 
-    let field_ref = if let Some(value) = attribs.write_value {
-        let ty = &field.ty;
-        quote!(&{
-            let value: #ty = {#value};
-            value
-        })
-    } else {
-        field_name.clone()
+    let field_ref = field_name;
+
+    let byte_order = attribs.byte_order_override();
+
+    let checksummed = |write: TokenStream| {
+        if let Some(checksum) = &attribs.checksum {
+            quote!(
+                {
+                    <#checksum as __bin_proto::Checksum>::write_checksummed(
+                        __io_writer,
+                        __byte_order,
+                        __ctx,
+                        |__io_writer, __ctx| {
+                            #write
+                            ::std::result::Result::Ok(())
+                        },
+                    )?
+                }
+            )
+        } else {
+            write
+        }
     };
 
-    if let Some(field_width) = attribs.bits {
+    if parent_attribs.lsb0 && attribs.bits.is_some() && attribs.checksum.is_some() {
+        return Error::new(
+            field.span(),
+            "checksum cannot be applied to lsb0-packed bitfields",
+        )
+        .to_compile_error();
+    }
+    if parent_attribs.lsb0 && attribs.bits.is_some() && attribs.align_before {
+        return Error::new(
+            field.span(),
+            "align_before cannot be applied to lsb0-packed bitfields",
+        )
+        .to_compile_error();
+    }
+    if parent_attribs.lsb0 && attribs.bits.is_some() && attribs.read_skip.is_some() {
+        return Error::new(
+            field.span(),
+            "read_skip cannot be applied to lsb0-packed bitfields",
+        )
+        .to_compile_error();
+    }
+
+    let write = if let (Some(field_width), true) = (attribs.bits, parent_attribs.lsb0) {
         quote!(
             {
-                ::bin_proto::BitFieldWrite::write(#field_ref, __io_writer, __byte_order, __ctx, #field_width)?
+                __lsb0.write(#field_ref, __byte_order, __ctx, #field_width)?
             }
         )
-    } else if attribs.flexible_array_member {
-        quote!(
+    } else if let Some(field_width) = attribs.bits {
+        checksummed(quote!(
             {
-                ::bin_proto::UntaggedWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
+                __bin_proto::BitFieldWrite::write(#field_ref, __io_writer, __byte_order, __ctx, #field_width)?
             }
-        )
-    } else if let Some(tag) = attribs.tag {
+        ))
+    } else if attribs.remaining {
+        checksummed(quote!(
+            {
+                __bin_proto::BitWrite::write_bytes(
+                    __io_writer,
+                    ::std::convert::AsRef::<[u8]>::as_ref(#field_ref),
+                )?
+            }
+        ))
+    } else if attribs.flexible_array_member
+        || attribs.present_if_remaining
+        || attribs.length.is_some()
+    {
+        checksummed(quote!(
+            {
+                __bin_proto::UntaggedWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
+            }
+        ))
+    } else if let Some(present_if) = &attribs.present_if {
+        let check = check_presence(present_if, siblings, field_ref, name);
+        checksummed(quote!(
+            {
+                #check
+                __bin_proto::UntaggedWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
+            }
+        ))
+    } else if let Some(count) = attribs.count {
+        let check = check_count(count, field_ref);
+        checksummed(quote!(
+            {
+                #check
+                __bin_proto::UntaggedWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
+            }
+        ))
+    } else if let Some(tag) = &attribs.tag {
         match tag {
-            Tag::External(_) => quote!(
+            Tag::External(_) => checksummed(quote!(
                 {
-                    ::bin_proto::UntaggedWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
+                    __bin_proto::UntaggedWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
                 }
-            ),
+            )),
             Tag::Prepend {
                 typ,
                 write_value: value,
-            } => quote!(
-                {
-                    <#typ as ::bin_proto::ProtocolWrite<_>>::write(&{#value}, __io_writer, __byte_order, __ctx)?;
-                    ::bin_proto::UntaggedWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
-                }
-            ),
+            } => {
+                let write = checksummed(quote!(
+                    {
+                        __bin_proto::UntaggedWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
+                    }
+                ));
+                let value = if let Some((scale, adjust)) = attribs.tag_transform() {
+                    quote!(__bin_proto::unadjust_tag::<#typ>({#value}, #scale, #adjust)?)
+                } else {
+                    quote!({#value})
+                };
+                quote!(
+                    {
+                        <#typ as __bin_proto::ProtocolWrite<_>>::write(&#value, __io_writer, __byte_order, __ctx)?;
+                        #write
+                    }
+                )
+            }
         }
     } else {
+        checksummed(quote!(
+            {
+                __bin_proto::ProtocolWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
+            }
+        ))
+    };
+
+    let write = if let Some(policy) = attribs.float_policy() {
+        quote!(
+            {
+                __bin_proto::CheckFloat::check_float(#field_ref, #policy, #name)?;
+                #write
+            }
+        )
+    } else {
+        write
+    };
+
+    let write = match attribs.pad_bits {
+        Some(pad_bits) if parent_attribs.lsb0 && attribs.bits.is_some() => quote!(
+            {
+                __lsb0.pad(#pad_bits);
+                #write
+            }
+        ),
+        Some(pad_bits) => quote!(
+            {
+                for _ in 0..#pad_bits {
+                    __bin_proto::BitWrite::write_bit(__io_writer, false)?;
+                }
+                #write
+            }
+        ),
+        None => write,
+    };
+
+    let write = if attribs.align_before {
+        quote!(
+            {
+                __bin_proto::BitWrite::byte_align(__io_writer)?;
+                #write
+            }
+        )
+    } else {
+        write
+    };
+
+    let write = if let Some(condition) = attribs.condition {
+        let condition = written_values(&condition, siblings);
+        quote!(
+            {
+                if #condition #write
+            }
+        )
+    } else {
+        write
+    };
+
+    let write = if let Some(read_skip) = attribs.read_skip {
+        let read_skip = read_skip as usize;
+        quote!(
+            {
+                __bin_proto::BitWrite::write_bytes(__io_writer, &[0u8; #read_skip])?;
+                #write
+            }
+        )
+    } else {
+        write
+    };
+
+    let write = if attribs.byte_order.is_some() {
+        quote!(
+            {
+                #byte_order
+                #write
+            }
+        )
+    } else {
+        write
+    };
+
+    if parent_attribs.lsb0 && attribs.bits.is_none() {
         quote!(
             {
-                ::bin_proto::ProtocolWrite::write(#field_ref, __io_writer, __byte_order, __ctx)?
+                __lsb0.finish(__io_writer)?;
+                #write
             }
         )
+    } else {
+        write
     }
 }
 
-fn write_named_fields(fields_named: &syn::FieldsNamed, self_prefix: bool) -> TokenStream {
+/// Fails with `Error::PresenceMismatch` unless the `Option` at `field_ref` is
+/// `Some` exactly when `present_if` holds.
+fn check_presence(
+    present_if: &syn::Expr,
+    siblings: &[(syn::Ident, syn::Member)],
+    field_ref: &TokenStream,
+    name: &str,
+) -> TokenStream {
+    let present_if = written_values(present_if, siblings);
+    quote!(
+        let __present: bool = #present_if;
+        if ::std::option::Option::is_some(#field_ref) != __present {
+            return ::std::result::Result::Err(__bin_proto::Error::PresenceMismatch {
+                field: #name,
+            });
+        }
+    )
+}
+
+/// Fails with `Error::CountMismatch` unless the collection at `field_ref` has
+/// `count` elements.
+fn check_count(count: u32, field_ref: &TokenStream) -> TokenStream {
+    let count = count as usize;
+    quote!(
+        if #field_ref.len() != #count {
+            return ::std::result::Result::Err(__bin_proto::Error::CountMismatch {
+                expected: #count,
+                actual: #field_ref.len(),
+            });
+        }
+    )
+}
+
+/// Rewrites the siblings in a condition to `(*sibling)`, so that on write it
+/// reads the values written for them through the references they are bound
+/// to, the same way as it reads the fields on read.
+fn written_values(condition: &syn::Expr, siblings: &[(syn::Ident, syn::Member)]) -> syn::Expr {
+    struct DerefSiblings<'a>(&'a [(syn::Ident, syn::Member)]);
+
+    impl VisitMut for DerefSiblings<'_> {
+        fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+            if let syn::Expr::Path(expr_path) = expr {
+                if let Some(ident) = expr_path.path.get_ident() {
+                    if self.0.iter().any(|(sibling, _)| sibling == ident) {
+                        *expr = syn::parse_quote!((*#ident));
+                        return;
+                    }
+                }
+            }
+            visit_mut::visit_expr_mut(self, expr);
+        }
+    }
+
+    let mut condition = condition.clone();
+    DerefSiblings(siblings).visit_expr_mut(&mut condition);
+    condition
+}
+
+/// Binds a field to the value that is written for it: its `write_value`, or
+/// the field itself. Conditions of later fields refer to these bindings.
+fn written_value(
+    field: &syn::Field,
+    binding: &syn::Ident,
+    member: &syn::Member,
+    self_prefix: bool,
+) -> TokenStream {
+    let ty = &field.ty;
+    // Invalid attributes are reported by `write`.
+    let write_value = Attrs::try_from(field.attrs.as_slice())
+        .ok()
+        .and_then(|attribs| attribs.write_value);
+    if let Some(value) = write_value {
+        quote!(
+            let #binding: &#ty = &{
+                let __write_value: #ty = {#value};
+                __write_value
+            };
+        )
+    } else if self_prefix {
+        quote!(let #binding: &#ty = &self.#member;)
+    } else {
+        quote!()
+    }
+}
+
+fn write_named_fields(
+    fields_named: &syn::FieldsNamed,
+    attrs: &Attrs,
+    self_prefix: bool,
+) -> TokenStream {
+    let siblings: Vec<_> = fields_named
+        .named
+        .iter()
+        .filter_map(|field| field.ident.clone())
+        .map(|ident| (ident.clone(), syn::Member::Named(ident)))
+        .collect();
     let field_writers: Vec<_> = fields_named
         .named
         .iter()
-        .map(|field| {
-            let field_name = &field.ident;
-            write(
-                field,
-                &if self_prefix {
-                    quote!(&self. #field_name)
-                } else {
-                    quote!(#field_name)
-                },
+        .zip(&siblings)
+        .map(|(field, (binding, member))| {
+            let name = binding.to_string();
+            let value = written_value(field, binding, member, self_prefix);
+            let write = write(field, &quote!(#binding), &name, &siblings, attrs);
+            let write = record_offset(&name, write, attrs);
+            quote!(
+                #value
+                #write
             )
         })
         .collect();
@@ -166,40 +843,96 @@ fn write_named_fields(fields_named: &syn::FieldsNamed, self_prefix: bool) -> Tok
     quote!( #( #field_writers );* )
 }
 
-fn read_unnamed_fields(fields_unnamed: &syn::FieldsUnnamed, attrs: &Attrs) -> TokenStream {
-    let field_initializers: Vec<_> = fields_unnamed
+/// Marks the bounds of a field's write, so that its offset can be recorded.
+fn record_offset(name: &str, write: TokenStream, attrs: &Attrs) -> TokenStream {
+    if attrs.record_offsets {
+        quote!(
+            {
+                __bin_proto::BitWrite::begin_field(__io_writer, #name);
+                #write
+                __bin_proto::BitWrite::end_field(__io_writer);
+            }
+        )
+    } else {
+        write
+    }
+}
+
+/// Reads unnamed fields into `field_<index>` locals, so that the expressions
+/// of later fields can refer to them.
+fn read_unnamed_fields(
+    fields_unnamed: &syn::FieldsUnnamed,
+    container: &syn::Ident,
+    attrs: &Attrs,
+) -> (TokenStream, TokenStream) {
+    let bindings: Vec<_> = fields_unnamed
         .unnamed
         .iter()
-        .map(|field| {
+        .enumerate()
+        .map(|(field_index, field)| binding(field, field_index))
+        .collect();
+    if let Err(e) = validate_tag_order(fields_unnamed.unnamed.iter()) {
+        let unreachable = bindings.iter().map(|_| quote!(::std::unreachable!()));
+        return (e.to_compile_error(), quote!(( #( #unreachable ),* )));
+    }
+
+    let fields: Vec<_> = fields_unnamed
+        .unnamed
+        .iter()
+        .zip(&bindings)
+        .enumerate()
+        .map(|(field_index, (field, binding))| {
             let field_ty = &field.ty;
-            let read = read(field, attrs);
+            let read = read(field, &field_index.to_string(), container, attrs);
+
+            let buffer = buffer_remaining(field);
+            let count = count_field_read(attrs);
 
             quote!(
-                {
-                    let res: #field_ty = #read?;
-                    res
-                }
+                #buffer
+                let #binding: #field_ty = #read?;
+                #count
             )
         })
         .collect();
 
-    quote!( ( #( #field_initializers ),* ) )
+    (quote!( #( #fields )* ), quote!( ( #( #bindings ),* ) ))
 }
 
-fn write_unnamed_fields(fields_unnamed: &syn::FieldsUnnamed, self_prefix: bool) -> TokenStream {
-    let field_writers: Vec<_> = fields_unnamed
+fn write_unnamed_fields(
+    fields_unnamed: &syn::FieldsUnnamed,
+    attrs: &Attrs,
+    self_prefix: bool,
+) -> TokenStream {
+    let siblings: Vec<_> = fields_unnamed
         .unnamed
         .iter()
         .enumerate()
         .map(|(field_index, field)| {
-            let field_index = syn::Index::from(field_index);
-            write(
+            (
+                binding(field, field_index),
+                syn::Member::Unnamed(field_index.into()),
+            )
+        })
+        .collect();
+    let field_writers: Vec<_> = fields_unnamed
+        .unnamed
+        .iter()
+        .zip(&siblings)
+        .enumerate()
+        .map(|(field_index, (field, (binding, member)))| {
+            let value = written_value(field, binding, member, self_prefix);
+            let write = write(
                 field,
-                &if self_prefix {
-                    quote!(&self. #field_index)
-                } else {
-                    format!("field_{}", field_index.index).parse().unwrap()
-                },
+                &quote!(#binding),
+                &field_index.to_string(),
+                &siblings,
+                attrs,
+            );
+            let write = record_offset(&field_index.to_string(), write, attrs);
+            quote!(
+                #value
+                #write
             )
         })
         .collect();
@@ -0,0 +1,193 @@
+use crate::attr::{Attrs, Tag};
+use proc_macro2::TokenStream;
+use syn::{spanned::Spanned, visit::Visit};
+
+use super::{
+    binding, buffer_remaining, is_recursive, length_tag, read, validate_tag_order, PathIdents,
+};
+
+/// Fields referred to by the expressions of other fields.
+fn referenced_fields(fields: &syn::Fields) -> Vec<syn::Ident> {
+    let mut referenced = Vec::new();
+    for field in fields {
+        if let Ok(attribs) = Attrs::try_from(field.attrs.as_slice()) {
+            let mut visitor = PathIdents(Vec::new());
+            if let Some(Tag::External(expr)) = &attribs.tag {
+                visitor.visit_expr(expr);
+            }
+            for expr in attribs
+                .length
+                .iter()
+                .chain(&attribs.present_if)
+                .chain(&attribs.condition)
+                .chain(&attribs.default)
+            {
+                visitor.visit_expr(expr);
+            }
+            referenced.extend(visitor.0.into_iter().cloned());
+        }
+    }
+    referenced
+}
+
+/// Reads a field into `self.#member` in place, if its type is read with
+/// `ProtocolRead` or `TaggedRead`.
+fn in_place_read(
+    field_attribs: &Attrs,
+    member: &syn::Member,
+    ctx_ty: &TokenStream,
+) -> Option<TokenStream> {
+    if field_attribs.checksum.is_some()
+        || field_attribs.pad_bits.is_some()
+        || field_attribs.align_before
+        || field_attribs.read_skip.is_some()
+        || field_attribs.condition.is_some()
+        || field_attribs.float_policy.is_some()
+        || field_attribs.tag_adjust.is_some()
+        || field_attribs.tag_scale.is_some()
+        || field_attribs.byte_order.is_some()
+    {
+        return None;
+    }
+    let tagged = |tag: TokenStream| {
+        quote!(__bin_proto::TaggedRead::<_, #ctx_ty>::read_replace(
+                &mut self.#member,
+                __io_reader,
+                __byte_order,
+                __ctx,
+                #tag,
+            )?;)
+    };
+    let read = match field_attribs {
+        Attrs {
+            tag: Some(Tag::External(tag)),
+            ..
+        } => tagged(quote!(#tag)),
+        Attrs {
+            tag: Some(Tag::Prepend { typ, .. }),
+            ..
+        } => quote!({
+            let __tag: #typ =
+                __bin_proto::ProtocolRead::<#ctx_ty>::read(__io_reader, __byte_order, __ctx)?;
+            __bin_proto::TaggedRead::<#typ, #ctx_ty>::read_replace(
+                &mut self.#member,
+                __io_reader,
+                __byte_order,
+                __ctx,
+                __tag,
+            )?;
+        }),
+        Attrs {
+            present_if: Some(present_if),
+            ..
+        } => tagged(quote!(#present_if)),
+        Attrs {
+            count: Some(count), ..
+        } => {
+            let count = *count as usize;
+            tagged(quote!(#count))
+        }
+        Attrs {
+            length: Some(length),
+            ..
+        } => tagged(length_tag(length)),
+        Attrs {
+            bits: None,
+            flexible_array_member: false,
+            present_if_remaining: false,
+            present_if: None,
+            remaining: false,
+            tag: None,
+            count: None,
+            length: None,
+            ..
+        } => quote!(__bin_proto::ProtocolRead::<#ctx_ty>::read_replace(
+                &mut self.#member,
+                __io_reader,
+                __byte_order,
+                __ctx,
+            )?;),
+        _ => return None,
+    };
+    Some(read)
+}
+
+/// Generates `ProtocolRead::read_replace`, which reads fields into `self` in
+/// place. Fields referred to by other fields are read into locals instead, so
+/// that expressions see them by value, as they do in `read`.
+pub fn struct_read_replace(
+    ast: &syn::DeriveInput,
+    strukt: &syn::DataStruct,
+    attribs: &Attrs,
+) -> TokenStream {
+    if attribs.lsb0 || attribs.pod || attribs.partial_read || attribs.builder.is_some() {
+        return TokenStream::new();
+    }
+    // Errors are reported by `read`.
+    if validate_tag_order(strukt.fields.iter()).is_err() {
+        return TokenStream::new();
+    }
+
+    let ctx_ty = attribs.ctx_ty();
+    let byte_order = attribs.byte_order_override();
+    let referenced = referenced_fields(&strukt.fields);
+    let mut reads = Vec::new();
+    let mut assignments = Vec::new();
+    for (index, field) in strukt.fields.iter().enumerate() {
+        let field_attribs = match Attrs::try_from(field.attrs.as_slice()) {
+            Ok(field_attribs)
+                if field_attribs
+                    .validate_field(&field.ty, field.span())
+                    .is_ok() =>
+            {
+                field_attribs
+            }
+            _ => return TokenStream::new(),
+        };
+        let member = field
+            .ident
+            .clone()
+            .map_or_else(|| syn::Member::Unnamed(index.into()), syn::Member::Named);
+        let name = field
+            .ident
+            .as_ref()
+            .map_or_else(|| index.to_string(), ToString::to_string);
+        let buffer = buffer_remaining(field);
+
+        // Recursive fields are read with `read`, which counts their depth.
+        let ident = binding(field, index);
+        if referenced.contains(&ident) || is_recursive(&field.ty, &ast.ident) {
+            let ty = &field.ty;
+            let read = read(field, &name, &ast.ident, attribs);
+            reads.push(quote!(
+                #buffer
+                let #ident: #ty = #read?;
+            ));
+            assignments.push(quote!(self.#member = #ident;));
+            continue;
+        }
+
+        let read = in_place_read(&field_attribs, &member, &ctx_ty).unwrap_or_else(|| {
+            let read = read(field, &name, &ast.ident, attribs);
+            quote!(self.#member = #read?;)
+        });
+        reads.push(quote!(
+            #buffer
+            #read
+        ));
+    }
+
+    quote!(
+        #[allow(unused_variables)]
+        fn read_replace(&mut self,
+                        __io_reader: &mut dyn __bin_proto::BitRead,
+                        __byte_order: __bin_proto::ByteOrder,
+                        __ctx: &mut #ctx_ty)
+                        -> __bin_proto::Result<()> {
+            #byte_order
+            #( #reads )*
+            #( #assignments )*
+            Ok(())
+        }
+    )
+}
@@ -1,4 +1,4 @@
-use crate::attr::Attrs;
+use crate::{attr::Attrs, codegen};
 
 use proc_macro2::{Span, TokenStream};
 use syn::{parse_quote, punctuated::Punctuated, Token};
@@ -6,7 +6,7 @@ use syn::{parse_quote, punctuated::Punctuated, Token};
 pub enum TraitImplType {
     ProtocolRead,
     ProtocolWrite,
-    TaggedRead(syn::Type),
+    TaggedRead(Box<syn::Type>),
     UntaggedWrite,
     Discriminable,
 }
@@ -59,17 +59,25 @@ pub fn impl_trait_for(
             | TraitImplType::TaggedRead(_)
             | TraitImplType::UntaggedWrite
     ) {
-        trait_generics.push(if let Some(ctx) = attribs.ctx {
+        trait_generics.push(if let Some(ctx) = &attribs.ctx {
             quote!(#ctx)
         } else {
             let ident = syn::Ident::new("__Ctx", Span::call_site());
+            let mut bounds = attribs.ctx_bounds.clone().unwrap_or_default();
+            if matches!(
+                typ,
+                TraitImplType::ProtocolRead | TraitImplType::TaggedRead(_)
+            ) && codegen::has_recursive_fields(ast)
+            {
+                bounds.push(parse_quote!(__bin_proto::DepthCtx));
+            }
             generics
                 .params
                 .push(syn::GenericParam::Type(syn::TypeParam {
                     attrs: Vec::new(),
                     ident: ident.clone(),
                     colon_token: None,
-                    bounds: attribs.ctx_bounds.unwrap_or(Punctuated::new()),
+                    bounds,
                     eq_token: None,
                     default: None,
                 }));
@@ -78,10 +86,33 @@ pub fn impl_trait_for(
     }
 
     let (impl_generics, _, where_clause) = generics.split_for_impl();
+    // The body binds fields to locals of the same name, which the user may
+    // have given a placeholder name such as `foo`.
+    with_crate_path(
+        &attribs,
+        &quote!(
+            #[automatically_derived]
+            #[allow(clippy::disallowed_names)]
+            impl #impl_generics __bin_proto::#trait_name<#trait_generics> for #name #ty_generics #where_clause {
+                #impl_body
+            }
+        ),
+    )
+}
+
+/// Wraps `item` in an anonymous const that imports the `bin_proto` crate as
+/// `__bin_proto`, which generated code refers to it by. The crate is found at
+/// the path given by `#[protocol(crate = "<path>")]`, or at `::bin_proto`.
+pub fn with_crate_path(attribs: &Attrs, item: &TokenStream) -> TokenStream {
+    let crate_path = if let Some(crate_path) = &attribs.crate_path {
+        quote!(#crate_path)
+    } else {
+        quote!(::bin_proto)
+    };
     quote!(
-        #[automatically_derived]
-        impl #impl_generics ::bin_proto::#trait_name<#trait_generics> for #name #ty_generics #where_clause {
-            #impl_body
-        }
+        const _: () = {
+            use #crate_path as __bin_proto;
+            #item
+        };
     )
 }
@@ -0,0 +1,161 @@
+use crate::attr::{Attrs, Tag};
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+
+use super::{
+    binding, check_count, check_presence, validate_tag_order, written_value, written_values,
+};
+
+/// Generates `validate_ctx` for a struct, which checks its fields directly
+/// instead of counting the bits they write: `bits` fields must fit, tags must
+/// match the lengths of the fields they prefix, `present_if` must hold
+/// exactly for `Some`, and other fields are validated in turn. The contents of
+/// tagged and trailing fields are not checked.
+pub fn struct_validate(strukt: &syn::DataStruct, attribs: &Attrs) -> TokenStream {
+    if attribs.pod || attribs.total_bits.is_some() {
+        return TokenStream::new();
+    }
+    // Errors are reported by `write`.
+    if validate_tag_order(strukt.fields.iter()).is_err() {
+        return TokenStream::new();
+    }
+
+    let ctx_ty = attribs.ctx_ty();
+    let byte_order = attribs.byte_order_override();
+    let siblings: Vec<_> = strukt
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let member = field
+                .ident
+                .clone()
+                .map_or_else(|| syn::Member::Unnamed(index.into()), syn::Member::Named);
+            (binding(field, index), member)
+        })
+        .collect();
+    let mut checks = Vec::new();
+    for (index, (field, (ident, member))) in strukt.fields.iter().zip(&siblings).enumerate() {
+        let field_attribs = match Attrs::try_from(field.attrs.as_slice()) {
+            Ok(field_attribs)
+                if field_attribs
+                    .validate_field(&field.ty, field.span())
+                    .is_ok() =>
+            {
+                field_attribs
+            }
+            _ => return TokenStream::new(),
+        };
+        let name = field
+            .ident
+            .as_ref()
+            .map_or_else(|| index.to_string(), ToString::to_string);
+        let value = written_value(field, ident, member, true);
+        let check = check(&field_attribs, ident, &name, &siblings, &ctx_ty);
+        checks.push(quote!(
+            #value
+            #check
+        ));
+    }
+
+    quote!(
+        #[allow(unused_variables)]
+        fn validate_ctx(&self,
+                        __byte_order: __bin_proto::ByteOrder,
+                        __ctx: &mut #ctx_ty)
+                        -> __bin_proto::Result<()> {
+            #byte_order
+            #( #checks )*
+            ::std::result::Result::Ok(())
+        }
+    )
+}
+
+fn check(
+    attribs: &Attrs,
+    ident: &syn::Ident,
+    name: &str,
+    siblings: &[(syn::Ident, syn::Member)],
+    ctx_ty: &TokenStream,
+) -> TokenStream {
+    let field_ref = quote!(#ident);
+
+    let tag_len = |tag: TokenStream| {
+        if let Some((scale, adjust)) = attribs.tag_transform() {
+            quote!(::std::option::Option::Some(
+                __bin_proto::adjust_tag(#tag, #scale, #adjust).ok()
+            ))
+        } else {
+            quote!({
+                #[allow(unused_imports)]
+                use __bin_proto::{NotTagAsLen as _, TagAsLen as _};
+                (&__bin_proto::TagLen(#tag)).tag_as_len()
+            })
+        }
+    };
+    let check = if let Some(bits) = attribs.bits {
+        quote!(__bin_proto::BitFieldWrite::<#ctx_ty>::validate_bits(#ident, #bits)?;)
+    } else if attribs.remaining
+        || attribs.flexible_array_member
+        || attribs.present_if_remaining
+        || attribs.length.is_some()
+    {
+        quote!()
+    } else if let Some(present_if) = &attribs.present_if {
+        check_presence(present_if, siblings, &field_ref, name)
+    } else if let Some(count) = attribs.count {
+        check_count(count, &field_ref)
+    } else if let Some(tag) = &attribs.tag {
+        let tag = match tag {
+            Tag::External(expr) => {
+                let expr = written_values(expr, siblings);
+                tag_len(quote!(#expr))
+            }
+            Tag::Prepend { typ, write_value } => {
+                if let Some((scale, adjust)) = attribs.tag_transform() {
+                    tag_len(quote!(__bin_proto::unadjust_tag::<#typ>(
+                        {#write_value},
+                        #scale,
+                        #adjust,
+                    )?))
+                } else {
+                    tag_len(quote!({
+                        let __tag: #typ = {#write_value};
+                        __tag
+                    }))
+                }
+            }
+        };
+        quote!(__bin_proto::validate_tag::<#ctx_ty, _>(#ident, #tag, #name)?;)
+    } else {
+        quote!(__bin_proto::ProtocolWrite::<#ctx_ty>::validate_ctx(#ident, __byte_order, __ctx)?;)
+    };
+
+    let check = if let Some(policy) = attribs.float_policy() {
+        quote!(
+            __bin_proto::CheckFloat::check_float(#ident, #policy, #name)?;
+            #check
+        )
+    } else {
+        check
+    };
+
+    let check = if let Some(condition) = &attribs.condition {
+        let condition = written_values(condition, siblings);
+        quote!(
+            if #condition {
+                #check
+            }
+        )
+    } else {
+        check
+    };
+
+    let byte_order = attribs.byte_order_override();
+    quote!(
+        {
+            #byte_order
+            #check
+        }
+    )
+}
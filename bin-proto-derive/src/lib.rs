@@ -11,9 +11,11 @@ mod plan;
 use attr::Attrs;
 use codegen::trait_impl::{impl_trait_for, TraitImplType};
 use proc_macro2::TokenStream;
-use syn::parse_macro_input;
+use syn::{parse_macro_input, spanned::Spanned};
 
-use crate::codegen::enums::{read_discriminant, variant_discriminant, write_discriminant};
+use crate::codegen::enums::{
+    read_discriminant, variant_discriminant, variant_name, write_discriminant,
+};
 
 #[derive(Clone, Copy)]
 enum Operation {
@@ -41,6 +43,7 @@ fn impl_protocol(ast: &syn::DeriveInput, protocol_type: Operation) -> TokenStrea
     }
 }
 
+#[allow(clippy::too_many_lines)]
 fn impl_for_struct(
     ast: &syn::DeriveInput,
     strukt: &syn::DataStruct,
@@ -51,46 +54,187 @@ fn impl_for_struct(
         Err(e) => return e.to_compile_error(),
     };
 
+    if let Err(e) = attribs.validate_struct(ast.span()) {
+        return e.to_compile_error();
+    }
+    if let Err(e) = codegen::validate_trailing_fields(&strukt.fields) {
+        return e.to_compile_error();
+    }
+    if attribs.lsb0 && attribs.record_offsets {
+        return syn::Error::new(
+            ast.ident.span(),
+            "record_offsets cannot be combined with lsb0 bitfield_packing",
+        )
+        .to_compile_error();
+    }
+    if attribs.pod && (attribs.lsb0 || attribs.record_offsets || attribs.builder.is_some()) {
+        return syn::Error::new(
+            ast.ident.span(),
+            "pod cannot be combined with lsb0 bitfield_packing, record_offsets or builder",
+        )
+        .to_compile_error();
+    }
+    if attribs.pod {
+        if let Some(attr) = strukt
+            .fields
+            .iter()
+            .flat_map(|field| &field.attrs)
+            .find(|attr| attr.path.is_ident("protocol"))
+        {
+            return syn::Error::new(attr.span(), "pod cannot be combined with field attributes")
+                .to_compile_error();
+        }
+    }
+
     let ctx_ty = attribs.ctx_ty();
+    let byte_order = attribs.byte_order_override();
 
     let (impl_body, trait_type) = match protocol_type {
         Operation::Read => {
-            let (reads, initializers) = codegen::reads(&strukt.fields, &attribs);
+            let (reads, initializers) = codegen::reads(&strukt.fields, &ast.ident, &attribs);
+            let read_replace = codegen::read_replace::struct_read_replace(ast, strukt, &attribs);
+            let pod = attribs.pod.then(|| {
+                quote!(if __byte_order == __bin_proto::ByteOrder::NATIVE {
+                    let mut __value: Self = __bin_proto::bytemuck::Zeroable::zeroed();
+                    __bin_proto::BitRead::read_bytes(
+                        __io_reader,
+                        __bin_proto::bytemuck::bytes_of_mut(&mut __value),
+                    )?;
+                    return Ok(__value);
+                })
+            });
+            let (lsb0, lsb0_finish) = if attribs.lsb0 {
+                (
+                    quote!(let mut __lsb0 = __bin_proto::Lsb0Reader::default();),
+                    quote!(__lsb0.finish(__io_reader)?;),
+                )
+            } else {
+                (quote!(), quote!())
+            };
+            let construct = if let Some(builder) = &attribs.builder {
+                let bindings = codegen::bindings(&strukt.fields);
+                quote!(#builder( #( #bindings ),* ))
+            } else {
+                quote!(Self #initializers)
+            };
+            let read_fields = quote!(
+                #lsb0
+                #reads
+                let __value: Self = #construct;
+                #lsb0_finish
+                Ok(__value)
+            );
+            let read_fields = if attribs.partial_read {
+                quote!(
+                    let mut __fields_read = 0usize;
+                    let __result = (|| -> __bin_proto::Result<Self> { #read_fields })();
+                    __result.map_err(|e| __bin_proto::Error::PartialRead {
+                        fields_read: __fields_read,
+                        source: ::std::boxed::Box::new(e),
+                    })
+                )
+            } else {
+                read_fields
+            };
             (
                 quote!(
                     #[allow(unused_variables)]
-                    fn read(__io_reader: &mut dyn ::bin_proto::BitRead,
-                            __byte_order: ::bin_proto::ByteOrder,
+                    fn read(__io_reader: &mut dyn __bin_proto::BitRead,
+                            __byte_order: __bin_proto::ByteOrder,
                             __ctx: &mut #ctx_ty)
-                            -> ::bin_proto::Result<Self> {
-                        #reads
-                        Ok(Self #initializers)
+                            -> __bin_proto::Result<Self> {
+                        #byte_order
+                        #pod
+                        #read_fields
                     }
+
+                    #read_replace
                 ),
                 TraitImplType::ProtocolRead,
             )
         }
         Operation::Write => {
-            let writes = codegen::writes(&strukt.fields, true);
+            let writes = codegen::writes(&strukt.fields, &attribs, true);
+            let validate = codegen::validate::struct_validate(strukt, &attribs);
+            let pod = attribs.pod.then(|| {
+                quote!(if __byte_order == __bin_proto::ByteOrder::NATIVE {
+                    __bin_proto::BitWrite::write_bytes(
+                        __io_writer,
+                        __bin_proto::bytemuck::bytes_of(self),
+                    )?;
+                    return Ok(());
+                })
+            });
+            let (lsb0, lsb0_finish) = if attribs.lsb0 {
+                (
+                    quote!(let mut __lsb0 = __bin_proto::Lsb0Writer::default();),
+                    quote!(__lsb0.finish(__io_writer)?;),
+                )
+            } else {
+                (quote!(), quote!())
+            };
+            let write_fields = quote!(
+                #lsb0
+                #writes
+                #lsb0_finish
+            );
+            let write_fields = if let Some(total_bits) = attribs.total_bits {
+                let total_bits = u64::from(total_bits);
+                quote!(
+                    let mut __counting = __bin_proto::CountingWrite::new(__io_writer);
+                    {
+                        let __io_writer: &mut dyn __bin_proto::BitWrite = &mut __counting;
+                        #write_fields
+                    }
+                    if __counting.written() != #total_bits {
+                        return ::std::result::Result::Err(__bin_proto::Error::TotalBitsMismatch {
+                            expected: #total_bits,
+                            actual: __counting.written(),
+                        });
+                    }
+                )
+            } else {
+                write_fields
+            };
             (
                 quote!(
                     #[allow(unused_variables)]
-                    fn write(&self, __io_writer: &mut dyn ::bin_proto::BitWrite,
-                             __byte_order: ::bin_proto::ByteOrder,
+                    fn write(&self, __io_writer: &mut dyn __bin_proto::BitWrite,
+                             __byte_order: __bin_proto::ByteOrder,
                              __ctx: &mut #ctx_ty)
-                             -> ::bin_proto::Result<()> {
-                        #writes
+                             -> __bin_proto::Result<()> {
+                        #byte_order
+                        #pod
+                        #write_fields
                         Ok(())
                     }
+
+                    #validate
                 ),
                 TraitImplType::ProtocolWrite,
             )
         }
     };
 
-    impl_trait_for(ast, &impl_body, &trait_type)
+    let protocol_impl = impl_trait_for(ast, &impl_body, &trait_type);
+    if let Operation::Write = protocol_type {
+        let fixed_size_impl = codegen::fixed_size::struct_fixed_size(ast, strukt, &attribs);
+        quote!(
+            #protocol_impl
+            #fixed_size_impl
+        )
+    } else if let Some(fields) = &attribs.header {
+        let header = codegen::header::header(ast, strukt, &attribs, fields);
+        quote!(
+            #protocol_impl
+            #header
+        )
+    } else {
+        protocol_impl
+    }
 }
 
+#[allow(clippy::too_many_lines)]
 fn impl_for_enum(
     ast: &syn::DeriveInput,
     e: &syn::DataEnum,
@@ -106,61 +250,83 @@ fn impl_for_enum(
     };
     let discriminant_ty = &plan.discriminant_ty;
     let ctx_ty = attribs.ctx_ty();
+    let byte_order = attribs.byte_order_override();
 
     match protocol_type {
         Operation::Read => {
             let read_variant = codegen::enums::read_variant_fields(&plan, &attribs);
             let impl_body = quote!(
                 #[allow(unused_variables)]
-                fn read(__io_reader: &mut dyn ::bin_proto::BitRead,
-                        __byte_order: ::bin_proto::ByteOrder,
+                fn read(__io_reader: &mut dyn __bin_proto::BitRead,
+                        __byte_order: __bin_proto::ByteOrder,
                         __ctx: &mut #ctx_ty,
                         __tag: __Tag)
-                        -> ::bin_proto::Result<Self> {
-                    Ok(#read_variant)
+                        -> __bin_proto::Result<Self> {
+                    #byte_order
+                    #read_variant
                 }
             );
             let externally_tagged_read_impl = impl_trait_for(
                 ast,
                 &impl_body,
-                &TraitImplType::TaggedRead(discriminant_ty.clone()),
+                &TraitImplType::TaggedRead(Box::new(discriminant_ty.clone())),
             );
+            if plan.has_string_discriminant() {
+                return externally_tagged_read_impl;
+            }
 
             let read_discriminant = read_discriminant(&attribs);
+            let read_body = if plan.is_fieldless() {
+                let match_discriminant = codegen::enums::match_discriminant(&plan, &attribs);
+                quote!(
+                    let __discriminant: #discriminant_ty = #read_discriminant?;
+                    #match_discriminant
+                )
+            } else {
+                quote!(
+                    let __tag: #discriminant_ty = #read_discriminant?;
+                    <Self as __bin_proto::TaggedRead<_, _>>::read(__io_reader, __byte_order, __ctx, __tag)
+                )
+            };
             let impl_body = quote!(
                 #[allow(unused_variables)]
-                fn read(__io_reader: &mut dyn ::bin_proto::BitRead,
-                        __byte_order: ::bin_proto::ByteOrder,
+                fn read(__io_reader: &mut dyn __bin_proto::BitRead,
+                        __byte_order: __bin_proto::ByteOrder,
                         __ctx: &mut #ctx_ty)
-                        -> ::bin_proto::Result<Self> {
-                    let __tag: #discriminant_ty = #read_discriminant?;
-                    <Self as ::bin_proto::TaggedRead<_, _>>::read(__io_reader, __byte_order, __ctx, __tag)
+                        -> __bin_proto::Result<Self> {
+                    #byte_order
+                    #read_body
                 }
             );
             let protocol_read_impl = impl_trait_for(ast, &impl_body, &TraitImplType::ProtocolRead);
 
+            let read_discriminant_impl =
+                codegen::enums::read_discriminant_impl(ast, &plan, &attribs);
+
             quote!(
                 #externally_tagged_read_impl
                 #protocol_read_impl
+                #read_discriminant_impl
             )
         }
         Operation::Write => {
-            let write_variant = codegen::enums::write_variant_fields(&plan);
+            let write_variant = codegen::enums::write_variant_fields(&plan, &attribs);
             let impl_body = quote!(
                 #[allow(unused_variables)]
                 fn write(&self,
-                         __io_writer: &mut dyn ::bin_proto::BitWrite,
-                         __byte_order: ::bin_proto::ByteOrder,
+                         __io_writer: &mut dyn __bin_proto::BitWrite,
+                         __byte_order: __bin_proto::ByteOrder,
                          __ctx: &mut #ctx_ty)
-                         -> ::bin_proto::Result<()> {
+                         -> __bin_proto::Result<()> {
+                    #byte_order
                     #write_variant
-                    Ok(())
                 }
             );
             let externally_tagged_write_impl =
                 impl_trait_for(ast, &impl_body, &TraitImplType::UntaggedWrite);
 
             let variant_discriminant = variant_discriminant(&plan, &attribs);
+            let variant_name = variant_name(&plan);
             let impl_body = quote!(
                 type Discriminant = #discriminant_ty;
 
@@ -168,28 +334,52 @@ fn impl_for_enum(
                 fn discriminant(&self) -> Self::Discriminant {
                     #variant_discriminant
                 }
+
+                fn variant_name(&self) -> &'static str {
+                    #variant_name
+                }
             );
             let discriminable_impl = impl_trait_for(ast, &impl_body, &TraitImplType::Discriminable);
+            if plan.has_string_discriminant() {
+                return quote!(
+                    #externally_tagged_write_impl
+                    #discriminable_impl
+                );
+            }
 
-            let write_discriminant = write_discriminant(&attribs);
+            let write_discriminant = write_discriminant(&plan, &attribs);
+            let write_variant = if plan.is_fieldless() {
+                quote!(Ok(()))
+            } else {
+                quote!(<Self as __bin_proto::UntaggedWrite<_>>::write(
+                    self,
+                    __io_writer,
+                    __byte_order,
+                    __ctx
+                ))
+            };
             let impl_body = quote!(
                 #[allow(unused_variables)]
                 fn write(&self,
-                         __io_writer: &mut dyn ::bin_proto::BitWrite,
-                         __byte_order: ::bin_proto::ByteOrder,
+                         __io_writer: &mut dyn __bin_proto::BitWrite,
+                         __byte_order: __bin_proto::ByteOrder,
                          __ctx: &mut #ctx_ty)
-                         -> ::bin_proto::Result<()> {
+                         -> __bin_proto::Result<()> {
+                    #byte_order
                     #write_discriminant
-                    <Self as ::bin_proto::UntaggedWrite<_>>::write(self, __io_writer, __byte_order, __ctx)
+                    #write_variant
                 }
             );
             let protocol_write_impl =
                 impl_trait_for(ast, &impl_body, &TraitImplType::ProtocolWrite);
 
+            let fixed_size_impl = codegen::fixed_size::enum_fixed_size(ast, &plan, &attribs);
+
             quote!(
                 #externally_tagged_write_impl
                 #discriminable_impl
                 #protocol_write_impl
+                #fixed_size_impl
             )
         }
     }
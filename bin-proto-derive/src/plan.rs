@@ -1,14 +1,19 @@
-use crate::attr::Attrs;
+use crate::{attr::Attrs, codegen};
 use syn::{spanned::Spanned, Error, Result};
 
 pub struct Enum {
+    pub ident: syn::Ident,
     pub discriminant_ty: syn::Type,
+    pub discriminant_mask: Option<syn::Expr>,
     pub variants: Vec<EnumVariant>,
 }
 
 pub struct EnumVariant {
     pub ident: syn::Ident,
     pub discriminant_value: syn::Expr,
+    pub inner_tag: bool,
+    pub added_in: Option<u32>,
+    pub removed_in: Option<u32>,
     pub fields: syn::Fields,
 }
 
@@ -17,8 +22,12 @@ impl Enum {
         let attrs = Attrs::try_from(ast.attrs.as_slice())?;
         attrs.validate_enum(ast.span())?;
 
+        let discriminant_mask = attrs.discriminant_mask;
+        let discriminant_ty = attrs.discriminant_type.unwrap();
+        let string_discriminant = is_string(&discriminant_ty);
+        let mut next_discriminant = attrs.discriminant_base.map(i128::from);
         let plan = Self {
-            discriminant_ty: attrs.discriminant_type.unwrap(),
+            ident: ast.ident.clone(),
             variants: e
                 .variants
                 .iter()
@@ -26,22 +35,201 @@ impl Enum {
                     let attrs = Attrs::try_from(variant.attrs.as_slice())?;
                     attrs.validate_variant(variant.span())?;
 
-                    let discriminant_value = match variant.discriminant.as_ref().map(|a| &a.1) {
-                        Some(expr_lit) => expr_lit.clone(),
-                        None => attrs
-                            .discriminant
+                    let discriminant_value = match variant
+                        .discriminant
+                        .as_ref()
+                        .map(|a| &a.1)
+                        .or(attrs.discriminant.as_ref())
+                    {
+                        Some(expr) => expr.clone(),
+                        None if string_discriminant => syn::Expr::Lit(syn::ExprLit {
+                            attrs: Vec::new(),
+                            lit: syn::LitStr::new(&variant.ident.to_string(), variant.ident.span())
+                                .into(),
+                        }),
+                        None => next_discriminant
+                            .map(discriminant_expr)
                             .ok_or(Error::new(variant.span(), "No discriminant for variant"))?,
                     };
+                    if next_discriminant.is_some() {
+                        next_discriminant = literal_value(&discriminant_value)?
+                            .and_then(|value| value.checked_add(1));
+                    }
+
+                    validate_discriminant(&discriminant_value, &discriminant_ty)?;
+                    codegen::validate_trailing_fields(&variant.fields)?;
+
+                    if attrs.inner_tag {
+                        if discriminant_mask.is_none() {
+                            return Err(Error::new(
+                                variant.span(),
+                                "inner_tag attribute requires discriminant_mask attribute on enum",
+                            ));
+                        }
+                        if variant.fields.len() != 1 {
+                            return Err(Error::new(
+                                variant.span(),
+                                "inner_tag attribute requires variant with exactly one field",
+                            ));
+                        }
+                    }
 
                     let variant = EnumVariant {
                         ident: variant.ident.clone(),
                         discriminant_value,
+                        inner_tag: attrs.inner_tag,
+                        added_in: attrs.added_in,
+                        removed_in: attrs.removed_in,
                         fields: variant.fields.clone(),
                     };
                     Ok(variant)
                 })
                 .collect::<Result<_>>()?,
+            discriminant_ty,
+            discriminant_mask,
         };
+        validate_unique_discriminants(&plan.variants)?;
         Ok(plan)
     }
 }
+
+impl Enum {
+    /// Whether the discriminant is a `String`. A `String` has no encoding of
+    /// its own, so the enum can only be read and written externally tagged.
+    pub fn has_string_discriminant(&self) -> bool {
+        is_string(&self.discriminant_ty)
+    }
+
+    /// Whether the enum has no fields and nothing else to check, so that it
+    /// can be read and written as just its discriminant.
+    pub fn is_fieldless(&self) -> bool {
+        self.discriminant_mask.is_none()
+            && self.variants.iter().all(|variant| {
+                matches!(variant.fields, syn::Fields::Unit)
+                    && variant.added_in.is_none()
+                    && variant.removed_in.is_none()
+            })
+    }
+}
+
+fn is_string(ty: &syn::Type) -> bool {
+    matches!(
+        ty,
+        syn::Type::Path(syn::TypePath { qself: None, path })
+            if path.segments.last().map_or(false, |segment| segment.ident == "String")
+    )
+}
+
+/// The magnitude and sign of an integer literal discriminant, which may be
+/// negated.
+fn literal_discriminant(value: &syn::Expr) -> Option<(&syn::LitInt, bool)> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => Some((lit, false)),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match expr.as_ref() {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit),
+                ..
+            }) => Some((lit, true)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The value of an integer literal discriminant.
+fn literal_value(value: &syn::Expr) -> Result<Option<i128>> {
+    literal_discriminant(value)
+        .map(|(lit, negative)| {
+            let magnitude = lit.base10_parse::<i128>()?;
+            Ok(if negative { -magnitude } else { magnitude })
+        })
+        .transpose()
+}
+
+/// An unsuffixed integer literal discriminant, counted from
+/// `discriminant_base`.
+fn discriminant_expr(value: i128) -> syn::Expr {
+    let lit = proc_macro2::Literal::i128_unsuffixed(value.abs());
+    if value < 0 {
+        syn::parse_quote!(-#lit)
+    } else {
+        syn::parse_quote!(#lit)
+    }
+}
+
+/// Whether a discriminant can be used as a pattern. Other discriminants, such
+/// as constants and constant expressions, are compared in match guards.
+pub fn is_literal_discriminant(value: &syn::Expr) -> bool {
+    literal_discriminant(value).is_some()
+}
+
+/// Rejects integer literal discriminants that occur more than once. Other
+/// discriminants are only compared when read.
+fn validate_unique_discriminants(variants: &[EnumVariant]) -> Result<()> {
+    let mut seen = Vec::new();
+    for variant in variants {
+        if let Some((lit, negative)) = literal_discriminant(&variant.discriminant_value) {
+            let value = (lit.base10_parse::<u128>()?, negative);
+            if seen.contains(&value) {
+                return Err(Error::new(
+                    variant.discriminant_value.span(),
+                    "duplicate discriminant",
+                ));
+            }
+            seen.push(value);
+        }
+    }
+    Ok(())
+}
+
+/// Checks that an integer literal discriminant is within the range of a
+/// primitive integer discriminant type. Other discriminants are left to the
+/// compiler.
+fn validate_discriminant(value: &syn::Expr, ty: &syn::Type) -> Result<()> {
+    let (lit, negative) = match literal_discriminant(value) {
+        Some(literal) => literal,
+        None => return Ok(()),
+    };
+    let ty_name = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => match path.path.get_ident() {
+            Some(ident) => ident.to_string(),
+            None => return Ok(()),
+        },
+        _ => return Ok(()),
+    };
+    let (min, max): (i128, u128) = match ty_name.as_str() {
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" => (0, u64::MAX.into()),
+        "u128" => (0, u128::MAX),
+        "i8" => (i8::MIN.into(), i8::MAX.unsigned_abs().into()),
+        "i16" => (i16::MIN.into(), i16::MAX.unsigned_abs().into()),
+        "i32" => (i32::MIN.into(), i32::MAX.unsigned_abs().into()),
+        "i64" => (i64::MIN.into(), i64::MAX.unsigned_abs().into()),
+        "i128" => (i128::MIN, i128::MAX.unsigned_abs()),
+        _ => return Ok(()),
+    };
+
+    let magnitude: u128 = lit.base10_parse()?;
+    let fits = if negative {
+        magnitude <= min.unsigned_abs()
+    } else {
+        magnitude <= max
+    };
+    if fits {
+        Ok(())
+    } else {
+        Err(Error::new(
+            value.span(),
+            format!("discriminant does not fit in discriminant type {ty_name}"),
+        ))
+    }
+}
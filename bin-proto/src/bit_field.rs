@@ -1,4 +1,5 @@
-use crate::{BitRead, BitWrite, ByteOrder, Result};
+use crate::{BitRead, BitWrite, ByteOrder, Error, Result};
+use std::io;
 
 /// A trait for variable-width bit-level decoding.
 ///
@@ -23,6 +24,19 @@ pub trait BitFieldWrite<Ctx = ()> {
         ctx: &mut Ctx,
         bits: u32,
     ) -> Result<()>;
+
+    /// Checks that this value fits in `bits` bits, without writing it.
+    /// Derived implementations of `validate` call this for `bits` fields.
+    fn validate_bits(&self, bits: u32) -> Result<()> {
+        let _ = bits;
+        Ok(())
+    }
+}
+
+/// Fails like a write of a value that does not fit in its bits, without
+/// allocating a message.
+pub(crate) fn excessive_value() -> Error {
+    io::Error::from(io::ErrorKind::InvalidInput).into()
 }
 
 /// ```compile_fail
@@ -1,6 +1,8 @@
 use std::io;
 
-use bitstream_io::{BE, LE};
+use bitstream_io::{BigEndian, BitReader, Endianness, LittleEndian, BE, LE};
+
+use crate::ByteOrder;
 
 /// A bit-level equivalent of `std::io::Read`. An object-safe wrapper over
 /// `bitstream_io::BitRead`.
@@ -46,6 +48,169 @@ pub trait BitRead {
     fn read_i32_bf(&mut self, bits: u32) -> io::Result<i32>;
     fn read_u64_bf(&mut self, bits: u32) -> io::Result<u64>;
     fn read_i64_bf(&mut self, bits: u32) -> io::Result<i64>;
+
+    /// Reads all remaining bytes.
+    fn read_remaining(&mut self) -> io::Result<Vec<u8>> {
+        read_remaining(self)
+    }
+
+    /// Reads `bytes` bytes, sharing the underlying buffer instead of copying
+    /// it when the source is a `bytes::Bytes`.
+    #[cfg(feature = "bytes")]
+    fn read_to_shared(&mut self, bytes: usize) -> io::Result<bytes::Bytes> {
+        self.read_to_vec(bytes).map(Into::into)
+    }
+
+    /// Reads all remaining bytes, sharing the underlying buffer instead of
+    /// copying it when the source is a `bytes::Bytes`.
+    #[cfg(feature = "bytes")]
+    fn read_remaining_shared(&mut self) -> io::Result<bytes::Bytes> {
+        self.read_remaining().map(Into::into)
+    }
+}
+
+/// Reads bytes until the end of the stream, one at a time.
+pub(crate) fn read_remaining<R: BitRead + ?Sized>(read: &mut R) -> io::Result<Vec<u8>> {
+    let mut remaining = Vec::new();
+    loop {
+        match read.read_u8() {
+            Ok(byte) => remaining.push(byte),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(remaining),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Implements the required methods of `BitRead` by forwarding them to the
+/// `BitRead` in `self.$inner`.
+macro_rules! forward_bit_read {
+    ($inner:ident) => {
+        forward_bit_read! {
+            $inner;
+            fn read_bit(&mut self) -> io::Result<bool>;
+            fn skip(&mut self, bits: u32) -> io::Result<()>;
+            fn read_bytes(&mut self, buf: &mut [u8]) -> io::Result<()>;
+            fn read_to_vec(&mut self, bytes: usize) -> io::Result<Vec<u8>>;
+            fn read_unary0(&mut self) -> io::Result<u32>;
+            fn read_unary1(&mut self) -> io::Result<u32>;
+
+            fn read_u8(&mut self) -> io::Result<u8>;
+            fn read_i8(&mut self) -> io::Result<i8>;
+
+            fn read_u16_le(&mut self) -> io::Result<u16>;
+            fn read_u16_be(&mut self) -> io::Result<u16>;
+            fn read_i16_le(&mut self) -> io::Result<i16>;
+            fn read_i16_be(&mut self) -> io::Result<i16>;
+            fn read_u32_le(&mut self) -> io::Result<u32>;
+            fn read_u32_be(&mut self) -> io::Result<u32>;
+            fn read_i32_le(&mut self) -> io::Result<i32>;
+            fn read_i32_be(&mut self) -> io::Result<i32>;
+            fn read_u64_le(&mut self) -> io::Result<u64>;
+            fn read_u64_be(&mut self) -> io::Result<u64>;
+            fn read_i64_le(&mut self) -> io::Result<i64>;
+            fn read_i64_be(&mut self) -> io::Result<i64>;
+            fn read_u128_le(&mut self) -> io::Result<u128>;
+            fn read_u128_be(&mut self) -> io::Result<u128>;
+            fn read_i128_le(&mut self) -> io::Result<i128>;
+            fn read_i128_be(&mut self) -> io::Result<i128>;
+            fn read_f32_le(&mut self) -> io::Result<f32>;
+            fn read_f32_be(&mut self) -> io::Result<f32>;
+            fn read_f64_le(&mut self) -> io::Result<f64>;
+            fn read_f64_be(&mut self) -> io::Result<f64>;
+
+            fn read_u8_bf(&mut self, bits: u32) -> io::Result<u8>;
+            fn read_i8_bf(&mut self, bits: u32) -> io::Result<i8>;
+            fn read_u16_bf(&mut self, bits: u32) -> io::Result<u16>;
+            fn read_i16_bf(&mut self, bits: u32) -> io::Result<i16>;
+            fn read_u32_bf(&mut self, bits: u32) -> io::Result<u32>;
+            fn read_i32_bf(&mut self, bits: u32) -> io::Result<i32>;
+            fn read_u64_bf(&mut self, bits: u32) -> io::Result<u64>;
+            fn read_i64_bf(&mut self, bits: u32) -> io::Result<i64>;
+        }
+
+        fn byte_aligned(&self) -> bool {
+            $crate::BitRead::byte_aligned(&self.$inner)
+        }
+
+        fn byte_align(&mut self) {
+            $crate::BitRead::byte_align(&mut self.$inner);
+        }
+    };
+    ($inner:ident; $(fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        $(
+            fn $name(&mut self $(, $arg: $ty)*) -> $ret {
+                $crate::BitRead::$name(&mut self.$inner $(, $arg)*)
+            }
+        )*
+    };
+}
+
+/// A `BitReader` that reads the remaining bytes of a byte-aligned stream from
+/// the underlying reader in chunks, instead of one byte at a time.
+pub(crate) struct ChunkedReader<R: io::Read, E: Endianness> {
+    pub inner: BitReader<R, E>,
+}
+
+impl<R: io::Read, E: Endianness> ChunkedReader<R, E> {
+    pub fn new(read: R, endianness: E) -> Self {
+        Self {
+            inner: BitReader::endian(read, endianness),
+        }
+    }
+}
+
+impl<R: io::Read, E: Endianness> BitRead for ChunkedReader<R, E> {
+    forward_bit_read!(inner);
+
+    fn read_remaining(&mut self) -> io::Result<Vec<u8>> {
+        match self.inner.reader() {
+            Some(reader) => {
+                let mut remaining = Vec::new();
+                reader.read_to_end(&mut remaining)?;
+                Ok(remaining)
+            }
+            None => read_remaining(self),
+        }
+    }
+}
+
+/// Wraps a `std::io::Read`, so that values can be read from it with
+/// [`ProtocolRead::read`](crate::ProtocolRead::read). Bits are packed in the
+/// same order as `from_bytes` packs them for `byte_order`.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolRead};
+/// let mut reader = bin_proto::from_read([0x12, 0x34].as_slice(), ByteOrder::BigEndian);
+/// assert_eq!(u16::read(&mut *reader, ByteOrder::BigEndian, &mut ()).unwrap(), 0x1234);
+/// ```
+pub fn from_read<'a, R: io::Read + 'a>(read: R, byte_order: ByteOrder) -> Box<dyn BitRead + 'a> {
+    match byte_order {
+        ByteOrder::LittleEndian => Box::new(ChunkedReader::new(read, LittleEndian)),
+        ByteOrder::BigEndian => Box::new(ChunkedReader::new(read, BigEndian)),
+    }
+}
+
+/// The capacity of the buffer used by [`from_read_buffered`].
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Like [`from_read`], but reads `read` in chunks of up to 8 KiB instead of
+/// as few bytes as each value needs, which saves a system call per value on
+/// sockets and unbuffered files. The reader may consume bytes past the last
+/// value read from it, which are lost when it is dropped.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolRead};
+/// let mut reader = bin_proto::from_read_buffered([0x12, 0x34].as_slice(), ByteOrder::BigEndian);
+/// assert_eq!(u16::read(&mut *reader, ByteOrder::BigEndian, &mut ()).unwrap(), 0x1234);
+/// ```
+pub fn from_read_buffered<'a, R: io::Read + 'a>(
+    read: R,
+    byte_order: ByteOrder,
+) -> Box<dyn BitRead + 'a> {
+    from_read(
+        io::BufReader::with_capacity(READ_BUFFER_SIZE, read),
+        byte_order,
+    )
 }
 
 impl<T> BitRead for T
@@ -1,6 +1,8 @@
 use std::io;
 
-use bitstream_io::{BE, LE};
+use bitstream_io::{BigEndian, BitWriter, LittleEndian, BE, LE};
+
+use crate::ByteOrder;
 
 /// A bit-level equivalent of `std::io::Write`. An object-safe wrapper over
 /// `bitstream_io::BitWrite`.
@@ -44,6 +46,48 @@ pub trait BitWrite {
     fn write_i32_bf(&mut self, bits: u32, value: i32) -> io::Result<()>;
     fn write_u64_bf(&mut self, bits: u32, value: u64) -> io::Result<()>;
     fn write_i64_bf(&mut self, bits: u32, value: i64) -> io::Result<()>;
+
+    /// Whether values are written in their canonical encoding, in which
+    /// unordered collections such as `HashMap` are sorted by the encoding of
+    /// their keys. See [`ProtocolWrite::canonical_bytes_ctx`].
+    ///
+    /// [`ProtocolWrite::canonical_bytes_ctx`]: crate::ProtocolWrite::canonical_bytes_ctx
+    fn canonical(&self) -> bool {
+        false
+    }
+
+    /// Marks the start of the field `name`. Called by derived implementations
+    /// with `#[protocol(record_offsets)]`.
+    fn begin_field(&mut self, _name: &'static str) {}
+
+    /// Marks the end of the field most recently begun.
+    fn end_field(&mut self) {}
+}
+
+/// Wraps a `std::io::Write`, so that values can be written to it with
+/// [`ProtocolWrite::write`](crate::ProtocolWrite::write). Bits are packed in
+/// the same order as `bytes` packs them for `byte_order`.
+///
+/// A final partial byte is only written by [`BitWrite::byte_align`], which
+/// pads it with zeros.
+///
+/// Each value is written to `write` as soon as it is complete, so wrap a
+/// socket or file in an `io::BufWriter` to write it in larger chunks.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolWrite};
+/// let mut bytes = Vec::new();
+/// let mut writer = bin_proto::to_write(&mut bytes, ByteOrder::LittleEndian);
+/// 0x1234u16.write(&mut *writer, ByteOrder::LittleEndian, &mut ()).unwrap();
+/// writer.byte_align().unwrap();
+/// drop(writer);
+/// assert_eq!(bytes, [0x34, 0x12]);
+/// ```
+pub fn to_write<'a, W: io::Write + 'a>(write: W, byte_order: ByteOrder) -> Box<dyn BitWrite + 'a> {
+    match byte_order {
+        ByteOrder::LittleEndian => Box::new(BitWriter::endian(write, LittleEndian)),
+        ByteOrder::BigEndian => Box::new(BitWriter::endian(write, BigEndian)),
+    }
 }
 
 impl<T> BitWrite for T
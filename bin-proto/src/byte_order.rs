@@ -7,6 +7,15 @@ pub enum ByteOrder {
     BigEndian,
 }
 
+impl ByteOrder {
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Self = Self::LittleEndian;
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Self = Self::BigEndian;
+}
+
 macro_rules! impl_byte_order_helpers {
     ( $( $ty:ty => [ $read_name:ident : [ $read_le:ident, $read_be:ident ], $write_name:ident : [ $write_le:ident, $write_be:ident ] ] )* ) => {
         impl ByteOrder {
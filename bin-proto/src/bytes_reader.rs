@@ -0,0 +1,65 @@
+//! Reading from `bytes::Bytes` without copying
+
+use bitstream_io::{BitReader, Endianness};
+use bytes::Bytes;
+
+use crate::{bit_read, BitRead};
+use std::io;
+
+/// A `BitRead` over a `Bytes`, which hands out slices of it instead of copies
+/// when reading byte-aligned `Bytes` fields.
+pub(crate) struct BytesReader<E: Endianness> {
+    inner: BitReader<io::Cursor<Bytes>, E>,
+}
+
+impl<E: Endianness> BytesReader<E> {
+    pub fn new(bytes: Bytes, endianness: E) -> Self {
+        Self {
+            inner: BitReader::endian(io::Cursor::new(bytes), endianness),
+        }
+    }
+
+    /// Slices `len` bytes off the underlying buffer, or returns `None` if the
+    /// reader is not byte-aligned.
+    fn slice(&mut self, len: Option<usize>) -> Option<io::Result<Bytes>> {
+        let cursor = self.inner.reader()?;
+        let bytes = cursor.get_ref();
+        let start =
+            usize::try_from(cursor.position()).map_or(bytes.len(), |start| start.min(bytes.len()));
+        let end = match len {
+            Some(len) if len > bytes.len() - start => {
+                return Some(Err(io::ErrorKind::UnexpectedEof.into()))
+            }
+            Some(len) => start + len,
+            None => bytes.len(),
+        };
+        let slice = bytes.slice(start..end);
+        cursor.set_position(end as u64);
+        Some(Ok(slice))
+    }
+}
+
+impl<E: Endianness> BitRead for BytesReader<E> {
+    forward_bit_read!(inner);
+
+    fn read_remaining(&mut self) -> io::Result<Vec<u8>> {
+        match self.slice(None) {
+            Some(slice) => slice.map(Vec::from),
+            None => bit_read::read_remaining(self),
+        }
+    }
+
+    fn read_to_shared(&mut self, bytes: usize) -> io::Result<Bytes> {
+        match self.slice(Some(bytes)) {
+            Some(slice) => slice,
+            None => self.read_to_vec(bytes).map(Into::into),
+        }
+    }
+
+    fn read_remaining_shared(&mut self) -> io::Result<Bytes> {
+        match self.slice(None) {
+            Some(slice) => slice,
+            None => bit_read::read_remaining(self).map(Into::into),
+        }
+    }
+}
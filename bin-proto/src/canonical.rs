@@ -0,0 +1,85 @@
+//! Writing values in a canonical encoding
+
+use crate::BitWrite;
+use std::io;
+
+/// Forwards writes to a `BitWrite`, reporting itself as
+/// [`BitWrite::canonical`], so that unordered collections are written sorted.
+pub(crate) struct CanonicalWrite<'a> {
+    inner: &'a mut dyn BitWrite,
+}
+
+impl<'a> CanonicalWrite<'a> {
+    pub fn new(inner: &'a mut dyn BitWrite) -> Self {
+        Self { inner }
+    }
+}
+
+macro_rules! forward {
+    ($(fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*);)*) => {
+        $(
+            fn $name(&mut self $(, $arg: $ty)*) -> io::Result<()> {
+                self.inner.$name($($arg),*)
+            }
+        )*
+    };
+}
+
+impl BitWrite for CanonicalWrite<'_> {
+    forward! {
+        fn write_bit(&mut self, bit: bool);
+        fn write_bytes(&mut self, buf: &[u8]);
+        fn write_unary0(&mut self, value: u32);
+        fn write_unary1(&mut self, value: u32);
+        fn byte_align(&mut self);
+
+        fn write_u8(&mut self, value: u8);
+        fn write_i8(&mut self, value: i8);
+
+        fn write_u16_le(&mut self, value: u16);
+        fn write_u16_be(&mut self, value: u16);
+        fn write_i16_le(&mut self, value: i16);
+        fn write_i16_be(&mut self, value: i16);
+        fn write_u32_le(&mut self, value: u32);
+        fn write_u32_be(&mut self, value: u32);
+        fn write_i32_le(&mut self, value: i32);
+        fn write_i32_be(&mut self, value: i32);
+        fn write_u64_le(&mut self, value: u64);
+        fn write_u64_be(&mut self, value: u64);
+        fn write_i64_le(&mut self, value: i64);
+        fn write_i64_be(&mut self, value: i64);
+        fn write_u128_le(&mut self, value: u128);
+        fn write_u128_be(&mut self, value: u128);
+        fn write_i128_le(&mut self, value: i128);
+        fn write_i128_be(&mut self, value: i128);
+        fn write_f32_le(&mut self, value: f32);
+        fn write_f32_be(&mut self, value: f32);
+        fn write_f64_le(&mut self, value: f64);
+        fn write_f64_be(&mut self, value: f64);
+
+        fn write_u8_bf(&mut self, bits: u32, value: u8);
+        fn write_i8_bf(&mut self, bits: u32, value: i8);
+        fn write_u16_bf(&mut self, bits: u32, value: u16);
+        fn write_i16_bf(&mut self, bits: u32, value: i16);
+        fn write_u32_bf(&mut self, bits: u32, value: u32);
+        fn write_i32_bf(&mut self, bits: u32, value: i32);
+        fn write_u64_bf(&mut self, bits: u32, value: u64);
+        fn write_i64_bf(&mut self, bits: u32, value: i64);
+    }
+
+    fn byte_aligned(&self) -> bool {
+        self.inner.byte_aligned()
+    }
+
+    fn canonical(&self) -> bool {
+        true
+    }
+
+    fn begin_field(&mut self, name: &'static str) {
+        self.inner.begin_field(name);
+    }
+
+    fn end_field(&mut self) {
+        self.inner.end_field();
+    }
+}
@@ -0,0 +1,186 @@
+//! Checksums over the encoded bytes of a field
+
+use bitstream_io::{BigEndian, BitReader, BitWriter, LittleEndian};
+
+use crate::{
+    canonical::CanonicalWrite, BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite,
+    Result,
+};
+use std::io;
+
+/// A checksum algorithm, computed over the encoded bytes of a field and placed
+/// directly after it.
+pub trait Checksum {
+    type Output: PartialEq;
+
+    fn checksum(bytes: &[u8]) -> Self::Output;
+
+    /// Reads a value using `read_value`, followed by its checksum, which is
+    /// verified against the bytes that were consumed.
+    fn read_checksummed<Ctx, T>(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        read_value: impl FnOnce(&mut dyn BitRead, &mut Ctx) -> Result<T>,
+    ) -> Result<T>
+    where
+        Self::Output: ProtocolRead<Ctx>,
+    {
+        let mut bytes = Vec::new();
+        let recorder = Recorder {
+            read,
+            bytes: &mut bytes,
+        };
+        let value = match byte_order {
+            ByteOrder::LittleEndian => {
+                let mut reader = BitReader::endian(recorder, LittleEndian);
+                let value = read_value(&mut reader, ctx)?;
+                check_aligned(bitstream_io::BitRead::byte_aligned(&reader))?;
+                value
+            }
+            ByteOrder::BigEndian => {
+                let mut reader = BitReader::endian(recorder, BigEndian);
+                let value = read_value(&mut reader, ctx)?;
+                check_aligned(bitstream_io::BitRead::byte_aligned(&reader))?;
+                value
+            }
+        };
+
+        let checksum = Self::Output::read(read, byte_order, ctx)?;
+        if checksum == Self::checksum(&bytes) {
+            Ok(value)
+        } else {
+            Err(Error::ChecksumMismatch)
+        }
+    }
+
+    /// Writes a value using `write_value`, followed by the checksum of the
+    /// bytes that were produced.
+    fn write_checksummed<Ctx>(
+        write: &mut dyn BitWrite,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        write_value: impl FnOnce(&mut dyn BitWrite, &mut Ctx) -> Result<()>,
+    ) -> Result<()>
+    where
+        Self::Output: ProtocolWrite<Ctx>,
+    {
+        // The field is written to its own buffer, which must stay canonical
+        // if the outer writer is.
+        let canonical = write.canonical();
+        let write_value = |writer: &mut dyn BitWrite, ctx: &mut Ctx| {
+            if canonical {
+                write_value(&mut CanonicalWrite::new(writer), ctx)
+            } else {
+                write_value(writer, ctx)
+            }
+        };
+        let mut bytes = Vec::new();
+        match byte_order {
+            ByteOrder::LittleEndian => {
+                let mut writer = BitWriter::endian(&mut bytes, LittleEndian);
+                write_value(&mut writer, ctx)?;
+                check_aligned(bitstream_io::BitWrite::byte_aligned(&writer))?;
+            }
+            ByteOrder::BigEndian => {
+                let mut writer = BitWriter::endian(&mut bytes, BigEndian);
+                write_value(&mut writer, ctx)?;
+                check_aligned(bitstream_io::BitWrite::byte_aligned(&writer))?;
+            }
+        }
+
+        write.write_bytes(&bytes)?;
+        Self::checksum(&bytes).write(write, byte_order, ctx)
+    }
+}
+
+/// CRC-16/CCITT-FALSE: polynomial `0x1021`, initial value `0xffff`, no
+/// reflection, no final XOR.
+pub struct Crc16;
+
+impl Checksum for Crc16 {
+    type Output = u16;
+
+    fn checksum(bytes: &[u8]) -> u16 {
+        bytes.iter().fold(0xffff, |crc, byte| {
+            (0..8).fold(crc ^ (u16::from(*byte) << 8), |crc, _| {
+                if crc & 0x8000 == 0 {
+                    crc << 1
+                } else {
+                    (crc << 1) ^ 0x1021
+                }
+            })
+        })
+    }
+}
+
+/// CRC-32/ISO-HDLC, as used by zlib, PNG and Ethernet: reflected polynomial
+/// `0xedb88320`, initial value and final XOR `0xffffffff`.
+pub struct Crc32;
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    fn checksum(bytes: &[u8]) -> u32 {
+        !bytes.iter().fold(0xffff_ffff, |crc, byte| {
+            (0..8).fold(crc ^ u32::from(*byte), |crc, _| {
+                if crc & 1 == 0 {
+                    crc >> 1
+                } else {
+                    (crc >> 1) ^ 0xedb8_8320
+                }
+            })
+        })
+    }
+}
+
+/// Forwards reads to a `BitRead`, keeping a copy of every byte.
+struct Recorder<'a> {
+    read: &'a mut dyn BitRead,
+    bytes: &'a mut Vec<u8>,
+}
+
+impl io::Read for Recorder<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read.read_bytes(buf)?;
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+}
+
+fn check_aligned(aligned: bool) -> Result<()> {
+    if aligned {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "checksummed field does not span whole bytes",
+        )
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc16_check_value() {
+        assert_eq!(Crc16::checksum(b"123456789"), 0x29b1);
+    }
+
+    #[test]
+    fn crc16_empty() {
+        assert_eq!(Crc16::checksum(&[]), 0xffff);
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(Crc32::checksum(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn crc32_empty() {
+        assert_eq!(Crc32::checksum(&[]), 0);
+    }
+}
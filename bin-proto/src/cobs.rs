@@ -0,0 +1,184 @@
+//! Zero-delimited framing of whole messages with Consistent Overhead Byte
+//! Stuffing
+//!
+//! COBS removes every zero byte from a message, at a cost of at most one byte
+//! per 254, so that a single `0x00` can end each frame. This suits serial
+//! links and other streams without a length prefix, since a reader can
+//! resynchronize at the next zero byte.
+//!
+//! ```
+//! # use bin_proto::{cobs, ByteOrder};
+//! let mut stream = Vec::new();
+//! cobs::write_frame(&mut stream, &0x0100_0200u32, ByteOrder::BigEndian).unwrap();
+//! assert_eq!(stream, [0x02, 0x01, 0x02, 0x02, 0x01, 0x00]);
+//!
+//! let value: u32 = cobs::read_frame(&mut stream.as_slice(), ByteOrder::BigEndian, usize::MAX).unwrap();
+//! assert_eq!(value, 0x0100_0200);
+//! ```
+
+use crate::{ByteOrder, Error, ProtocolRead, ProtocolWrite, Result};
+use std::io;
+
+/// The largest code byte, for a block of 254 non-zero bytes not followed by a
+/// zero.
+const MAX_CODE: u8 = 0xff;
+
+/// Encodes `data` so that it contains no zero bytes.
+#[must_use]
+pub fn encode(data: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(data.len() + data.len() / 254 + 1);
+    let mut code_index = 0;
+    let mut code = 1;
+    encoded.push(0);
+    for &byte in data {
+        if byte != 0 {
+            encoded.push(byte);
+            code += 1;
+        }
+        if byte == 0 || code == MAX_CODE {
+            encoded[code_index] = code;
+            code_index = encoded.len();
+            encoded.push(0);
+            code = 1;
+        }
+    }
+    encoded[code_index] = code;
+    encoded
+}
+
+/// Decodes data produced by [`encode`]. Fails with
+/// `io::ErrorKind::InvalidData` if `encoded` contains a zero byte, or a block
+/// runs past its end.
+pub fn decode(encoded: &[u8]) -> Result<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut rest = encoded;
+    while let Some((&code, tail)) = rest.split_first() {
+        let len = usize::from(code)
+            .checked_sub(1)
+            .ok_or_else(|| malformed("zero byte inside a COBS frame"))?;
+        if len > tail.len() {
+            return Err(malformed("COBS block runs past the end of its frame"));
+        }
+        let (block, tail) = tail.split_at(len);
+        if block.contains(&0) {
+            return Err(malformed("zero byte inside a COBS frame"));
+        }
+        decoded.extend_from_slice(block);
+        if code != MAX_CODE && !tail.is_empty() {
+            decoded.push(0);
+        }
+        rest = tail;
+    }
+    Ok(decoded)
+}
+
+fn malformed(message: &str) -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
+
+/// Writes `value` to `writer`, COBS-encoded and followed by a zero byte.
+pub fn write_frame_ctx<T, Ctx>(
+    writer: &mut impl io::Write,
+    value: &T,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+) -> Result<()>
+where
+    T: ProtocolWrite<Ctx> + ?Sized,
+{
+    let message = value.bytes_ctx(byte_order, ctx)?;
+    writer.write_all(&encode(&message))?;
+    writer.write_all(&[0])?;
+    Ok(())
+}
+
+/// Reads a value written by [`write_frame_ctx`] from `reader`, consuming
+/// exactly one frame, up to and including its zero byte. Frames of more than
+/// `max_frame_size` encoded bytes fail with [`Error::FrameTooLarge`] without
+/// being buffered.
+pub fn read_frame_ctx<T, Ctx>(
+    reader: &mut impl io::Read,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+    max_frame_size: usize,
+) -> Result<T>
+where
+    T: ProtocolRead<Ctx>,
+{
+    let mut encoded = Vec::new();
+    loop {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        if byte[0] == 0 {
+            break;
+        }
+        if encoded.len() == max_frame_size {
+            return Err(Error::FrameTooLarge {
+                size: encoded.len() + 1,
+                max: max_frame_size,
+            });
+        }
+        encoded.push(byte[0]);
+    }
+    T::from_bytes_ctx(&decode(&encoded)?, byte_order, ctx)
+}
+
+/// Writes `value` to `writer`, COBS-encoded and followed by a zero byte.
+pub fn write_frame<T>(writer: &mut impl io::Write, value: &T, byte_order: ByteOrder) -> Result<()>
+where
+    T: ProtocolWrite + ?Sized,
+{
+    write_frame_ctx(writer, value, byte_order, &mut ())
+}
+
+/// Reads a value written by [`write_frame`] from `reader`, consuming exactly
+/// one frame.
+pub fn read_frame<T>(
+    reader: &mut impl io::Read,
+    byte_order: ByteOrder,
+    max_frame_size: usize,
+) -> Result<T>
+where
+    T: ProtocolRead,
+{
+    read_frame_ctx(reader, byte_order, &mut (), max_frame_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_known_vectors() {
+        for (data, encoded) in [
+            (&[][..], &[0x01][..]),
+            (&[0x00], &[0x01, 0x01]),
+            (&[0x00, 0x00], &[0x01, 0x01, 0x01]),
+            (&[0x11, 0x22, 0x00, 0x33], &[0x03, 0x11, 0x22, 0x02, 0x33]),
+            (&[0x11, 0x00, 0x00, 0x00], &[0x02, 0x11, 0x01, 0x01, 0x01]),
+        ] {
+            assert_eq!(encode(data), encoded);
+            assert_eq!(decode(encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn splits_long_blocks() {
+        let data: Vec<u8> = (1..=255).collect();
+        let encoded = encode(&data);
+        assert_eq!(encoded.len(), 257);
+        assert_eq!(encoded[0], 0xff);
+        assert_eq!(&encoded[255..], [0x02, 0xff]);
+        assert_eq!(decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn rejects_malformed_frames() {
+        for encoded in [&[0x03, 0x11][..], &[0x02, 0x00], &[0x00]] {
+            assert!(matches!(
+                decode(encoded),
+                Err(Error::IO(e)) if e.kind() == io::ErrorKind::InvalidData
+            ));
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Counting the bits written for a single value
+
+use crate::BitWrite;
+use std::io;
+
+/// Forwards writes to a `BitWrite`, counting the bits written. Used by derived
+/// implementations with `#[protocol(total_bits = <n>)]`.
+#[doc(hidden)]
+pub struct CountingWrite<'a> {
+    inner: &'a mut dyn BitWrite,
+    written: u64,
+}
+
+impl<'a> CountingWrite<'a> {
+    pub fn new(inner: &'a mut dyn BitWrite) -> Self {
+        Self { inner, written: 0 }
+    }
+
+    /// The number of bits written so far.
+    #[must_use]
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    fn count<T>(&mut self, bits: u64, result: io::Result<T>) -> io::Result<T> {
+        if result.is_ok() {
+            self.written += bits;
+        }
+        result
+    }
+}
+
+macro_rules! count {
+    ($(fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) = $bits:expr;)*) => {
+        $(
+            fn $name(&mut self $(, $arg: $ty)*) -> io::Result<()> {
+                let bits = $bits;
+                let result = self.inner.$name($($arg),*);
+                self.count(bits, result)
+            }
+        )*
+    };
+}
+
+impl BitWrite for CountingWrite<'_> {
+    count! {
+        fn write_bit(&mut self, bit: bool) = 1;
+        fn write_bytes(&mut self, buf: &[u8]) = buf.len() as u64 * 8;
+        fn write_unary0(&mut self, value: u32) = u64::from(value) + 1;
+        fn write_unary1(&mut self, value: u32) = u64::from(value) + 1;
+
+        fn write_u8(&mut self, value: u8) = 8;
+        fn write_i8(&mut self, value: i8) = 8;
+
+        fn write_u16_le(&mut self, value: u16) = 16;
+        fn write_u16_be(&mut self, value: u16) = 16;
+        fn write_i16_le(&mut self, value: i16) = 16;
+        fn write_i16_be(&mut self, value: i16) = 16;
+        fn write_u32_le(&mut self, value: u32) = 32;
+        fn write_u32_be(&mut self, value: u32) = 32;
+        fn write_i32_le(&mut self, value: i32) = 32;
+        fn write_i32_be(&mut self, value: i32) = 32;
+        fn write_u64_le(&mut self, value: u64) = 64;
+        fn write_u64_be(&mut self, value: u64) = 64;
+        fn write_i64_le(&mut self, value: i64) = 64;
+        fn write_i64_be(&mut self, value: i64) = 64;
+        fn write_u128_le(&mut self, value: u128) = 128;
+        fn write_u128_be(&mut self, value: u128) = 128;
+        fn write_i128_le(&mut self, value: i128) = 128;
+        fn write_i128_be(&mut self, value: i128) = 128;
+        fn write_f32_le(&mut self, value: f32) = 32;
+        fn write_f32_be(&mut self, value: f32) = 32;
+        fn write_f64_le(&mut self, value: f64) = 64;
+        fn write_f64_be(&mut self, value: f64) = 64;
+
+        fn write_u8_bf(&mut self, bits: u32, value: u8) = u64::from(bits);
+        fn write_i8_bf(&mut self, bits: u32, value: i8) = u64::from(bits);
+        fn write_u16_bf(&mut self, bits: u32, value: u16) = u64::from(bits);
+        fn write_i16_bf(&mut self, bits: u32, value: i16) = u64::from(bits);
+        fn write_u32_bf(&mut self, bits: u32, value: u32) = u64::from(bits);
+        fn write_i32_bf(&mut self, bits: u32, value: i32) = u64::from(bits);
+        fn write_u64_bf(&mut self, bits: u32, value: u64) = u64::from(bits);
+        fn write_i64_bf(&mut self, bits: u32, value: i64) = u64::from(bits);
+    }
+
+    fn byte_aligned(&self) -> bool {
+        self.inner.byte_aligned()
+    }
+
+    // Padding depends on the position of the underlying writer, not on what
+    // was written through this one.
+    fn byte_align(&mut self) -> io::Result<()> {
+        let mut padding = 0;
+        while !self.inner.byte_aligned() {
+            self.inner.write_bit(false)?;
+            padding += 1;
+        }
+        self.written += padding;
+        Ok(())
+    }
+
+    fn canonical(&self) -> bool {
+        self.inner.canonical()
+    }
+
+    fn begin_field(&mut self, name: &'static str) {
+        self.inner.begin_field(name);
+    }
+
+    fn end_field(&mut self) {
+        self.inner.end_field();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitstream_io::{BigEndian, BitWriter};
+
+    use super::*;
+
+    #[test]
+    fn counts_bits_and_padding() {
+        let mut data = Vec::new();
+        let mut writer = BitWriter::endian(&mut data, BigEndian);
+        writer.write_u8_bf(3, 0b101).unwrap();
+        let mut counting = CountingWrite::new(&mut writer);
+        counting.write_u16_be(0x0102).unwrap();
+        counting.byte_align().unwrap();
+        counting.write_bytes(&[1, 2]).unwrap();
+        assert_eq!(counting.written(), 16 + 5 + 16);
+        assert_eq!(data, [0b1010_0000, 0x20, 0x40, 1, 2]);
+    }
+}
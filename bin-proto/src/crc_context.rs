@@ -0,0 +1,229 @@
+//! Checksums over all bytes of a message
+
+use bitstream_io::{BigEndian, BitReader, BitWriter, LittleEndian};
+
+use crate::{
+    BitRead, BitWrite, ByteOrder, Checksum, Crc16, Error, ProtocolRead, ProtocolWrite, Result,
+};
+use std::{cell::RefCell, fmt, io, marker::PhantomData, rc::Rc};
+
+/// A context that keeps every byte read or written through
+/// [`CrcContext::from_bytes`] and [`CrcContext::bytes`], so that a trailing
+/// [`MessageChecksum`] covers the whole message before it.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, CrcContext, MessageChecksum, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+/// #[protocol(ctx = "CrcContext")]
+/// struct Message {
+///     kind: u8,
+///     value: u16,
+///     crc: MessageChecksum,
+/// }
+///
+/// let message = Message {
+///     kind: 1,
+///     value: 0x0203,
+///     crc: MessageChecksum::default(),
+/// };
+/// let bytes = CrcContext::new()
+///     .bytes(&message, ByteOrder::BigEndian)
+///     .unwrap();
+/// assert_eq!(
+///     CrcContext::new()
+///         .from_bytes::<Message>(&bytes, ByteOrder::BigEndian)
+///         .unwrap(),
+///     message
+/// );
+/// ```
+pub struct CrcContext<C = Crc16> {
+    bytes: Rc<RefCell<Vec<u8>>>,
+    checksum: PhantomData<C>,
+}
+
+impl<C: Checksum> CrcContext<C> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The checksum of the bytes read or written so far.
+    #[must_use]
+    pub fn checksum(&self) -> C::Output {
+        C::checksum(&self.bytes.borrow())
+    }
+
+    /// Parses a value from `bytes`, with this context.
+    pub fn from_bytes<T: ProtocolRead<Self>>(
+        &mut self,
+        bytes: &[u8],
+        byte_order: ByteOrder,
+    ) -> Result<T> {
+        self.bytes.borrow_mut().clear();
+        let recorder = Recorder {
+            read: io::Cursor::new(bytes),
+            bytes: Rc::clone(&self.bytes),
+        };
+        match byte_order {
+            ByteOrder::LittleEndian => T::read(
+                &mut BitReader::endian(recorder, LittleEndian),
+                byte_order,
+                self,
+            ),
+            ByteOrder::BigEndian => T::read(
+                &mut BitReader::endian(recorder, BigEndian),
+                byte_order,
+                self,
+            ),
+        }
+    }
+
+    /// Gets the raw bytes of a value, with this context.
+    pub fn bytes<T: ProtocolWrite<Self> + ?Sized>(
+        &mut self,
+        value: &T,
+        byte_order: ByteOrder,
+    ) -> Result<Vec<u8>> {
+        self.bytes.borrow_mut().clear();
+        let shared = Shared(Rc::clone(&self.bytes));
+        match byte_order {
+            ByteOrder::LittleEndian => {
+                let mut writer = BitWriter::endian(shared, LittleEndian);
+                value.write(&mut writer, byte_order, self)?;
+                writer.byte_align()?;
+            }
+            ByteOrder::BigEndian => {
+                let mut writer = BitWriter::endian(shared, BigEndian);
+                value.write(&mut writer, byte_order, self)?;
+                writer.byte_align()?;
+            }
+        }
+        Ok(self.bytes.borrow().clone())
+    }
+}
+
+impl<C> Default for CrcContext<C> {
+    fn default() -> Self {
+        Self {
+            bytes: Rc::default(),
+            checksum: PhantomData,
+        }
+    }
+}
+
+impl<C> fmt::Debug for CrcContext<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CrcContext")
+            .field("bytes", &self.bytes.borrow())
+            .finish()
+    }
+}
+
+/// The checksum of all bytes of a message before it, as kept by
+/// [`CrcContext`]. It is written from the context, and verified against it on
+/// read, so it holds no value.
+pub struct MessageChecksum<C = Crc16>(PhantomData<C>);
+
+impl<C> Default for MessageChecksum<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C> Clone for MessageChecksum<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for MessageChecksum<C> {}
+
+impl<C> PartialEq for MessageChecksum<C> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<C> Eq for MessageChecksum<C> {}
+
+impl<C> fmt::Debug for MessageChecksum<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MessageChecksum")
+    }
+}
+
+impl<C> ProtocolRead<CrcContext<C>> for MessageChecksum<C>
+where
+    C: Checksum,
+    C::Output: ProtocolRead<CrcContext<C>>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut CrcContext<C>,
+    ) -> Result<Self> {
+        check_aligned(read.byte_aligned())?;
+        let expected = ctx.checksum();
+        if C::Output::read(read, byte_order, ctx)? == expected {
+            Ok(Self::default())
+        } else {
+            Err(Error::ChecksumMismatch)
+        }
+    }
+}
+
+impl<C> ProtocolWrite<CrcContext<C>> for MessageChecksum<C>
+where
+    C: Checksum,
+    C::Output: ProtocolWrite<CrcContext<C>>,
+{
+    fn write(
+        &self,
+        write: &mut dyn BitWrite,
+        byte_order: ByteOrder,
+        ctx: &mut CrcContext<C>,
+    ) -> Result<()> {
+        check_aligned(write.byte_aligned())?;
+        ctx.checksum().write(write, byte_order, ctx)
+    }
+}
+
+/// Forwards reads to `read`, keeping a copy of every byte.
+struct Recorder<R> {
+    read: R,
+    bytes: Rc<RefCell<Vec<u8>>>,
+}
+
+impl<R: io::Read> io::Read for Recorder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.read.read(buf)?;
+        self.bytes.borrow_mut().extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+}
+
+/// Writes to a buffer shared with a `CrcContext`.
+struct Shared(Rc<RefCell<Vec<u8>>>);
+
+impl io::Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn check_aligned(aligned: bool) -> Result<()> {
+    if aligned {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message checksum does not start on a byte boundary",
+        )
+        .into())
+    }
+}
@@ -0,0 +1,104 @@
+//! Limiting how deeply values nest while reading
+
+use crate::{Error, Result};
+
+/// A context that limits how deeply recursive values nest while reading.
+///
+/// Derived implementations enter a level before reading a field whose type
+/// refers to the type being derived, such as `Box<Self>` or `Vec<Node>` in
+/// `Node`, and leave it afterwards. A context for such a type must implement
+/// this trait; one that wants a limit can embed a [`MaxDepth`] and forward to
+/// it.
+pub trait DepthCtx {
+    /// Enters one more level of nesting, failing if that exceeds the limit.
+    fn enter(&mut self) -> Result<()>;
+
+    /// Leaves a level entered by [`DepthCtx::enter`].
+    fn leave(&mut self);
+}
+
+/// Does not limit nesting.
+impl DepthCtx for () {
+    fn enter(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn leave(&mut self) {}
+}
+
+/// A context that fails reads nesting recursive values more than `max` levels
+/// deep with [`Error::MaxDepthExceeded`], so that input for a recursive type
+/// cannot overflow the stack.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, Error, MaxDepth, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, ProtocolRead, ProtocolWrite)]
+/// struct List {
+///     value: u8,
+///     #[protocol(tag(type = "bool", write_value = "self.next.is_some()"))]
+///     next: Option<Box<List>>,
+/// }
+///
+/// let bytes = [1, 1, 2, 1, 3, 0];
+/// assert!(List::from_bytes_ctx(&bytes, ByteOrder::BigEndian, &mut MaxDepth::new(3)).is_ok());
+/// assert!(matches!(
+///     List::from_bytes_ctx(&bytes, ByteOrder::BigEndian, &mut MaxDepth::new(2)),
+///     Err(Error::MaxDepthExceeded { max: 2 })
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxDepth {
+    max: usize,
+    depth: usize,
+}
+
+impl MaxDepth {
+    #[must_use]
+    pub fn new(max: usize) -> Self {
+        Self { max, depth: 0 }
+    }
+}
+
+impl DepthCtx for MaxDepth {
+    fn enter(&mut self) -> Result<()> {
+        if self.depth >= self.max {
+            return Err(Error::MaxDepthExceeded { max: self.max });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+/// Reads a recursive field one level deeper. Used by derived implementations.
+#[doc(hidden)]
+pub fn read_recursive<Ctx: DepthCtx, T>(
+    ctx: &mut Ctx,
+    read: impl FnOnce(&mut Ctx) -> Result<T>,
+) -> Result<T> {
+    ctx.enter()?;
+    let result = read(ctx);
+    ctx.leave();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_depth_counts_nesting() {
+        let mut ctx = MaxDepth::new(2);
+        let result = read_recursive(&mut ctx, |ctx| {
+            read_recursive(ctx, |ctx| {
+                assert_eq!(ctx.depth, 2);
+                read_recursive(ctx, |_| Ok(()))
+            })
+        });
+        assert!(matches!(result, Err(Error::MaxDepthExceeded { max: 2 })));
+        assert_eq!(ctx.depth, 0);
+    }
+}
@@ -1,6 +1,33 @@
-/// A trait for types with discriminants. Automatically derived for `enum`s.
+/// A trait for types with discriminants. Implemented by
+/// `#[derive(ProtocolWrite)]` for `enum`s, so that the discriminant of a value
+/// can be inspected without writing it, such as to log message types or to
+/// dispatch on them.
+///
+/// ```
+/// # use bin_proto::{Discriminable, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(discriminant_type = "u8")]
+/// enum Message {
+///     #[protocol(discriminant = "1")]
+///     Ping,
+///     #[protocol(discriminant = "0x10")]
+///     Data(u16),
+/// }
+///
+/// let message = Message::Data(7);
+/// assert_eq!(message.discriminant(), 0x10);
+/// assert_eq!(message.variant_name(), "Data");
+/// ```
 pub trait Discriminable {
     type Discriminant;
 
+    /// The discriminant of this value's variant, as it is written.
     fn discriminant(&self) -> Self::Discriminant;
+
+    /// The name of this value's variant. Defaults to the name of the type, so
+    /// that implementations written before this method was added still
+    /// compile.
+    fn variant_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
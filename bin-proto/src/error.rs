@@ -1,5 +1,21 @@
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The number of discriminants listed by `Error::UnknownEnumDiscriminant`.
+pub const MAX_EXPECTED_DISCRIMINANTS: usize = 16;
+
+fn expected_list(expected: &[String]) -> String {
+    if expected.is_empty() {
+        String::new()
+    } else {
+        format!(" (expected {})", expected.join(", "))
+    }
+}
+
+// Clippy dates `NulError` to 1.64, when it moved to `alloc`, though `std` has
+// exported it since 1.0.
+#[allow(clippy::incompatible_msrv)]
+type NulError = std::ffi::NulError;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
@@ -7,17 +23,70 @@ pub enum Error {
     #[error(transparent)]
     FromUtf8(#[from] std::string::FromUtf8Error),
     #[error(transparent)]
-    FromNulError(#[from] std::ffi::NulError),
+    FromNulError(#[from] NulError),
     #[error(transparent)]
     TryFromIntError(#[from] std::num::TryFromIntError),
-    #[error("Unknown enum discriminant: '{0}'")]
-    UnknownEnumDiscriminant(String),
+    #[error(
+        "Unknown discriminant {discriminant} for enum {enum_name}{}",
+        expected_list(.expected)
+    )]
+    UnknownEnumDiscriminant {
+        enum_name: &'static str,
+        discriminant: String,
+        /// The discriminants of the variants, at most
+        /// [`MAX_EXPECTED_DISCRIMINANTS`] of them, followed by `"..."` if
+        /// there are more.
+        expected: Vec<String>,
+    },
     #[error("Failed to convert tag")]
     TagConvert,
+    #[error("Discriminant does not fit in its discriminant type")]
+    DiscriminantOverflow,
+    #[error("Buffer is too small")]
+    BufferFull,
+    #[error("Frame of {size} bytes exceeds the maximum of {max} bytes")]
+    FrameTooLarge { size: usize, max: usize },
+    #[error("Invalid bool: {0:#04x}")]
+    InvalidBool(u8),
+    #[error("Expected {expected} elements, found {actual}")]
+    CountMismatch { expected: usize, actual: usize },
+    #[error("Expected at most {max} elements, found {actual}")]
+    TooManyElements { max: usize, actual: usize },
+    #[error("Checksum mismatch")]
+    ChecksumMismatch,
+    #[error("Variant '{variant}' is not supported in protocol version {version}")]
+    VersionMismatch { variant: &'static str, version: u32 },
+    #[error("Field '{field}' does not allow this special floating-point value")]
+    NonFiniteFloat { field: &'static str },
+    #[error("Failed after reading {fields_read} fields: {source}")]
+    PartialRead {
+        fields_read: usize,
+        source: Box<Error>,
+    },
+    #[error("Field '{field}' does not match whether its present_if expression holds")]
+    PresenceMismatch { field: &'static str },
+    #[error("Tag of field '{field}' does not match its length of {len}")]
+    TagMismatch { field: &'static str, len: usize },
+    #[error("Expected {expected} bits to be written, wrote {actual}")]
+    TotalBitsMismatch { expected: u64, actual: u64 },
+    #[error("Map entry {index} repeats an earlier key")]
+    DuplicateMapKey { index: usize },
+    #[error("Range starts after its end")]
+    InvertedRange,
+    #[error("Values nest more than {max} deep")]
+    MaxDepthExceeded { max: usize },
     #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
 
+/// Lets `?` apply to conversions that cannot fail, such as `try_into` between
+/// integers that are the same size on some targets.
+impl From<std::convert::Infallible> for Error {
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,4 +95,17 @@ mod tests {
     trait IsSized: Sized {}
 
     impl IsSized for Error {}
+
+    #[test]
+    fn unknown_discriminant_message() {
+        let error = Error::UnknownEnumDiscriminant {
+            enum_name: "BoatKind",
+            discriminant: "7".into(),
+            expected: vec!["1".into(), "2".into(), "3".into()],
+        };
+        assert_eq!(
+            error.to_string(),
+            "Unknown discriminant 7 for enum BoatKind (expected 1, 2, 3)"
+        );
+    }
 }
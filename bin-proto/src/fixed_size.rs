@@ -0,0 +1,92 @@
+use std::{
+    marker::{PhantomData, PhantomPinned},
+    mem,
+};
+
+/// A trait for types whose encoding always occupies the same number of bits.
+///
+/// Derived alongside `ProtocolWrite` for `struct`s whose fields are all fixed
+/// size, and for `enum`s whose variants all share the same field types. The
+/// size can be checked at compile time:
+///
+/// ```
+/// # use bin_proto::{FixedSize, ProtocolWrite};
+/// #[derive(ProtocolWrite)]
+/// struct Header {
+///     id: u16,
+///     flags: [u8; 2],
+/// }
+///
+/// const _: () = assert!(Header::SIZE_BITS == 32);
+/// ```
+pub trait FixedSize {
+    const SIZE_BITS: usize;
+}
+
+macro_rules! impl_fixed_size_for_primitive {
+    ($($ty:ty)*) => {
+        $(
+            impl FixedSize for $ty {
+                const SIZE_BITS: usize = mem::size_of::<$ty>() * 8;
+            }
+        )*
+    };
+}
+
+impl_fixed_size_for_primitive!(u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 usize isize f32 f64);
+
+impl FixedSize for bool {
+    const SIZE_BITS: usize = 8;
+}
+
+impl<T> FixedSize for PhantomData<T> {
+    const SIZE_BITS: usize = 0;
+}
+
+impl FixedSize for PhantomPinned {
+    const SIZE_BITS: usize = 0;
+}
+
+impl<T: FixedSize, const N: usize> FixedSize for [T; N] {
+    const SIZE_BITS: usize = T::SIZE_BITS * N;
+}
+
+macro_rules! impl_fixed_size_for_tuple {
+    ($($t:ident),*) => {
+        impl<$($t: FixedSize,)*> FixedSize for ($($t,)*) {
+            const SIZE_BITS: usize = 0 $(+ $t::SIZE_BITS)*;
+        }
+    };
+}
+
+impl_fixed_size_for_tuple!(A, B, C, D, E, F, G, H);
+impl_fixed_size_for_tuple!(A, B, C, D, E, F, G);
+impl_fixed_size_for_tuple!(A, B, C, D, E, F);
+impl_fixed_size_for_tuple!(A, B, C, D, E);
+impl_fixed_size_for_tuple!(A, B, C, D);
+impl_fixed_size_for_tuple!(A, B, C);
+impl_fixed_size_for_tuple!(A, B);
+impl_fixed_size_for_tuple!(A);
+impl_fixed_size_for_tuple!();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitives() {
+        assert_eq!(u8::SIZE_BITS, 8);
+        assert_eq!(bool::SIZE_BITS, 8);
+        assert_eq!(i128::SIZE_BITS, 128);
+        assert_eq!(f64::SIZE_BITS, 64);
+    }
+
+    #[test]
+    fn compound() {
+        assert_eq!(<[u16; 3]>::SIZE_BITS, 48);
+        assert_eq!(<[[u8; 4]; 4]>::SIZE_BITS, 128);
+        assert_eq!(<(u8, [u32; 2], bool)>::SIZE_BITS, 80);
+        assert_eq!(<()>::SIZE_BITS, 0);
+        assert_eq!(<(u8, PhantomData<String>, PhantomPinned)>::SIZE_BITS, 8);
+    }
+}
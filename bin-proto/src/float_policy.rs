@@ -0,0 +1,75 @@
+//! Rejecting special floating-point values
+
+use crate::{Error, Result};
+
+/// Which special floating-point values a field rejects, set with
+/// `#[protocol(float_policy = "...")]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FloatPolicy {
+    /// Reject NaN, of any bit pattern.
+    RejectNan,
+    /// Reject NaN and positive or negative infinity.
+    RejectNonFinite,
+}
+
+/// A floating-point type whose values can be checked against a
+/// [`FloatPolicy`].
+pub trait CheckFloat {
+    /// Fails with [`Error::NonFiniteFloat`] if `policy` rejects this value.
+    fn check_float(&self, policy: FloatPolicy, field: &'static str) -> Result<()>;
+}
+
+macro_rules! impl_check_float {
+    ($($ty:ty),*) => {
+        $(
+            impl CheckFloat for $ty {
+                fn check_float(&self, policy: FloatPolicy, field: &'static str) -> Result<()> {
+                    let rejected = match policy {
+                        FloatPolicy::RejectNan => self.is_nan(),
+                        FloatPolicy::RejectNonFinite => !self.is_finite(),
+                    };
+                    if rejected {
+                        Err(Error::NonFiniteFloat { field })
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_check_float!(f32, f64);
+
+#[cfg(feature = "half")]
+impl_check_float!(half::f16);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reject_nan() {
+        assert!(1.5f32.check_float(FloatPolicy::RejectNan, "x").is_ok());
+        assert!(f64::INFINITY
+            .check_float(FloatPolicy::RejectNan, "x")
+            .is_ok());
+        assert!(matches!(
+            f64::NAN.check_float(FloatPolicy::RejectNan, "x"),
+            Err(Error::NonFiniteFloat { field: "x" })
+        ));
+    }
+
+    #[test]
+    fn reject_non_finite() {
+        assert!((-0.0f64)
+            .check_float(FloatPolicy::RejectNonFinite, "x")
+            .is_ok());
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert!(matches!(
+                value.check_float(FloatPolicy::RejectNonFinite, "x"),
+                Err(Error::NonFiniteFloat { field: "x" })
+            ));
+        }
+    }
+}
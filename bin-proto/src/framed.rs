@@ -0,0 +1,232 @@
+//! Length-prefixed framing of whole messages on byte streams
+//!
+//! ```
+//! # use bin_proto::{framed::{self, FrameConfig, PrefixWidth}, ByteOrder};
+//! let config = FrameConfig::new(PrefixWidth::U16);
+//! let mut stream = Vec::new();
+//! framed::write_frame(&mut stream, &0x0102_0304u32, ByteOrder::BigEndian, &config).unwrap();
+//! assert_eq!(stream, [0x00, 0x04, 0x01, 0x02, 0x03, 0x04]);
+//!
+//! let value: u32 = framed::read_frame(&mut stream.as_slice(), ByteOrder::BigEndian, &config).unwrap();
+//! assert_eq!(value, 0x0102_0304);
+//! ```
+
+use crate::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite, Result, VarInt};
+use std::io::{self, Read as _};
+
+/// The length of the longest LEB128 encoding of a 64-bit integer.
+const MAX_VARINT_LEN: usize = 10;
+
+/// The largest message accepted by default, see [`FrameConfig::max_frame_size`].
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// The encoding of the length prefix of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrefixWidth {
+    U8,
+    U16,
+    U32,
+    /// An LEB128 variable-length integer, see [`VarInt`].
+    VarInt,
+}
+
+/// The format of the length prefix of a frame, and the largest accepted
+/// frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameConfig {
+    width: PrefixWidth,
+    byte_order: ByteOrder,
+    includes_prefix: bool,
+    max_frame_size: usize,
+}
+
+impl FrameConfig {
+    /// A big-endian length prefix counting only the message, accepting
+    /// messages of up to [`DEFAULT_MAX_FRAME_SIZE`] bytes.
+    #[must_use]
+    pub fn new(width: PrefixWidth) -> Self {
+        Self {
+            width,
+            byte_order: ByteOrder::BigEndian,
+            includes_prefix: false,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+
+    /// Sets the byte order of the length prefix. The message itself uses the
+    /// byte order passed when reading or writing the frame.
+    #[must_use]
+    pub fn byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /// Makes the length prefix count its own bytes as well as the message.
+    #[must_use]
+    pub fn includes_prefix(mut self) -> Self {
+        self.includes_prefix = true;
+        self
+    }
+
+    /// Sets the largest accepted message, in bytes, excluding the prefix.
+    /// Larger frames fail with [`Error::FrameTooLarge`] before they are
+    /// read.
+    #[must_use]
+    pub fn max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    fn encode_prefix(&self, len: usize) -> Result<Vec<u8>> {
+        match self.width {
+            PrefixWidth::U8 => u8::try_from(len)?.bytes(self.byte_order),
+            PrefixWidth::U16 => u16::try_from(len)?.bytes(self.byte_order),
+            PrefixWidth::U32 => u32::try_from(len)?.bytes(self.byte_order),
+            PrefixWidth::VarInt => VarInt(len).bytes(self.byte_order),
+        }
+    }
+
+    fn read_prefix(&self, reader: &mut impl io::Read) -> Result<(usize, usize)> {
+        let mut prefix = Vec::new();
+        let len = match self.width {
+            PrefixWidth::U8 => read_exact(reader, &mut prefix, 1)?[0].into(),
+            PrefixWidth::U16 => usize::from(u16::from_bytes(
+                read_exact(reader, &mut prefix, 2)?,
+                self.byte_order,
+            )?),
+            PrefixWidth::U32 => usize::try_from(u32::from_bytes(
+                read_exact(reader, &mut prefix, 4)?,
+                self.byte_order,
+            )?)?,
+            PrefixWidth::VarInt => {
+                loop {
+                    let byte = read_exact(reader, &mut prefix, 1)?[0];
+                    if byte & 0x80 == 0 || prefix.len() == MAX_VARINT_LEN {
+                        break;
+                    }
+                }
+                VarInt::<usize>::from_bytes(&prefix, self.byte_order)?.0
+            }
+        };
+        Ok((len, prefix.len()))
+    }
+}
+
+impl Default for FrameConfig {
+    fn default() -> Self {
+        Self::new(PrefixWidth::U32)
+    }
+}
+
+/// Appends exactly `len` bytes from `reader` to `buf`, returning them.
+fn read_exact<'a>(
+    reader: &mut impl io::Read,
+    buf: &'a mut Vec<u8>,
+    len: usize,
+) -> io::Result<&'a [u8]> {
+    let start = buf.len();
+    buf.resize(start + len, 0);
+    reader.read_exact(&mut buf[start..])?;
+    Ok(&buf[start..])
+}
+
+/// Writes `value` to `writer`, preceded by its length in bytes.
+pub fn write_frame_ctx<T, Ctx>(
+    writer: &mut impl io::Write,
+    value: &T,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+    config: &FrameConfig,
+) -> Result<()>
+where
+    T: ProtocolWrite<Ctx> + ?Sized,
+{
+    let message = value.bytes_ctx(byte_order, ctx)?;
+    if message.len() > config.max_frame_size {
+        return Err(Error::FrameTooLarge {
+            size: message.len(),
+            max: config.max_frame_size,
+        });
+    }
+
+    let mut len = message.len();
+    if config.includes_prefix {
+        // A longer length may need a longer variable-length prefix.
+        let mut prefix_len = 0;
+        loop {
+            let next = config.encode_prefix(message.len() + prefix_len)?.len();
+            if next == prefix_len {
+                break;
+            }
+            prefix_len = next;
+        }
+        len += prefix_len;
+    }
+    let prefix = config.encode_prefix(len)?;
+    writer.write_all(&prefix)?;
+    writer.write_all(&message)?;
+    Ok(())
+}
+
+/// Reads a value written by [`write_frame_ctx`] from `reader`, consuming
+/// exactly one frame.
+pub fn read_frame_ctx<T, Ctx>(
+    reader: &mut impl io::Read,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+    config: &FrameConfig,
+) -> Result<T>
+where
+    T: ProtocolRead<Ctx>,
+{
+    let (mut len, prefix_len) = config.read_prefix(reader)?;
+    if config.includes_prefix {
+        len = len.checked_sub(prefix_len).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "frame length is shorter than its prefix",
+            )
+        })?;
+    }
+    if len > config.max_frame_size {
+        return Err(Error::FrameTooLarge {
+            size: len,
+            max: config.max_frame_size,
+        });
+    }
+
+    // The buffer grows as bytes arrive, so that a prefix claiming more bytes
+    // than the stream holds cannot allocate them up front.
+    let mut message = Vec::new();
+    reader.take(len as u64).read_to_end(&mut message)?;
+    if message.len() < len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    T::from_bytes_ctx(&message, byte_order, ctx)
+}
+
+/// Writes `value` to `writer`, preceded by its length in bytes.
+pub fn write_frame<T>(
+    writer: &mut impl io::Write,
+    value: &T,
+    byte_order: ByteOrder,
+    config: &FrameConfig,
+) -> Result<()>
+where
+    T: ProtocolWrite + ?Sized,
+{
+    write_frame_ctx(writer, value, byte_order, &mut (), config)
+}
+
+/// Reads a value written by [`write_frame`] from `reader`, consuming exactly
+/// one frame.
+pub fn read_frame<T>(
+    reader: &mut impl io::Read,
+    byte_order: ByteOrder,
+    config: &FrameConfig,
+) -> Result<T>
+where
+    T: ProtocolRead,
+{
+    read_frame_ctx(reader, byte_order, &mut (), config)
+}
@@ -61,15 +61,44 @@
     clippy::implicit_hasher
 )]
 pub use self::bit_field::{BitFieldRead, BitFieldWrite};
-pub use self::bit_read::BitRead;
-pub use self::bit_write::BitWrite;
+pub use self::bit_read::{from_read, from_read_buffered, BitRead};
+pub use self::bit_write::{to_write, BitWrite};
 pub use self::byte_order::ByteOrder;
+pub use self::checksum::{Checksum, Crc16, Crc32};
+#[doc(hidden)]
+pub use self::counting_write::CountingWrite;
+pub use self::crc_context::{CrcContext, MessageChecksum};
+#[doc(hidden)]
+pub use self::depth::read_recursive;
+pub use self::depth::{DepthCtx, MaxDepth};
 pub use self::discriminable::Discriminable;
-pub use self::error::{Error, Result};
+pub use self::error::{Error, Result, MAX_EXPECTED_DISCRIMINANTS};
+pub use self::fixed_size::FixedSize;
 pub use self::flexible_array_member::FlexibleArrayMemberRead;
+pub use self::float_policy::{CheckFloat, FloatPolicy};
+pub use self::limited_read::LimitedRead;
+#[doc(hidden)]
+pub use self::lsb0::{Lsb0Reader, Lsb0Writer};
+pub use self::message_stream::MessageStream;
+pub use self::offsets::{AnnotatedBytes, FieldOffsets};
 pub use self::protocol::ProtocolNoCtx;
 pub use self::protocol::{ProtocolRead, ProtocolWrite};
+#[doc(hidden)]
+pub use self::remaining::buffer_remaining;
+pub use self::remaining::RemainingRead;
+#[doc(hidden)]
+pub use self::tagged::{adjust_tag, unadjust_tag, validate_tag, NotTagAsLen, TagAsLen, TagLen};
 pub use self::tagged::{TaggedRead, UntaggedWrite};
+#[doc(hidden)]
+pub use self::types::read_if_remaining;
+#[cfg(feature = "uuid")]
+pub use self::types::Guid;
+pub use self::types::{
+    Bits, ByteCounted, FixedList, FixedPoint, KeepFirstMap, LengthDelimited, LenientBool,
+    LossyString, Micros, Millis, NanBoxed, Nanos, OrderedRange, StrictBool, StrictMap, VarInt,
+    ZigZag, Q16_16, Q8_8,
+};
+pub use self::version::ProtocolVersion;
 
 /// Derive the `ProtocolRead` and `ProtocolWrite` traits.
 ///
@@ -80,16 +109,30 @@ pub use self::tagged::{TaggedRead, UntaggedWrite};
 /// - `<type>`: an arbitrary type that implements `ProtocolRead` or `ProtocolWrite`
 ///
 /// Specify if enum variant should be determined by a string or interger
-/// representation of its discriminant.
+/// representation of its discriminant. Deriving `ProtocolRead` also generates
+/// an inherent `read_discriminant` function, which reads only the
+/// discriminant.
+///
+/// A `String` discriminant has no encoding of its own, so such an enum only
+/// implements `TaggedRead` and `UntaggedWrite`, and is read with a tag that
+/// converts into a `String`, such as a `&str`. Variants without a
+/// `discriminant` default to their name.
+///
+/// An enum whose variants have no fields is read and written as just its
+/// discriminant. An enum without variants can be derived as well: reading it always fails
+/// with `Error::UnknownEnumDiscriminant` after reading the discriminant.
 ///
 /// ```
-/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// # use bin_proto::{ByteOrder, ProtocolRead, ProtocolWrite};
 /// #[derive(ProtocolRead, ProtocolWrite)]
 /// #[protocol(discriminant_type = "u8")]
 /// enum Example {
 ///     Variant1 = 1,
 ///     Variant5 = 5,
 /// }
+///
+/// let mut reader = bitstream_io::BitReader::endian([5u8].as_slice(), bitstream_io::BigEndian);
+/// assert_eq!(Example::read_discriminant(&mut reader, ByteOrder::BigEndian, &mut ()).unwrap(), 5);
 /// ```
 ///
 /// ## `#[protocol(discriminant = "<value>")]`
@@ -109,10 +152,115 @@ pub use self::tagged::{TaggedRead, UntaggedWrite};
 ///
 /// Specify the discriminant for a variant.
 ///
+/// The value can be any constant expression, such as a `const` item shared
+/// with other code. Duplicate integer literals are rejected at compile time.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// mod message_type {
+///     pub const ACK: u8 = 0x40 | 3;
+/// }
+///
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// #[protocol(discriminant_type = "u8")]
+/// enum Message {
+///     #[protocol(discriminant = "message_type::ACK")]
+///     Ack,
+///     #[protocol(discriminant = "message_type::ACK + 1")]
+///     Nack,
+/// }
+///
+/// assert_eq!(Message::from_bytes(&[0x44], ByteOrder::BigEndian).unwrap(), Message::Nack);
+/// ```
+///
+/// ## `#[protocol(discriminant_base = <value>)]`
+/// - Applies to: `enum`
+/// - `<value>`: an integer literal
+///
+/// Number variants without a discriminant from `<value>`, counting up by one
+/// from the previous variant, as Rust does from zero. A variant with an
+/// integer literal discriminant restarts the count after it.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// #[protocol(discriminant_type = "u8", discriminant_base = 0x10)]
+/// enum Opcode {
+///     Read,
+///     Write,
+///     Erase = 0x20,
+///     Lock,
+/// }
+///
+/// assert_eq!(Opcode::Write.bytes(ByteOrder::BigEndian).unwrap(), [0x11]);
+/// assert_eq!(Opcode::from_bytes(&[0x21], ByteOrder::BigEndian).unwrap(), Opcode::Lock);
+/// ```
+///
+/// ## `#[protocol(added_in = <version>)]`, `#[protocol(removed_in = <version>)]`
+/// - Applies to: `enum` variant, in an `enum` whose ctx implements
+///   `ProtocolVersion`
+///
+/// Restrict a variant to the protocol versions `added_in..removed_in`. Reading
+/// or writing the variant with any other version returns
+/// `Error::VersionMismatch`.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, Error, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(discriminant_type = "u8", ctx = "u32")]
+/// enum Message {
+///     #[protocol(discriminant = "1")]
+///     Ping,
+///     #[protocol(discriminant = "2", added_in = 2)]
+///     Hello,
+/// }
+///
+/// assert!(matches!(
+///     Message::Hello.bytes_ctx(ByteOrder::BigEndian, &mut 1),
+///     Err(Error::VersionMismatch { .. })
+/// ));
+/// ```
+///
+/// ## `#[protocol(discriminant_mask = "<mask>")]`
+/// - Applies to: `enum`
+/// - `<mask>`: value of the discriminant's type
+///
+/// Select variants using only the bits of the tag within `<mask>`.
+///
+/// ## `#[protocol(inner_tag)]`
+/// - Applies to: `enum` variant with a single field that implements
+///   `TaggedRead` and `UntaggedWrite`, in an `enum` with a `discriminant_mask`
+///
+/// Pass the bits of the tag outside the `discriminant_mask` to the field as its
+/// tag, so that nested enums share a single tag. On write, the tag combines the
+/// discriminants of both enums.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(discriminant_type = "u8")]
+/// enum Video {
+///     H264 = 0x1,
+///     Vp9 = 0x2,
+/// }
+///
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(discriminant_type = "u8", discriminant_mask = "0xf0")]
+/// enum Media {
+///     #[protocol(discriminant = "0x10", inner_tag)]
+///     Video(Video),
+///     #[protocol(discriminant = "0x20")]
+///     Ping,
+/// }
+///
+/// assert_eq!(Media::Video(Video::Vp9).bytes(ByteOrder::BigEndian).unwrap(), [0x12]);
+/// ```
+///
 /// ## `#[protocol(bits = <width>)]`
 /// - Applies to: `impl BitFieldRead`, `impl BitFieldWrite`, `enum` with discriminant that `impl BitField`
 ///
-/// Determine width of field in bits.
+/// Determine width of field in bits. On an `Option`, this is the width of a
+/// presence flag read directly before the value.
 ///
 /// **WARNING**: Bitfields disregard `ByteOrder` and instead have the same
 /// endianness as the underlying `BitRead` / `BitWrite` instance. If you're
@@ -124,25 +272,344 @@ pub use self::tagged::{TaggedRead, UntaggedWrite};
 /// struct Nibble(#[protocol(bits = 4)] u8);
 /// ```
 ///
+/// ## `#[protocol(pad_bits = <width>)]`
+/// - Applies to: fields
+///
+/// Insert `<width>` zero bits before the field, which are discarded on read.
+///
+/// ```
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// struct Padded {
+///     #[protocol(bits = 3)]
+///     low: u8,
+///     #[protocol(bits = 3, pad_bits = 2)]
+///     high: u8,
+/// }
+/// ```
+///
+/// ## `#[protocol(align_before)]`
+/// - Applies to: fields
+///
+/// Skip to the next byte boundary before reading the field, and write zero
+/// bits up to it before writing the field. Does nothing if the stream is
+/// already byte-aligned.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+/// struct Header {
+///     #[protocol(bits = 3)]
+///     flags: u8,
+///     #[protocol(align_before)]
+///     length: u8,
+/// }
+///
+/// let header = Header { flags: 0b101, length: 7 };
+/// assert_eq!(header.bytes(ByteOrder::BigEndian).unwrap(), [0b1010_0000, 7]);
+/// ```
+///
+/// ## `#[protocol(read_skip = <bytes>)]`
+/// - Applies to: fields
+///
+/// Skip `<bytes>` bytes before reading the field, and write as many zero
+/// bytes before writing it. This keeps the place of a field that was removed
+/// from the protocol, so that its bytes are still skipped when parsing data
+/// in the old format.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+/// struct Record {
+///     id: u8,
+///     // A `u16` checksum used to be here.
+///     #[protocol(read_skip = 2)]
+///     value: u8,
+/// }
+///
+/// let record = Record::from_bytes(&[1, 0xab, 0xcd, 7], ByteOrder::BigEndian).unwrap();
+/// assert_eq!(record, Record { id: 1, value: 7 });
+/// assert_eq!(record.bytes(ByteOrder::BigEndian).unwrap(), [1, 0, 0, 7]);
+/// ```
+///
+/// ## `#[protocol(bitfield_packing = "<packing>")]`
+/// - Applies to: `struct`
+/// - `<packing>`: `"msb0"` (default) or `"lsb0"`
+///
+/// With `"lsb0"`, consecutive bitfields are packed starting from the least
+/// significant bit of each byte regardless of `ByteOrder`, as C compilers do
+/// on little-endian targets. A bitfield that would straddle a storage unit the
+/// size of its type starts at the next storage unit, and a run of bitfields
+/// is padded to a whole byte before the next non-bitfield field.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(bitfield_packing = "lsb0")]
+/// struct Flags {
+///     #[protocol(bits = 3)]
+///     low: u8,
+///     #[protocol(bits = 6)]
+///     high: u8,
+/// }
+///
+/// let flags = Flags { low: 1, high: 1 };
+/// assert_eq!(flags.bytes(ByteOrder::LittleEndian).unwrap(), [0x01, 0x01]);
+/// ```
+///
+/// ## `#[protocol(total_bits = <n>)]`
+/// - Applies to: `struct`
+/// - `<n>`: number of bits
+///
+/// Check that writing the struct writes exactly `<n>` bits, failing with
+/// `Error::TotalBitsMismatch` otherwise. This catches mistakes in the bit
+/// widths of complex headers.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(total_bits = 16)]
+/// struct Header {
+///     #[protocol(bits = 4)]
+///     version: u8,
+///     #[protocol(bits = 12)]
+///     length: u16,
+/// }
+///
+/// let header = Header { version: 1, length: 2 };
+/// assert_eq!(header.bytes(ByteOrder::BigEndian).unwrap(), [0x10, 0x02]);
+/// ```
+///
+/// ## `#[protocol(byte_order = "<order>")]`
+/// - Applies to: `struct`, `enum`, fields
+/// - `<order>`: `"le"`, `"be"` or `"native"`
+///
+/// Read and write the type and all of its fields in `<order>`, ignoring the
+/// byte order passed in, so that the type has a fixed wire format. On a
+/// field, only that field, including its tag and any fields nested in it, is
+/// read and written in `<order>`.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(byte_order = "le")]
+/// struct Header {
+///     length: u16,
+///     #[protocol(byte_order = "be")]
+///     port: u16,
+/// }
+///
+/// let header = Header { length: 1, port: 2 };
+/// assert_eq!(header.bytes(ByteOrder::BigEndian).unwrap(), [0x01, 0x00, 0x00, 0x02]);
+/// ```
+///
+/// ## `#[protocol(record_offsets)]`
+/// - Applies to: `struct`
+///
+/// Record the bit range of each field when written with
+/// `ProtocolWrite::bytes_with_offsets_ctx`. Fields of nested structs with this
+/// attribute are recorded under a dotted path. `FieldOffsets::annotate` shows
+/// which bytes belong to which field.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(record_offsets)]
+/// struct Message {
+///     id: u8,
+///     length: u16,
+/// }
+///
+/// let (_, offsets) = Message { id: 1, length: 2 }
+///     .bytes_with_offsets(ByteOrder::BigEndian)
+///     .unwrap();
+/// assert_eq!(offsets.get("length"), Some(8..24));
+/// ```
+///
+/// ## `#[protocol(partial_read)]`
+/// - Applies to: `struct`
+///
+/// Wrap errors when reading the struct in `Error::PartialRead`, which holds
+/// the number of fields read successfully before the error, to help recover
+/// from truncated messages.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, ProtocolRead, ProtocolWrite)]
+/// #[protocol(partial_read)]
+/// struct Message {
+///     id: u8,
+///     length: u16,
+///     checksum: u32,
+/// }
+///
+/// assert!(matches!(
+///     Message::from_bytes(&[0x01, 0x00, 0x02, 0xff], ByteOrder::BigEndian),
+///     Err(Error::PartialRead { fields_read: 2, .. })
+/// ));
+/// ```
+///
+/// ## `#[protocol(header(fields = "<fields>"))]`
+/// - Applies to: `struct` with named fields and no generics
+/// - `<fields>`: the names of the leading fields, in order
+///
+/// Deriving `ProtocolRead` also generates a `<Name>Header` struct with just
+/// those fields, and an inherent `read_header` function, which reads only them
+/// from the start of a message, for example to dispatch on a message type
+/// before parsing the body. The fields must be a prefix of the struct's
+/// fields, which is checked at compile time.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(header(fields = "version, msg_type"))]
+/// pub struct Packet {
+///     pub version: u8,
+///     pub msg_type: u8,
+///     pub length: u16,
+///     #[protocol(tag = "length as usize")]
+///     pub payload: Vec<u8>,
+/// }
+///
+/// let header = Packet::read_header(&[1, 7, 0xff, 0xff], ByteOrder::BigEndian, &mut ()).unwrap();
+/// assert_eq!((header.version, header.msg_type), (1, 7));
+/// ```
+///
+/// ## `#[protocol(builder = "<path>")]`
+/// - Applies to: `struct`
+/// - `<path>`: a function taking the fields in order, and returning `Self`
+///
+/// Construct the struct with `<path>` after reading its fields, instead of
+/// with a struct expression, so that a constructor can uphold the struct's
+/// invariants. Cannot be combined with `pod`, whose copied memory would
+/// bypass the constructor.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(builder = "Span::new")]
+/// pub struct Span {
+///     start: u8,
+///     end: u8,
+/// }
+///
+/// impl Span {
+///     pub fn new(start: u8, end: u8) -> Self {
+///         Self { start, end: end.max(start) }
+///     }
+/// }
+///
+/// let span = Span::from_bytes(&[5, 2], ByteOrder::BigEndian).unwrap();
+/// assert_eq!((span.start, span.end), (5, 5));
+/// ```
+///
+/// ## `#[protocol(pod)]`
+/// - Applies to: `struct` implementing `bytemuck::Pod`
+/// - Requires feature: `bytemuck`
+///
+/// When the byte order matches the target's native byte order, copy the
+/// struct's memory directly instead of encoding field by field. Other byte
+/// orders fall back to the field-by-field encoding. Fails to compile if the
+/// struct is not `Pod`, or if any of its fields has a `#[protocol(...)]`
+/// attribute, since the copied memory would ignore it.
+///
+/// ```ignore
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[repr(C)]
+/// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable, ProtocolRead, ProtocolWrite)]
+/// #[protocol(pod)]
+/// struct Sample {
+///     timestamp: u32,
+///     value: f32,
+/// }
+/// ```
+///
 /// ## `#[protocol(flexible_array_member)]`
 /// - Applies to: `impl FlexibleArrayMemberRead`
 ///
 /// Variable-length field is final field in container, hence lacks a length
 /// prefix and should be read until eof.
 ///
+/// `Box<T>` passes through to `T`, and `Box<[T]>` reads like `Vec<T>`.
+///
 /// ```
 /// # use bin_proto::{ProtocolRead, ProtocolWrite};
 /// #[derive(ProtocolRead, ProtocolWrite)]
 /// struct ReadToEnd(#[protocol(flexible_array_member)] Vec<u8>);
 /// ```
 ///
+/// ## `#[protocol(present_if = "<expr>")]`
+/// - Applies to: `Option` fields
+/// - `<expr>`: arbitrary `bool` expression. Fields in parent container can be
+///   used without prefixing them with `self`, and must precede the field.
+///
+/// Optional field whose presence is determined by an earlier field, such as a
+/// bit in a flags field, rather than by a flag of its own. The field is read
+/// if `<expr>` holds and is `None` otherwise. Writing fails with
+/// `Error::PresenceMismatch` unless the field is `Some` exactly when `<expr>`
+/// holds. As with `condition`, the fields in `<expr>` are the values written
+/// for them on write.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// struct Header {
+///     flags: u8,
+///     #[protocol(present_if = "flags & 0x01 != 0")]
+///     id: Option<u16>,
+/// }
+///
+/// let header = Header { flags: 0x01, id: Some(2) };
+/// assert_eq!(header.bytes(ByteOrder::BigEndian).unwrap(), [0x01, 0x00, 0x02]);
+/// ```
+///
+/// ## `#[protocol(present_if_remaining)]`
+/// - Applies to: `Option` fields
+///
+/// Optional trailing field, which is `Some` if the stream has bytes remaining
+/// and `None` at eof. Nothing is written for `None`.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// struct Trailer {
+///     id: u8,
+///     #[protocol(present_if_remaining)]
+///     extension: Option<u16>,
+/// }
+///
+/// assert_eq!(
+///     Trailer::from_bytes(&[0x01], ByteOrder::BigEndian).unwrap(),
+///     Trailer { id: 1, extension: None }
+/// );
+/// ```
+///
+/// ## `#[protocol(remaining)]`
+/// - Applies to: `impl RemainingRead`, i.e. `Vec<u8>`, `Box<[u8]>` or `Bytes`
+///
+/// Final field captures all remaining bytes verbatim. Within a
+/// `LengthDelimited`, only the bytes up to its length remain.
+///
+/// ```
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// struct Packet {
+///     kind: u8,
+///     #[protocol(remaining)]
+///     payload: Vec<u8>,
+/// }
+/// ```
+///
 /// ## `#[protocol(tag = "<expr>")]`
 /// - Applies to: `impl TaggedRead` or `impl UntaggedWrite`
 /// - `<expr>`: arbitrary expression. Fields in parent container can be used
-///   without prefixing them with `self`.
+///   without prefixing them with `self`, and must precede the tagged field.
+///   Unnamed fields are referred to as `field_<index>`.
 ///
 /// Specify tag of field. The tag represents a length prefix for variable-length
-/// fields, and a boolean for `Option`.
+/// fields, a boolean for `Option`, and a discriminant for `enum`s. It may be a
+/// field of any earlier field, such as `header.msg_type`, not just the field
+/// right before.
 ///
 /// ```
 /// # use bin_proto::{ProtocolRead, ProtocolWrite};
@@ -153,6 +620,12 @@ pub use self::tagged::{TaggedRead, UntaggedWrite};
 ///     #[protocol(tag = "count as usize")]
 ///     pub data: Vec<u32>,
 /// }
+///
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// pub struct Unnamed(
+///     #[protocol(write_value = "self.1.len() as u8")] u8,
+///     #[protocol(tag = "field_0")] Vec<u8>,
+/// );
 /// ```
 ///
 /// ## `#[protocol(tag(type = "<type>", write_value = "<expr>"))]`
@@ -174,6 +647,182 @@ pub use self::tagged::{TaggedRead, UntaggedWrite};
 /// }
 /// ```
 ///
+/// ## `#[protocol(tag_adjust = <n>)]`
+/// - Applies to: fields with `tag` or `tag(type = "<type>", ...)`
+/// - `<n>`: signed integer
+///
+/// Add `n` to the tag after it is read, for prefixes that are offset from the
+/// field's length, such as a length that includes the prefix itself. Reading
+/// fails with `Error::TagConvert` if the result is negative. A `tag(type =
+/// ...)` prefix has `n` subtracted from its `write_value` before it is written;
+/// the `write_value` of a field used as an external `tag` should account for
+/// `n` itself.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+/// pub struct Record {
+///     // The prefix counts its own byte.
+///     #[protocol(tag(type = "u8", write_value = "self.data.len() as u8"))]
+///     #[protocol(tag_adjust = -1)]
+///     pub data: Vec<u8>,
+/// }
+///
+/// let record = Record { data: vec![7, 8] };
+/// assert_eq!(record.bytes(ByteOrder::BigEndian).unwrap(), [3, 7, 8]);
+/// assert_eq!(Record::from_bytes(&[3, 7, 8], ByteOrder::BigEndian).unwrap(), record);
+/// ```
+///
+/// ## `#[protocol(tag_scale = <n>)]`
+/// - Applies to: fields with `tag` or `tag(type = "<type>", ...)`
+/// - `<n>`: positive integer
+///
+/// Multiply the tag by `n` after it is read, for prefixes counting a larger
+/// unit than the field's elements, such as 16-bit words of a byte field. With
+/// `tag_adjust`, the tag is scaled before it is adjusted. A `tag(type = ...)`
+/// prefix has its `write_value` divided by `n` before it is written, failing
+/// with `Error::TagConvert` if it is not a multiple of `n`.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+/// pub struct Words {
+///     // The prefix counts 16-bit words.
+///     #[protocol(tag(type = "u8", write_value = "self.data.len() as u8"))]
+///     #[protocol(tag_scale = 2)]
+///     pub data: Vec<u8>,
+/// }
+///
+/// let words = Words { data: vec![1, 2, 3, 4] };
+/// assert_eq!(words.bytes(ByteOrder::BigEndian).unwrap(), [2, 1, 2, 3, 4]);
+/// assert_eq!(Words::from_bytes(&[2, 1, 2, 3, 4], ByteOrder::BigEndian).unwrap(), words);
+/// ```
+///
+/// ## `#[protocol(count = <n>)]`
+/// - Applies to: `impl TaggedRead` and `impl UntaggedWrite` with a `len` method
+/// - `<n>`: number of elements
+///
+/// Read exactly `n` elements without a length prefix. Writing fails with
+/// `Error::CountMismatch` unless the field has exactly `n` elements.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// pub struct Palette {
+///     #[protocol(count = 2)]
+///     pub colors: Vec<u16>,
+/// }
+///
+/// let palette = Palette::from_bytes(&[0x00, 0x01, 0x00, 0x02, 0x00], ByteOrder::BigEndian).unwrap();
+/// assert_eq!(palette.colors, [1, 2]);
+/// ```
+///
+/// ## `#[protocol(length = "<expr>")]`
+/// - Applies to: `impl TaggedRead` or `impl UntaggedWrite`
+/// - `<expr>`: arbitrary expression, which can refer to `remaining`, the
+///   number of bytes left in the input, as a `usize`. Fields in parent
+///   container can be used without prefixing them with `self`, and must
+///   precede the field.
+///
+/// Specify the tag of a field in terms of the length of the rest of the input,
+/// e.g. for a payload followed by a fixed-size trailer. If `<expr>` refers to
+/// `remaining`, the rest of the input is read into memory before the field is
+/// read; otherwise only the field itself is read.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// pub struct Frame {
+///     #[protocol(length = "remaining.saturating_sub(2)")]
+///     pub payload: Vec<u8>,
+///     pub crc: u16,
+/// }
+///
+/// let frame = Frame::from_bytes(&[0x01, 0x02, 0x03, 0xab, 0xcd], ByteOrder::BigEndian).unwrap();
+/// assert_eq!(frame.payload, [0x01, 0x02, 0x03]);
+/// assert_eq!(frame.crc, 0xabcd);
+/// ```
+///
+/// ## `#[protocol(checksum = "<type>")]`
+/// - Applies to: fields
+/// - `<type>`: a type that implements `Checksum`
+///
+/// Place a checksum of the field's encoded bytes directly after it, and verify
+/// it on read. A prepended tag is not covered by the checksum.
+///
+/// ```
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// pub struct WithChecksum {
+///     #[protocol(
+///         tag(type = "u16", write_value = "self.data.len() as u16"),
+///         checksum = "bin_proto::Crc16"
+///     )]
+///     pub data: Vec<u8>,
+/// }
+/// ```
+///
+/// ## `#[protocol(float_policy = "<policy>")]`
+/// - Applies to: fields of a type that implements `CheckFloat`
+/// - `<policy>`: `"reject_nan"` or `"reject_nonfinite"`
+///
+/// Fail to read or write the field with `Error::NonFiniteFloat` if it is NaN,
+/// or with `"reject_nonfinite"`, also if it is infinite.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// pub struct Measurement {
+///     #[protocol(float_policy = "reject_nonfinite")]
+///     pub value: f32,
+/// }
+///
+/// assert!(matches!(
+///     Measurement { value: f32::INFINITY }.bytes(ByteOrder::BigEndian),
+///     Err(Error::NonFiniteFloat { field: "value" })
+/// ));
+/// ```
+///
+/// ## `#[protocol(condition = "<expr>")]`
+/// - Applies to: fields
+/// - `<expr>`: boolean expression. Fields in parent container can be used
+///   without prefixing them with `self`, and must precede the field.
+///
+/// Only read or write the field if the condition holds. Otherwise, nothing is
+/// consumed or emitted, and the field is read as its `default`, which is
+/// required unless the field is an `Option`, read as `None`. On write, the
+/// fields in the condition are the values written for them, such as their
+/// `write_value`.
+///
+/// ```
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// pub struct WithCondition {
+///     pub flags: u8,
+///     #[protocol(
+///         condition = "flags & 0x01 != 0",
+///         tag(type = "bool", write_value = "self.value.is_some()")
+///     )]
+///     pub value: Option<u32>,
+/// }
+/// ```
+///
+/// ## `#[protocol(default = "<expr>")]`
+/// - Applies to: fields with a `condition`
+/// - `<expr>`: An expression that can be coerced to the field type
+///
+/// Specify the value of a field whose condition does not hold.
+///
+/// ```
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// pub struct WithDefault {
+///     pub flags: u8,
+///     #[protocol(condition = "flags & 0x01 != 0", default = "0xff")]
+///     pub value: u32,
+/// }
+/// ```
+///
 /// ## `#[protocol(write_value = "<expr>")]`
 /// - Applies to: fields
 /// - `<expr>`: An expression that can be coerced to the field type, potentially
@@ -198,7 +847,9 @@ pub use self::tagged::{TaggedRead, UntaggedWrite};
 /// - `<type>`: The type of the context. Either a concrete type, or one of the
 ///   container's generics
 ///
-/// Specify the type of context that will be passed to codec functions
+/// Specify the type of context that will be passed to codec functions. The
+/// context of a container with fields that refer to the container itself, such
+/// as `Box<Self>`, must implement [`DepthCtx`].
 ///
 /// ```
 /// # use bin_proto::{ByteOrder, ProtocolRead, ProtocolWrite};
@@ -285,22 +936,65 @@ pub use self::tagged::{TaggedRead, UntaggedWrite};
 /// #[protocol(ctx_bounds = "CtxTrait")]
 /// pub struct WithCtx(NeedsCtx);
 /// ```
+///
+/// ## `#[protocol(crate = "<path>")]`
+/// - Applies to: containers
+/// - `<path>`: path to the `bin_proto` crate
+///
+/// Use `<path>` instead of `::bin_proto` in generated code. Expressions in other
+/// attributes are used as written. This is needed when `bin-proto` is renamed in
+/// `Cargo.toml`, or used through a re-export from another crate.
+///
+/// ```
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// mod facade {
+///     pub(crate) use ::bin_proto as proto;
+/// }
+///
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(crate = "facade::proto")]
+/// pub struct Reexported(u8);
+/// ```
 #[cfg(feature = "derive")]
 pub use bin_proto_derive::{ProtocolRead, ProtocolWrite};
 
+#[cfg(feature = "bytemuck")]
+#[doc(hidden)]
+pub use bytemuck;
+
 mod bit_field;
+#[macro_use]
 mod bit_read;
 mod bit_write;
 #[macro_use]
 mod tagged;
 mod byte_order;
+#[cfg(feature = "bytes")]
+mod bytes_reader;
+mod canonical;
+mod checksum;
+pub mod cobs;
+mod counting_write;
+mod crc_context;
+mod depth;
 mod error;
+mod fixed_size;
 mod flexible_array_member;
+mod float_policy;
+pub mod framed;
+mod limited_read;
+mod lsb0;
+mod message_stream;
+mod offsets;
+mod remaining;
+#[cfg(feature = "proptest")]
+pub mod testing;
 mod types;
 #[macro_use]
 mod protocol;
 mod discriminable;
 mod util;
+mod version;
 
 pub extern crate bitstream_io;
 
@@ -317,3 +1011,118 @@ pub extern crate bitstream_io;
 #[cfg(all(feature = "derive", doctest))]
 #[allow(unused)]
 fn compile_fail_if_multiple_exclusive_attrs() {}
+
+/// ```compile_fail
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// struct TagAfterTaggedField {
+///     #[protocol(tag = "length as usize")]
+///     pub data: Vec<u8>,
+///     pub length: u8,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_tag_follows_tagged_field() {}
+
+/// ```compile_fail
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// struct DefaultWithoutCondition {
+///     #[protocol(default = "1")]
+///     pub value: u8,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_default_without_condition() {}
+
+/// ```compile_fail
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// struct ConditionWithoutDefault {
+///     pub flags: u8,
+///     #[protocol(condition = "flags & 0x01 != 0")]
+///     pub value: u8,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_condition_without_default() {}
+
+/// ```compile_fail
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(discriminant_type = "u8")]
+/// enum DiscriminantOverflow {
+///     #[protocol(discriminant = "300")]
+///     Variant,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_discriminant_overflows() {}
+
+/// ```compile_fail
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(discriminant_type = "u8")]
+/// enum DuplicateDiscriminant {
+///     #[protocol(discriminant = "1")]
+///     First,
+///     #[protocol(discriminant = "0x01")]
+///     Second,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_discriminant_duplicated() {}
+
+/// ```compile_fail
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// struct FlexibleArrayMemberNotLast {
+///     #[protocol(flexible_array_member)]
+///     pub data: Vec<u8>,
+///     pub trailer: u8,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_flexible_array_member_not_last() {}
+
+/// ```compile_fail
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// struct FlexibleArrayMemberUnsupported {
+///     #[protocol(flexible_array_member)]
+///     pub data: u32,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_flexible_array_member_unsupported() {}
+
+/// ```compile_fail
+/// # use bin_proto::{ProtocolRead, ProtocolWrite};
+/// #[derive(ProtocolRead, ProtocolWrite)]
+/// #[protocol(length = "4")]
+/// struct FieldAttributeOnStruct;
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_field_attribute_on_struct() {}
+
+/// ```compile_fail
+/// # use bin_proto::ProtocolRead;
+/// #[derive(ProtocolRead)]
+/// #[protocol(header(fields = "version, length"))]
+/// struct HeaderNotPrefix {
+///     version: u8,
+///     msg_type: u8,
+///     length: u16,
+/// }
+/// ```
+#[cfg(all(feature = "derive", doctest))]
+#[allow(unused)]
+fn compile_fail_if_header_fields_not_prefix() {}
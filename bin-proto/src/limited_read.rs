@@ -0,0 +1,128 @@
+//! Capping the number of bytes read for a single value
+
+use crate::BitRead;
+use std::io;
+
+/// A `BitRead` that fails once more than a fixed number of bytes has been
+/// read through it, so that a value with huge lengths in its prefixes cannot
+/// make a reader consume or allocate unbounded amounts of memory.
+///
+/// The limit is checked before each read, so reads that would exceed it do
+/// not reach the underlying reader.
+///
+/// ```
+/// # use bin_proto::{BitRead, ByteOrder, LimitedRead, ProtocolRead};
+/// # use bitstream_io::{BigEndian, BitReader};
+/// let bytes = [0x00, 0x01, 0x02, 0x03];
+/// let mut reader = BitReader::endian(bytes.as_slice(), BigEndian);
+/// let mut limited = LimitedRead::new(&mut reader, 2);
+/// assert_eq!(u16::read(&mut limited, ByteOrder::BigEndian, &mut ()).unwrap(), 1);
+/// assert!(u16::read(&mut limited, ByteOrder::BigEndian, &mut ()).is_err());
+/// ```
+pub struct LimitedRead<'a> {
+    inner: &'a mut dyn BitRead,
+    remaining: u64,
+}
+
+impl<'a> LimitedRead<'a> {
+    /// Limits reads from `inner` to `limit` bytes.
+    pub fn new(inner: &'a mut dyn BitRead, limit: u64) -> Self {
+        Self {
+            inner,
+            remaining: limit.saturating_mul(8),
+        }
+    }
+
+    /// The number of bits that can still be read.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    fn take(&mut self, bits: u64) -> io::Result<()> {
+        match self.remaining.checked_sub(bits) {
+            Some(remaining) => {
+                self.remaining = remaining;
+                Ok(())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "read limit exceeded",
+            )),
+        }
+    }
+}
+
+macro_rules! limit {
+    ($(fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) -> $ret:ty = $bits:expr;)*) => {
+        $(
+            fn $name(&mut self $(, $arg: $ty)*) -> $ret {
+                self.take($bits)?;
+                self.inner.$name($($arg),*)
+            }
+        )*
+    };
+}
+
+impl BitRead for LimitedRead<'_> {
+    limit! {
+        fn read_bit(&mut self) -> io::Result<bool> = 1;
+        fn skip(&mut self, bits: u32) -> io::Result<()> = bits.into();
+        fn read_bytes(&mut self, buf: &mut [u8]) -> io::Result<()> = (buf.len() as u64).saturating_mul(8);
+        fn read_to_vec(&mut self, bytes: usize) -> io::Result<Vec<u8>> = (bytes as u64).saturating_mul(8);
+
+        fn read_u8(&mut self) -> io::Result<u8> = 8;
+        fn read_i8(&mut self) -> io::Result<i8> = 8;
+
+        fn read_u16_le(&mut self) -> io::Result<u16> = 16;
+        fn read_u16_be(&mut self) -> io::Result<u16> = 16;
+        fn read_i16_le(&mut self) -> io::Result<i16> = 16;
+        fn read_i16_be(&mut self) -> io::Result<i16> = 16;
+        fn read_u32_le(&mut self) -> io::Result<u32> = 32;
+        fn read_u32_be(&mut self) -> io::Result<u32> = 32;
+        fn read_i32_le(&mut self) -> io::Result<i32> = 32;
+        fn read_i32_be(&mut self) -> io::Result<i32> = 32;
+        fn read_u64_le(&mut self) -> io::Result<u64> = 64;
+        fn read_u64_be(&mut self) -> io::Result<u64> = 64;
+        fn read_i64_le(&mut self) -> io::Result<i64> = 64;
+        fn read_i64_be(&mut self) -> io::Result<i64> = 64;
+        fn read_u128_le(&mut self) -> io::Result<u128> = 128;
+        fn read_u128_be(&mut self) -> io::Result<u128> = 128;
+        fn read_i128_le(&mut self) -> io::Result<i128> = 128;
+        fn read_i128_be(&mut self) -> io::Result<i128> = 128;
+        fn read_f32_le(&mut self) -> io::Result<f32> = 32;
+        fn read_f32_be(&mut self) -> io::Result<f32> = 32;
+        fn read_f64_le(&mut self) -> io::Result<f64> = 64;
+        fn read_f64_be(&mut self) -> io::Result<f64> = 64;
+
+        fn read_u8_bf(&mut self, bits: u32) -> io::Result<u8> = bits.into();
+        fn read_i8_bf(&mut self, bits: u32) -> io::Result<i8> = bits.into();
+        fn read_u16_bf(&mut self, bits: u32) -> io::Result<u16> = bits.into();
+        fn read_i16_bf(&mut self, bits: u32) -> io::Result<i16> = bits.into();
+        fn read_u32_bf(&mut self, bits: u32) -> io::Result<u32> = bits.into();
+        fn read_i32_bf(&mut self, bits: u32) -> io::Result<i32> = bits.into();
+        fn read_u64_bf(&mut self, bits: u32) -> io::Result<u64> = bits.into();
+        fn read_i64_bf(&mut self, bits: u32) -> io::Result<i64> = bits.into();
+    }
+
+    // The length of a unary value is only known once it has been read.
+    fn read_unary0(&mut self) -> io::Result<u32> {
+        let value = self.inner.read_unary0()?;
+        self.take(u64::from(value) + 1)?;
+        Ok(value)
+    }
+
+    fn read_unary1(&mut self) -> io::Result<u32> {
+        let value = self.inner.read_unary1()?;
+        self.take(u64::from(value) + 1)?;
+        Ok(value)
+    }
+
+    fn byte_aligned(&self) -> bool {
+        self.inner.byte_aligned()
+    }
+
+    fn byte_align(&mut self) {
+        self.inner.byte_align();
+    }
+}
@@ -0,0 +1,146 @@
+//! Least-significant-bit-first bitfield packing, as laid out by C compilers on
+//! little-endian targets
+
+use bitstream_io::{BigEndian, BitReader, BitWriter};
+
+use crate::{BitFieldRead, BitFieldWrite, BitRead, BitWrite, ByteOrder, Result};
+use std::{io, mem};
+
+/// Reads a run of consecutive bitfields packed starting from the least
+/// significant bit of each byte.
+///
+/// A bitfield that would straddle a storage unit of its type's size starts at
+/// the next storage unit instead.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Lsb0Reader {
+    bytes: Vec<u8>,
+    offset: u32,
+}
+
+impl Lsb0Reader {
+    pub fn read<Ctx, T>(
+        &mut self,
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        bits: u32,
+    ) -> Result<T>
+    where
+        T: BitFieldRead<Ctx>,
+    {
+        let offset = place::<T>(self.offset, bits)?;
+        while self.bytes.len() * 8 < (offset + bits) as usize {
+            self.bytes.push(read.read_u8()?);
+        }
+        self.offset = offset + bits;
+
+        let mut data = Vec::new();
+        let mut writer = BitWriter::endian(&mut data, BigEndian);
+        for bit in (offset..offset + bits).rev() {
+            BitWrite::write_bit(&mut writer, get_bit(&self.bytes, bit))?;
+        }
+        BitWrite::byte_align(&mut writer)?;
+        T::read(
+            &mut BitReader::endian(data.as_slice(), BigEndian),
+            byte_order,
+            ctx,
+            bits,
+        )
+    }
+
+    /// Skips padding bits within the current run of bitfields.
+    pub fn pad(&mut self, bits: u32) {
+        self.offset += bits;
+    }
+
+    /// Ends the current run of bitfields, skipping any trailing padding. Bits
+    /// of the run that were not read are padding.
+    pub fn finish(&mut self, read: &mut dyn BitRead) -> Result<()> {
+        while self.bytes.len() * 8 < self.offset as usize {
+            read.read_u8()?;
+            self.bytes.push(0);
+        }
+        self.bytes.clear();
+        self.offset = 0;
+        Ok(())
+    }
+}
+
+/// Writes a run of consecutive bitfields packed starting from the least
+/// significant bit of each byte.
+///
+/// A bitfield that would straddle a storage unit of its type's size starts at
+/// the next storage unit instead.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Lsb0Writer {
+    bytes: Vec<u8>,
+    offset: u32,
+}
+
+impl Lsb0Writer {
+    pub fn write<Ctx, T>(
+        &mut self,
+        value: &T,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        bits: u32,
+    ) -> Result<()>
+    where
+        T: BitFieldWrite<Ctx>,
+    {
+        let offset = place::<T>(self.offset, bits)?;
+        self.bytes.resize(((offset + bits + 7) / 8) as usize, 0);
+        self.offset = offset + bits;
+
+        let mut data = Vec::new();
+        let mut writer = BitWriter::endian(&mut data, BigEndian);
+        value.write(&mut writer, byte_order, ctx, bits)?;
+        BitWrite::byte_align(&mut writer)?;
+        let mut reader = BitReader::endian(data.as_slice(), BigEndian);
+        for bit in (offset..offset + bits).rev() {
+            if BitRead::read_bit(&mut reader)? {
+                self.bytes[(bit / 8) as usize] |= 1 << (bit % 8);
+            }
+        }
+        Ok(())
+    }
+
+    /// Inserts zeroed padding bits within the current run of bitfields.
+    pub fn pad(&mut self, bits: u32) {
+        self.offset += bits;
+        self.bytes.resize(((self.offset + 7) / 8) as usize, 0);
+    }
+
+    /// Ends the current run of bitfields, writing it out with unused bits
+    /// zeroed.
+    pub fn finish(&mut self, write: &mut dyn BitWrite) -> Result<()> {
+        write.write_bytes(&self.bytes)?;
+        self.bytes.clear();
+        self.offset = 0;
+        Ok(())
+    }
+}
+
+/// Returns the offset at which a bitfield of type `T` starts, given the offset
+/// following the previous bitfield.
+fn place<T>(offset: u32, bits: u32) -> Result<u32> {
+    let unit = u32::try_from(mem::size_of::<T>() * 8)?;
+    if bits > unit {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "bitfield is wider than its storage unit",
+        )
+        .into());
+    }
+    if offset % unit + bits > unit {
+        Ok((offset / unit + 1) * unit)
+    } else {
+        Ok(offset)
+    }
+}
+
+fn get_bit(bytes: &[u8], bit: u32) -> bool {
+    bytes[(bit / 8) as usize] & (1 << (bit % 8)) != 0
+}
@@ -0,0 +1,143 @@
+//! Parsing consecutive messages out of a byte slice
+
+use bitstream_io::{BigEndian, Endianness, LittleEndian};
+
+use crate::{bit_read::ChunkedReader, ByteOrder, ProtocolRead, Result};
+use std::{io, marker::PhantomData};
+
+/// An iterator parsing consecutive values of `T` from a byte slice, e.g. a
+/// memory-mapped capture file, until it is exhausted.
+///
+/// When a value fails to parse, the error is yielded and parsing resumes at
+/// the next byte boundary, or at the next occurrence of the magic value if
+/// one was set with [`MessageStream::resync_on`]. A value that reads no bits
+/// yields an error and ends the stream, since reading it again would not
+/// advance.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, MessageStream};
+/// let mut stream = MessageStream::<u16>::new(&[0x00, 0x01, 0x00, 0x02], ByteOrder::BigEndian);
+/// assert_eq!(stream.next().unwrap().unwrap(), 1);
+/// assert_eq!(stream.position(), 16);
+/// assert_eq!(stream.next().unwrap().unwrap(), 2);
+/// assert!(stream.next().is_none());
+/// ```
+pub struct MessageStream<'a, T, Ctx = ()> {
+    bytes: &'a [u8],
+    position: u64,
+    byte_order: ByteOrder,
+    ctx: Ctx,
+    magic: Option<&'a [u8]>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> MessageStream<'a, T>
+where
+    T: ProtocolRead,
+{
+    #[must_use]
+    pub fn new(bytes: &'a [u8], byte_order: ByteOrder) -> Self {
+        Self::with_ctx(bytes, byte_order, ())
+    }
+}
+
+impl<'a, T, Ctx> MessageStream<'a, T, Ctx>
+where
+    T: ProtocolRead<Ctx>,
+{
+    pub fn with_ctx(bytes: &'a [u8], byte_order: ByteOrder, ctx: Ctx) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            byte_order,
+            ctx,
+            magic: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// After an error, resumes parsing at the next occurrence of `magic`
+    /// instead of the next byte boundary.
+    #[must_use]
+    pub fn resync_on(mut self, magic: &'a [u8]) -> Self {
+        self.magic = Some(magic);
+        self
+    }
+
+    /// The position of the next value, in bits.
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The number of bits remaining.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        (self.bytes.len() as u64 * 8).saturating_sub(self.position)
+    }
+
+    #[must_use]
+    pub fn ctx(&self) -> &Ctx {
+        &self.ctx
+    }
+
+    fn read(&mut self) -> Result<(T, u64)> {
+        match self.byte_order {
+            ByteOrder::LittleEndian => self.read_endian(LittleEndian),
+            ByteOrder::BigEndian => self.read_endian(BigEndian),
+        }
+    }
+
+    fn read_endian<E: Endianness>(&mut self, endianness: E) -> Result<(T, u64)> {
+        let mut reader = ChunkedReader::new(io::Cursor::new(self.bytes), endianness);
+        reader.inner.seek_bits(io::SeekFrom::Start(self.position))?;
+        let value = T::read(&mut reader, self.byte_order, &mut self.ctx)?;
+        Ok((value, reader.inner.position_in_bits()?))
+    }
+
+    /// Moves past the start of a value that failed to parse.
+    fn resync(&mut self, start: u64) {
+        let next = usize::try_from(start / 8 + 1).unwrap_or(usize::MAX);
+        let next = match self.magic {
+            Some(magic) if !magic.is_empty() => self
+                .bytes
+                .get(next..)
+                .and_then(|rest| rest.windows(magic.len()).position(|window| window == magic))
+                .map_or(self.bytes.len(), |offset| next + offset),
+            _ => next.min(self.bytes.len()),
+        };
+        self.position = next as u64 * 8;
+    }
+}
+
+impl<T, Ctx> Iterator for MessageStream<'_, T, Ctx>
+where
+    T: ProtocolRead<Ctx>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining() == 0 {
+            return None;
+        }
+        let start = self.position;
+        match self.read() {
+            Ok((_, position)) if position == start => {
+                self.position = self.bytes.len() as u64 * 8;
+                Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "value read no bits, so the stream would not advance",
+                )
+                .into()))
+            }
+            Ok((value, position)) => {
+                self.position = position;
+                Some(Ok(value))
+            }
+            Err(e) => {
+                self.resync(start);
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Recording where fields land in encoded output
+
+use crate::BitWrite;
+use std::{fmt, io, ops::Range};
+
+/// Bit ranges of fields within encoded output, keyed by their dotted path,
+/// e.g. `header.length`.
+///
+/// Only fields of types deriving `ProtocolWrite` with
+/// `#[protocol(record_offsets)]` are recorded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldOffsets(Vec<(String, Range<u64>)>);
+
+impl FieldOffsets {
+    /// Gets the bit range of the field at `path`.
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<Range<u64>> {
+        self.0
+            .iter()
+            .find(|(field, _)| field == path)
+            .map(|(_, range)| range.clone())
+    }
+
+    /// Iterates over recorded fields, in the order they were written.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Range<u64>)> {
+        self.0
+            .iter()
+            .map(|(field, range)| (field.as_str(), range.clone()))
+    }
+
+    /// Pairs recorded fields with the bytes they were written to in `bytes`,
+    /// for display. Each field is shown on its own line with its bit range and
+    /// the bytes spanning it, enclosing fields before the fields within them.
+    ///
+    /// ```
+    /// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+    /// #[derive(ProtocolRead, ProtocolWrite)]
+    /// #[protocol(record_offsets)]
+    /// struct Message {
+    ///     id: u8,
+    ///     length: u16,
+    /// }
+    ///
+    /// let (bytes, offsets) = Message { id: 1, length: 2 }
+    ///     .bytes_with_offsets(ByteOrder::BigEndian)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     offsets.annotate(&bytes).to_string(),
+    ///     "id [0..8]: 01\nlength [8..24]: 00 02\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn annotate<'a>(&'a self, bytes: &'a [u8]) -> AnnotatedBytes<'a> {
+        AnnotatedBytes {
+            offsets: self,
+            bytes,
+        }
+    }
+}
+
+/// Encoded output annotated with the fields it contains, created by
+/// [`FieldOffsets::annotate`].
+#[derive(Clone, Copy, Debug)]
+pub struct AnnotatedBytes<'a> {
+    offsets: &'a FieldOffsets,
+    bytes: &'a [u8],
+}
+
+impl fmt::Display for AnnotatedBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut fields = self.offsets.0.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|(_, range)| (range.start, std::cmp::Reverse(range.end)));
+        for (path, range) in fields {
+            write!(f, "{path} [{}..{}]:", range.start, range.end)?;
+            let start = usize::try_from(range.start / 8).unwrap_or(usize::MAX);
+            let end = usize::try_from((range.end + 7) / 8).unwrap_or(usize::MAX);
+            for byte in self.bytes.get(start..end).unwrap_or_default() {
+                write!(f, " {byte:02x}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Forwards writes to a `BitWrite`, tracking the bit position and the ranges
+/// of fields.
+pub(crate) struct OffsetRecorder<'a> {
+    write: &'a mut dyn BitWrite,
+    position: u64,
+    fields: Vec<(&'static str, u64)>,
+    offsets: FieldOffsets,
+}
+
+impl<'a> OffsetRecorder<'a> {
+    pub(crate) fn new(write: &'a mut dyn BitWrite) -> Self {
+        Self {
+            write,
+            position: 0,
+            fields: Vec::new(),
+            offsets: FieldOffsets::default(),
+        }
+    }
+
+    pub(crate) fn into_offsets(self) -> FieldOffsets {
+        self.offsets
+    }
+
+    fn advance<T>(&mut self, bits: u32, result: io::Result<T>) -> io::Result<T> {
+        if result.is_ok() {
+            self.position += u64::from(bits);
+        }
+        result
+    }
+}
+
+macro_rules! forward_write {
+    ( $( $name:ident ( $ty:ty ) => $bits:expr ),* $(,)? ) => {
+        $(
+            fn $name(&mut self, value: $ty) -> io::Result<()> {
+                let result = self.write.$name(value);
+                self.advance($bits, result)
+            }
+        )*
+    };
+}
+
+macro_rules! forward_write_bf {
+    ( $( $name:ident ( $ty:ty ) ),* $(,)? ) => {
+        $(
+            fn $name(&mut self, bits: u32, value: $ty) -> io::Result<()> {
+                let result = self.write.$name(bits, value);
+                self.advance(bits, result)
+            }
+        )*
+    };
+}
+
+impl BitWrite for OffsetRecorder<'_> {
+    fn write_bit(&mut self, bit: bool) -> io::Result<()> {
+        let result = self.write.write_bit(bit);
+        self.advance(1, result)
+    }
+
+    fn write_bytes(&mut self, buf: &[u8]) -> io::Result<()> {
+        let result = self.write.write_bytes(buf);
+        if result.is_ok() {
+            self.position += buf.len() as u64 * 8;
+        }
+        result
+    }
+
+    fn write_unary0(&mut self, value: u32) -> io::Result<()> {
+        let result = self.write.write_unary0(value);
+        self.advance(value + 1, result)
+    }
+
+    fn write_unary1(&mut self, value: u32) -> io::Result<()> {
+        let result = self.write.write_unary1(value);
+        self.advance(value + 1, result)
+    }
+
+    fn byte_aligned(&self) -> bool {
+        self.write.byte_aligned()
+    }
+
+    fn byte_align(&mut self) -> io::Result<()> {
+        self.write.byte_align()?;
+        self.position = (self.position + 7) / 8 * 8;
+        Ok(())
+    }
+
+    forward_write!(
+        write_u8(u8) => 8,
+        write_i8(i8) => 8,
+        write_u16_le(u16) => 16,
+        write_u16_be(u16) => 16,
+        write_i16_le(i16) => 16,
+        write_i16_be(i16) => 16,
+        write_u32_le(u32) => 32,
+        write_u32_be(u32) => 32,
+        write_i32_le(i32) => 32,
+        write_i32_be(i32) => 32,
+        write_u64_le(u64) => 64,
+        write_u64_be(u64) => 64,
+        write_i64_le(i64) => 64,
+        write_i64_be(i64) => 64,
+        write_u128_le(u128) => 128,
+        write_u128_be(u128) => 128,
+        write_i128_le(i128) => 128,
+        write_i128_be(i128) => 128,
+        write_f32_le(f32) => 32,
+        write_f32_be(f32) => 32,
+        write_f64_le(f64) => 64,
+        write_f64_be(f64) => 64,
+    );
+
+    forward_write_bf!(
+        write_u8_bf(u8),
+        write_i8_bf(i8),
+        write_u16_bf(u16),
+        write_i16_bf(i16),
+        write_u32_bf(u32),
+        write_i32_bf(i32),
+        write_u64_bf(u64),
+        write_i64_bf(i64),
+    );
+
+    fn canonical(&self) -> bool {
+        self.write.canonical()
+    }
+
+    fn begin_field(&mut self, name: &'static str) {
+        self.fields.push((name, self.position));
+    }
+
+    fn end_field(&mut self) {
+        let path = self
+            .fields
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(".");
+        if let Some((_, start)) = self.fields.pop() {
+            self.offsets.0.push((path, start..self.position));
+        }
+    }
+}
@@ -1,26 +1,71 @@
-use bitstream_io::{BigEndian, BitReader, BitWriter, LittleEndian};
+use bitstream_io::{BigEndian, BitCounter, BitWriter, LittleEndian};
 
-use crate::{BitRead, BitWrite, ByteOrder, Result};
-use std::io;
+use crate::{
+    bit_read::ChunkedReader, canonical::CanonicalWrite, offsets::OffsetRecorder, BitRead, BitWrite,
+    ByteOrder, Error, FieldOffsets, Result,
+};
+use std::{hash::Hasher, io};
+
+#[cfg(feature = "bytes")]
+use crate::bytes_reader::BytesReader;
 
 /// A trait for bit-level decoding.
 pub trait ProtocolRead<Ctx = ()>: Sized {
     /// Reads self from a stream.
     fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self>;
 
+    /// Reads a value into `self`, reusing its allocations where possible.
+    /// Derived structs read their fields in place, so that `Vec`, `String`
+    /// and map fields keep their capacity.
+    ///
+    /// No part of the previous value is kept. If reading fails, `self` is
+    /// left in a valid but unspecified state.
+    fn read_replace(
+        &mut self,
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+    ) -> Result<()> {
+        *self = Self::read(read, byte_order, ctx)?;
+        Ok(())
+    }
+
     /// Parses a new value from its raw byte representation with additional context.
+    ///
+    /// The implementations in this crate, and derived ones, fail with an
+    /// `Error` rather than panic on malformed input.
     fn from_bytes_ctx(bytes: &[u8], byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
         match byte_order {
             ByteOrder::LittleEndian => {
-                let mut buffer = BitReader::endian(io::Cursor::new(bytes), LittleEndian);
+                let mut buffer = ChunkedReader::new(bytes, LittleEndian);
                 Self::read(&mut buffer, byte_order, ctx)
             }
             ByteOrder::BigEndian => {
-                let mut buffer = BitReader::endian(io::Cursor::new(bytes), BigEndian);
+                let mut buffer = ChunkedReader::new(bytes, BigEndian);
                 Self::read(&mut buffer, byte_order, ctx)
             }
         }
     }
+
+    /// Parses a new value from a buffer with additional context. `Bytes`
+    /// fields share the buffer's storage instead of copying it when `buf` is
+    /// a `bytes::Bytes`.
+    #[cfg(feature = "bytes")]
+    fn from_buf_ctx(
+        mut buf: impl bytes::Buf,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+    ) -> Result<Self> {
+        let bytes = buf.copy_to_bytes(buf.remaining());
+        match byte_order {
+            ByteOrder::LittleEndian => {
+                Self::read(&mut BytesReader::new(bytes, LittleEndian), byte_order, ctx)
+            }
+            ByteOrder::BigEndian => {
+                Self::read(&mut BytesReader::new(bytes, BigEndian), byte_order, ctx)
+            }
+        }
+    }
 }
 
 /// A trait for bit-level encoding.
@@ -31,21 +76,162 @@ pub trait ProtocolWrite<Ctx = ()> {
     /// Gets the raw bytes of this type with provided context.
     fn bytes_ctx(&self, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Vec<u8>> {
         let mut data = Vec::new();
-        match byte_order {
+        write_endian(self, &mut data, byte_order, ctx)?;
+        Ok(data)
+    }
+
+    /// Gets the canonical bytes of this type with provided context, for
+    /// hashing and signing. The canonical encoding is big-endian, and writes
+    /// `HashMap` entries and `HashSet` elements sorted by the bytes of their
+    /// keys and elements, instead of in their iteration order. Everything
+    /// else is written as by `bytes_ctx`, and `bool` is always written as `0`
+    /// or `1`.
+    ///
+    /// The canonical encoding of a type does not change across minor
+    /// versions.
+    fn canonical_bytes_ctx(&self, ctx: &mut Ctx) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        let mut writer = BitWriter::endian(&mut data, BigEndian);
+        self.write(
+            &mut CanonicalWrite::new(&mut writer),
+            ByteOrder::BigEndian,
+            ctx,
+        )?;
+        writer.byte_align()?;
+        Ok(data)
+    }
+
+    /// Feeds the canonical bytes of this type with provided context, as by
+    /// [`Self::canonical_bytes_ctx`], to `state`.
+    fn canonical_hash_ctx(&self, state: &mut dyn Hasher, ctx: &mut Ctx) -> Result<()> {
+        state.write(&self.canonical_bytes_ctx(ctx)?);
+        Ok(())
+    }
+
+    /// Gets the raw bytes of this type with provided context, written into a
+    /// `bytes::BytesMut`.
+    #[cfg(feature = "bytes")]
+    fn to_bytes_ctx(&self, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<bytes::Bytes> {
+        use bytes::BufMut;
+
+        let data = bytes::BytesMut::new().writer();
+        let data = match byte_order {
             ByteOrder::LittleEndian => {
-                let mut writer = BitWriter::endian(&mut data, LittleEndian);
+                let mut writer = BitWriter::endian(data, LittleEndian);
                 self.write(&mut writer, byte_order, ctx)?;
                 writer.byte_align()?;
+                writer.into_writer()
             }
             ByteOrder::BigEndian => {
-                let mut writer = BitWriter::endian(&mut data, BigEndian);
+                let mut writer = BitWriter::endian(data, BigEndian);
                 self.write(&mut writer, byte_order, ctx)?;
                 writer.byte_align()?;
+                writer.into_writer()
             }
         };
 
-        Ok(data)
+        Ok(data.into_inner().freeze())
+    }
+
+    /// Writes the raw bytes of this type into `buf` with provided context,
+    /// returning the number of bytes written. If they do not fit, fails with
+    /// `Error::BufferFull`, leaving the bytes that did fit in `buf`.
+    fn write_to_slice_ctx(
+        &self,
+        buf: &mut [u8],
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+    ) -> Result<usize> {
+        let len = buf.len();
+        let mut rest = buf;
+        match write_endian(self, &mut rest, byte_order, ctx) {
+            Err(Error::IO(e)) if e.kind() == io::ErrorKind::WriteZero => Err(Error::BufferFull),
+            result => result.map(|()| len - rest.len()),
+        }
+    }
+
+    /// Gets the number of bits this type writes with provided context,
+    /// without storing them. This excludes the padding to the next byte
+    /// boundary that `bytes_ctx` adds.
+    ///
+    /// The default implementation always writes `self` into a
+    /// [`BitCounter`], even for types implementing
+    /// [`FixedSize`](crate::FixedSize), so that it fails wherever writing
+    /// would. Where only the length is needed, `SIZE_BITS` gives it at compile
+    /// time without writing.
+    fn bit_len_ctx(&self, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<u64> {
+        // The endianness of the counter does not affect the count.
+        let mut counter = BitCounter::<u64, BigEndian>::new();
+        self.write(&mut counter, byte_order, ctx)?;
+        Ok(counter.written())
+    }
+
+    /// Checks that this type can be written with provided context, without
+    /// storing its bytes. The default implementation counts the bits written,
+    /// failing with the error that writing would fail with.
+    ///
+    /// Derived structs instead check their fields directly: `bits` fields must
+    /// fit their width, tags must match the lengths of the fields they
+    /// prefix, which catches a `write_value` such as `self.data.len() as u8`
+    /// that truncates, and `present_if` must hold exactly for `Some`. Other
+    /// fields are validated in turn, but the contents of tagged and trailing
+    /// fields are not checked.
+    fn validate_ctx(&self, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.bit_len_ctx(byte_order, ctx).map(drop)
+    }
+
+    /// Gets the raw bytes of this type with provided context, along with the
+    /// bit ranges of fields recorded with `#[protocol(record_offsets)]`.
+    fn bytes_with_offsets_ctx(
+        &self,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+    ) -> Result<(Vec<u8>, FieldOffsets)> {
+        let mut data = Vec::new();
+        let offsets = match byte_order {
+            ByteOrder::LittleEndian => {
+                let mut writer = BitWriter::endian(&mut data, LittleEndian);
+                let mut recorder = OffsetRecorder::new(&mut writer);
+                self.write(&mut recorder, byte_order, ctx)?;
+                recorder.byte_align()?;
+                recorder.into_offsets()
+            }
+            ByteOrder::BigEndian => {
+                let mut writer = BitWriter::endian(&mut data, BigEndian);
+                let mut recorder = OffsetRecorder::new(&mut writer);
+                self.write(&mut recorder, byte_order, ctx)?;
+                recorder.byte_align()?;
+                recorder.into_offsets()
+            }
+        };
+
+        Ok((data, offsets))
+    }
+}
+
+/// Writes a value to `write`, followed by padding to the next byte boundary.
+fn write_endian<T, Ctx>(
+    value: &T,
+    write: impl io::Write,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+) -> Result<()>
+where
+    T: ProtocolWrite<Ctx> + ?Sized,
+{
+    match byte_order {
+        ByteOrder::LittleEndian => {
+            let mut writer = BitWriter::endian(write, LittleEndian);
+            value.write(&mut writer, byte_order, ctx)?;
+            writer.byte_align()?;
+        }
+        ByteOrder::BigEndian => {
+            let mut writer = BitWriter::endian(write, BigEndian);
+            value.write(&mut writer, byte_order, ctx)?;
+            writer.byte_align()?;
+        }
     }
+    Ok(())
 }
 
 /// A trait with helper functions for contextless `Protocol`s
@@ -55,10 +241,124 @@ pub trait ProtocolNoCtx: ProtocolRead + ProtocolWrite {
         Self::from_bytes_ctx(bytes, byte_order, &mut ())
     }
 
+    /// Parses a new value from a buffer without context.
+    #[cfg(feature = "bytes")]
+    fn from_buf(buf: impl bytes::Buf, byte_order: ByteOrder) -> Result<Self> {
+        Self::from_buf_ctx(buf, byte_order, &mut ())
+    }
+
     /// Gets the raw bytes of this type without context.
     fn bytes(&self, byte_order: ByteOrder) -> Result<Vec<u8>> {
         self.bytes_ctx(byte_order, &mut ())
     }
+
+    /// Gets the canonical bytes of this type without context, as by
+    /// [`ProtocolWrite::canonical_bytes_ctx`].
+    fn canonical_bytes(&self) -> Result<Vec<u8>> {
+        self.canonical_bytes_ctx(&mut ())
+    }
+
+    /// Feeds the canonical bytes of this type without context to `state`.
+    fn canonical_hash<H: Hasher>(&self, state: &mut H) -> Result<()> {
+        self.canonical_hash_ctx(state, &mut ())
+    }
+
+    /// Gets the raw bytes of this type without context, as a `bytes::Bytes`.
+    #[cfg(feature = "bytes")]
+    fn to_bytes(&self, byte_order: ByteOrder) -> Result<bytes::Bytes> {
+        self.to_bytes_ctx(byte_order, &mut ())
+    }
+
+    /// Writes the raw bytes of this type into `buf` without context, returning
+    /// the number of bytes written.
+    fn write_to_slice(&self, buf: &mut [u8], byte_order: ByteOrder) -> Result<usize> {
+        self.write_to_slice_ctx(buf, byte_order, &mut ())
+    }
+
+    /// Gets the number of bits this type writes without context, without
+    /// storing them.
+    fn bit_len(&self, byte_order: ByteOrder) -> Result<u64> {
+        self.bit_len_ctx(byte_order, &mut ())
+    }
+
+    /// Checks that this type can be written without context, without storing
+    /// its bytes.
+    fn validate(&self, byte_order: ByteOrder) -> Result<()> {
+        self.validate_ctx(byte_order, &mut ())
+    }
+
+    /// Gets the raw bytes of this type without context, along with the bit
+    /// ranges of fields recorded with `#[protocol(record_offsets)]`.
+    fn bytes_with_offsets(&self, byte_order: ByteOrder) -> Result<(Vec<u8>, FieldOffsets)> {
+        self.bytes_with_offsets_ctx(byte_order, &mut ())
+    }
 }
 
 impl<T> ProtocolNoCtx for T where T: ProtocolRead + ProtocolWrite {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_to_slice_exact_fit() {
+        let mut buf = [0; 4];
+        assert_eq!(
+            0x0102_0304u32
+                .write_to_slice(&mut buf, ByteOrder::BigEndian)
+                .unwrap(),
+            4
+        );
+        assert_eq!(buf, [0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn write_to_slice_leaves_rest_untouched() {
+        let mut buf = [0xff; 4];
+        assert_eq!(
+            0x0102u16
+                .write_to_slice(&mut buf, ByteOrder::LittleEndian)
+                .unwrap(),
+            2
+        );
+        assert_eq!(buf, [0x02, 0x01, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn write_to_slice_overflow() {
+        let mut buf = [0xff; 3];
+        assert!(matches!(
+            0x0102_0304u32.write_to_slice(&mut buf, ByteOrder::BigEndian),
+            Err(Error::BufferFull)
+        ));
+    }
+
+    #[test]
+    fn write_to_slice_writes_once() {
+        struct CountsWrites<'a>(&'a std::cell::Cell<u32>);
+
+        impl<Ctx> ProtocolWrite<Ctx> for CountsWrites<'_> {
+            fn write(&self, write: &mut dyn BitWrite, _: ByteOrder, _: &mut Ctx) -> Result<()> {
+                self.0.set(self.0.get() + 1);
+                write.write_u8(1)?;
+                Ok(())
+            }
+        }
+
+        let writes = std::cell::Cell::new(0);
+        let mut buf = [0; 1];
+        assert_eq!(
+            CountsWrites(&writes)
+                .write_to_slice_ctx(&mut buf, ByteOrder::BigEndian, &mut ())
+                .unwrap(),
+            1
+        );
+        assert_eq!(writes.get(), 1);
+    }
+
+    #[test]
+    fn bit_len_of_fixed_size() {
+        assert_eq!(0x0102_0304u32.bit_len(ByteOrder::BigEndian).unwrap(), 32);
+        assert_eq!([0u16; 3].bit_len(ByteOrder::LittleEndian).unwrap(), 48);
+    }
+}
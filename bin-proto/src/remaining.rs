@@ -0,0 +1,47 @@
+use bitstream_io::{BigEndian, LittleEndian};
+
+use crate::{bit_read::ChunkedReader, BitRead, ByteOrder, Result};
+use std::io;
+
+/// A trait for byte buffers capturing all remaining bytes of a stream
+/// verbatim, used by fields with `#[protocol(remaining)]`.
+pub trait RemainingRead: Sized {
+    fn read(read: &mut dyn BitRead) -> Result<Self>;
+}
+
+impl RemainingRead for Vec<u8> {
+    fn read(read: &mut dyn BitRead) -> Result<Self> {
+        Ok(read.read_remaining()?)
+    }
+}
+
+impl RemainingRead for Box<[u8]> {
+    fn read(read: &mut dyn BitRead) -> Result<Self> {
+        Ok(read.read_remaining()?.into_boxed_slice())
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl RemainingRead for bytes::Bytes {
+    fn read(read: &mut dyn BitRead) -> Result<Self> {
+        Ok(read.read_remaining_shared()?)
+    }
+}
+
+/// Reads all remaining bytes of a stream into memory, returning their number
+/// and a reader over them. Used by fields with `#[protocol(length = "...")]`.
+#[doc(hidden)]
+pub fn buffer_remaining(
+    read: &mut dyn BitRead,
+    byte_order: ByteOrder,
+) -> Result<(usize, Box<dyn BitRead>)> {
+    let bytes = read.read_remaining()?;
+    let len = bytes.len();
+    let reader: Box<dyn BitRead> = match byte_order {
+        ByteOrder::LittleEndian => {
+            Box::new(ChunkedReader::new(io::Cursor::new(bytes), LittleEndian))
+        }
+        ByteOrder::BigEndian => Box::new(ChunkedReader::new(io::Cursor::new(bytes), BigEndian)),
+    };
+    Ok((len, reader))
+}
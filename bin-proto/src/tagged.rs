@@ -1,18 +1,156 @@
 //! Utilities for externally length prefixed fields
 
-use crate::{BitRead, BitWrite, ByteOrder, Result};
+use crate::{BitRead, BitWrite, ByteOrder, Error, Result};
 
 /// A trait for decoding variable-length types with a disjoint length prefix.
+///
+/// The tag is supplied by the parent container, either from an earlier field
+/// via `#[protocol(tag = "<expr>")]`, or from a prefix read directly before the
+/// field via `#[protocol(tag(type = "<type>", write_value = "<expr>"))]`. Custom
+/// containers participate by implementing this trait and [`UntaggedWrite`].
+///
+/// ```
+/// # use bin_proto::{BitRead, BitWrite, ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite, Result, TaggedRead, UntaggedWrite};
+/// #[derive(Debug, PartialEq)]
+/// pub struct Stack(Vec<u8>);
+///
+/// impl<Tag: TryInto<usize>, Ctx> TaggedRead<Tag, Ctx> for Stack {
+///     fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx, tag: Tag) -> Result<Self> {
+///         let len = tag.try_into().map_err(|_| Error::TagConvert)?;
+///         let mut items = Vec::with_capacity(len);
+///         for _ in 0..len {
+///             items.push(u8::read(read, byte_order, ctx)?);
+///         }
+///         items.reverse();
+///         Ok(Self(items))
+///     }
+/// }
+///
+/// impl<Ctx> UntaggedWrite<Ctx> for Stack {
+///     fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+///         for item in self.0.iter().rev() {
+///             item.write(write, byte_order, ctx)?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// pub struct WithStack {
+///     #[protocol(tag(type = "u8", write_value = "self.stack.0.len() as u8"))]
+///     pub stack: Stack,
+/// }
+///
+/// let value = WithStack { stack: Stack(vec![1, 2, 3]) };
+/// let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+/// assert_eq!(bytes, [3, 3, 2, 1]);
+/// assert_eq!(WithStack::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(), value);
+/// ```
 pub trait TaggedRead<Tag, Ctx = ()>: Sized {
     fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx, tag: Tag)
         -> Result<Self>;
+
+    /// Reads a value into `self`, reusing its allocations where possible. See
+    /// [`ProtocolRead::read_replace`](crate::ProtocolRead::read_replace).
+    fn read_replace(
+        &mut self,
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<()> {
+        *self = Self::read(read, byte_order, ctx, tag)?;
+        Ok(())
+    }
 }
 
 /// A trait for encoding variable-length types with a disjoint length prefix.
+///
+/// Collections are written in iteration order. For `HashSet`, `HashMap` and
+/// `BinaryHeap` that order is unspecified, and reading builds the collection
+/// from whatever order the elements were serialized in.
 pub trait UntaggedWrite<Ctx = ()>: Sized {
     fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()>;
+
+    /// The tag this value has to be read with, such as the length of a
+    /// collection, if it is known without writing the value. Derived
+    /// implementations of `validate` check tags against it.
+    fn tag_len(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Multiplies a tag that was read by `scale` and adds `adjust` to it, for
+/// `#[protocol(tag_scale = <n>)]` and `#[protocol(tag_adjust = <n>)]`. Fails
+/// with `Error::TagConvert` if the result is negative.
+#[doc(hidden)]
+pub fn adjust_tag<Tag: TryInto<i128>>(tag: Tag, scale: u32, adjust: i64) -> Result<usize> {
+    let tag = tag.try_into().map_err(|_| Error::TagConvert)?;
+    tag.checked_mul(scale.into())
+        .and_then(|tag| tag.checked_add(adjust.into()))
+        .and_then(|tag| usize::try_from(tag).ok())
+        .ok_or(Error::TagConvert)
+}
+
+/// Subtracts `adjust` from a tag that is about to be written and divides it by
+/// `scale`, undoing [`adjust_tag`]. Fails with `Error::TagConvert` if the
+/// result is not a whole number or does not fit in the tag's type.
+#[doc(hidden)]
+pub fn unadjust_tag<Tag: TryInto<i128> + TryFrom<i128>>(
+    tag: Tag,
+    scale: u32,
+    adjust: i64,
+) -> Result<Tag> {
+    let tag = tag.try_into().map_err(|_| Error::TagConvert)?;
+    let scale = i128::from(scale);
+    tag.checked_sub(adjust.into())
+        .filter(|tag| tag % scale == 0)
+        .and_then(|tag| Tag::try_from(tag / scale).ok())
+        .ok_or(Error::TagConvert)
+}
+
+/// Checks that `tag`, as it is read back, matches the tag `value` has to be
+/// read with. `tag` is `None` if it is not read back as a length, in which
+/// case there is nothing to check. Used by derived implementations of
+/// `validate`.
+#[doc(hidden)]
+pub fn validate_tag<Ctx, T: UntaggedWrite<Ctx>>(
+    value: &T,
+    tag: Option<Option<usize>>,
+    field: &'static str,
+) -> Result<()> {
+    match (tag, value.tag_len()) {
+        (Some(tag), Some(len)) if tag != Some(len) => Err(Error::TagMismatch { field, len }),
+        _ => Ok(()),
+    }
 }
 
+/// A tag that `validate` compares against the length of its field, if it
+/// converts to `usize`. `(&TagLen(tag)).tag_as_len()` resolves to
+/// [`TagAsLen`] if it does, and to [`NotTagAsLen`] otherwise.
+#[doc(hidden)]
+pub struct TagLen<T>(pub T);
+
+#[doc(hidden)]
+pub trait TagAsLen {
+    fn tag_as_len(&self) -> Option<Option<usize>>;
+}
+
+impl<T: Copy + TryInto<usize>> TagAsLen for TagLen<T> {
+    fn tag_as_len(&self) -> Option<Option<usize>> {
+        Some(self.0.try_into().ok())
+    }
+}
+
+#[doc(hidden)]
+pub trait NotTagAsLen {
+    fn tag_as_len(&self) -> Option<Option<usize>> {
+        None
+    }
+}
+
+impl<T> NotTagAsLen for &TagLen<T> {}
+
 #[cfg(test)]
 macro_rules! test_externally_tagged {
     ($t:ty => [$bytes:expr, $value:expr]) => {
@@ -0,0 +1,63 @@
+//! Property-based tests for user-defined protocols
+
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    prop_assert, prop_assert_eq,
+    test_runner::{TestCaseError, TestRunner},
+};
+
+use crate::{ByteOrder, ProtocolNoCtx};
+use std::fmt::Debug;
+
+/// Asserts that arbitrary values of `T` are read back unchanged after being
+/// written.
+///
+/// # Panics
+///
+/// Panics with the minimal failing value if the property does not hold.
+pub fn roundtrip<T>(byte_order: ByteOrder)
+where
+    T: ProtocolNoCtx + Arbitrary + PartialEq + Debug,
+{
+    let result = TestRunner::default().run(&any::<T>(), |value| {
+        let bytes = value.bytes(byte_order).map_err(fail)?;
+        let read = T::from_bytes(&bytes, byte_order).map_err(fail)?;
+        prop_assert_eq!(read, value);
+        Ok(())
+    });
+    if let Err(e) = result {
+        panic!("{e}");
+    }
+}
+
+/// Asserts that reading arbitrary bytes as `T` either fails, or produces a
+/// value that is written as a prefix of those bytes.
+///
+/// # Panics
+///
+/// Panics with the minimal failing input if the property does not hold.
+pub fn reparse<T>(byte_order: ByteOrder)
+where
+    T: ProtocolNoCtx + Debug,
+{
+    let result = TestRunner::default().run(&any::<Vec<u8>>(), |bytes| {
+        if let Ok(value) = T::from_bytes(&bytes, byte_order) {
+            let written = value.bytes(byte_order).map_err(fail)?;
+            prop_assert!(
+                bytes.starts_with(&written),
+                "{:?} was written as {:?}",
+                value,
+                written
+            );
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        panic!("{e}");
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn fail(e: crate::Error) -> TestCaseError {
+    TestCaseError::fail(e.to_string())
+}
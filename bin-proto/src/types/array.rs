@@ -1,19 +1,51 @@
 use crate::{util, BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result};
-use std::convert::TryInto;
+use std::mem::MaybeUninit;
+
+/// Drops the initialized prefix of an array that is being filled in place.
+struct PartialArray<T, const N: usize> {
+    elements: [MaybeUninit<T>; N],
+    initialized: usize,
+}
+
+impl<T, const N: usize> Drop for PartialArray<T, N> {
+    fn drop(&mut self) {
+        for element in &mut self.elements[..self.initialized] {
+            // SAFETY: the first `initialized` elements have been written.
+            unsafe { element.assume_init_drop() };
+        }
+    }
+}
 
 impl<Ctx, T, const N: usize> ProtocolRead<Ctx> for [T; N]
 where
-    T: ProtocolRead<Ctx> + std::fmt::Debug,
+    T: ProtocolRead<Ctx>,
 {
     fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
-        let elements = util::read_items(N, read, byte_order, ctx)?;
-        Ok(elements.try_into().unwrap())
+        let mut array = PartialArray::<T, N> {
+            // SAFETY: an array of `MaybeUninit` does not require initialization.
+            elements: unsafe { MaybeUninit::uninit().assume_init() },
+            initialized: 0,
+        };
+        for element in &mut array.elements {
+            element.write(T::read(read, byte_order, ctx)?);
+            array.initialized += 1;
+        }
+
+        let elements = std::mem::replace(
+            &mut array.elements,
+            // SAFETY: an array of `MaybeUninit` does not require initialization.
+            unsafe { MaybeUninit::uninit().assume_init() },
+        );
+        array.initialized = 0;
+        // SAFETY: all `N` elements have been written, and ownership of them has
+        // been taken away from the guard.
+        Ok(elements.map(|element| unsafe { element.assume_init() }))
     }
 }
 
 impl<Ctx, T, const N: usize> ProtocolWrite<Ctx> for [T; N]
 where
-    T: ProtocolWrite<Ctx> + std::fmt::Debug,
+    T: ProtocolWrite<Ctx>,
 {
     fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
         util::write_items(self.iter(), write, byte_order, ctx)
@@ -46,4 +78,91 @@ mod tests {
             .unwrap();
         assert_eq!(data, vec![5, 7, 9, 11]);
     }
+
+    #[test]
+    fn can_read_large_array() {
+        let bytes: Vec<u8> = (0..=u8::MAX).cycle().take(100_000).collect();
+        let read_back: Box<[u8; 100_000]> = Box::new(
+            ProtocolRead::read(
+                &mut BitReader::endian(bytes.as_slice(), BigEndian),
+                ByteOrder::BigEndian,
+                &mut (),
+            )
+            .unwrap(),
+        );
+        assert_eq!(read_back.as_slice(), bytes.as_slice());
+    }
+
+    #[test]
+    fn matrix_round_trips_row_major() {
+        let matrix = [
+            [0u16, 1, 2, 3],
+            [4, 5, 6, 7],
+            [8, 9, 10, 11],
+            [12, 13, 14, 15],
+        ];
+        let mut data = Vec::new();
+        matrix
+            .write(
+                &mut BitWriter::endian(&mut data, BigEndian),
+                ByteOrder::BigEndian,
+                &mut (),
+            )
+            .unwrap();
+        let expected: Vec<u8> = (0..16u8).flat_map(|value| [0, value]).collect();
+        assert_eq!(data, expected);
+
+        let read_back: [[u16; 4]; 4] = ProtocolRead::read(
+            &mut BitReader::endian(data.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        )
+        .unwrap();
+        assert_eq!(read_back, matrix);
+    }
+
+    #[test]
+    fn matrix_propagates_inner_error() {
+        let result: Result<[[u8; 4]; 4]> = ProtocolRead::read(
+            &mut BitReader::endian([0u8; 14].as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        );
+        assert!(matches!(result, Err(crate::Error::IO(_))));
+    }
+
+    #[test]
+    fn drops_read_elements_on_error() {
+        use std::rc::Rc;
+
+        struct Counted {
+            _counter: Rc<()>,
+        }
+
+        impl ProtocolRead<Rc<()>> for Counted {
+            fn read(read: &mut dyn BitRead, _: ByteOrder, ctx: &mut Rc<()>) -> Result<Self> {
+                read.read_u8()?;
+                Ok(Self {
+                    _counter: Rc::clone(ctx),
+                })
+            }
+        }
+
+        let mut counter = Rc::new(());
+        let result: Result<[Counted; 4]> = ProtocolRead::read(
+            &mut BitReader::endian([0u8, 1, 2].as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut counter,
+        );
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let result: Result<[[Counted; 2]; 2]> = ProtocolRead::read(
+            &mut BitReader::endian([0u8, 1, 2].as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut counter,
+        );
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
 }
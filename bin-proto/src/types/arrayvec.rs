@@ -0,0 +1,99 @@
+use crate::{
+    util, BitRead, BitWrite, ByteOrder, Error, FlexibleArrayMemberRead, ProtocolRead,
+    ProtocolWrite, Result, TaggedRead, UntaggedWrite,
+};
+use arrayvec::ArrayVec;
+use std::io;
+
+fn capacity_exceeded() -> Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "length exceeds ArrayVec capacity",
+    )
+    .into()
+}
+
+/// Fails to read if the tag exceeds the capacity `CAP`, before reading any
+/// elements.
+impl<Tag, Ctx, T, const CAP: usize> TaggedRead<Tag, Ctx> for ArrayVec<T, CAP>
+where
+    T: ProtocolRead<Ctx>,
+    Tag: TryInto<usize>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<Self> {
+        let count = tag.try_into().map_err(|_| Error::TagConvert)?;
+        if count > CAP {
+            return Err(capacity_exceeded());
+        }
+        let mut elements = Self::new();
+        for _ in 0..count {
+            elements.push(T::read(read, byte_order, ctx)?);
+        }
+        Ok(elements)
+    }
+}
+
+impl<Ctx, T, const CAP: usize> UntaggedWrite<Ctx> for ArrayVec<T, CAP>
+where
+    T: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        util::write_items(self.iter(), write, byte_order, ctx)
+    }
+
+    fn tag_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<Ctx, T, const CAP: usize> FlexibleArrayMemberRead<Ctx> for ArrayVec<T, CAP>
+where
+    T: ProtocolRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let mut elements = Self::new();
+        for element in util::read_items_to_eof(read, byte_order, ctx)? {
+            elements
+                .try_push(element)
+                .map_err(|_| capacity_exceeded())?;
+        }
+        Ok(elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitstream_io::{BigEndian, BitReader};
+
+    use super::*;
+
+    test_externally_tagged!(ArrayVec<u8, 4> => [[1, 2, 3], ArrayVec::<u8, 4>::try_from([1, 2, 3].as_slice()).unwrap()]);
+
+    #[test]
+    fn over_capacity_fails() {
+        let bytes = [1, 2, 3];
+        let read: Result<ArrayVec<u8, 2>> = TaggedRead::read(
+            &mut BitReader::endian(bytes.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            3usize,
+        );
+        assert!(matches!(read, Err(Error::IO(e)) if e.kind() == io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn flexible_array_member_over_capacity_fails() {
+        let bytes = [1, 2, 3];
+        let read: Result<ArrayVec<u8, 2>> = FlexibleArrayMemberRead::read(
+            &mut BitReader::endian(bytes.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        );
+        assert!(read.is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result};
+use std::sync::atomic::Ordering;
+
+macro_rules! impl_protocol_for_atomic {
+    ($($cfg:literal => $ty:ident($inner:ty),)*) => {
+        $(
+            /// Encoded as the loaded value. Reads and writes use
+            /// [`Ordering::Relaxed`].
+            #[cfg(target_has_atomic = $cfg)]
+            impl<Ctx> ProtocolRead<Ctx> for std::sync::atomic::$ty {
+                fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+                    Ok(Self::new(<$inner>::read(read, byte_order, ctx)?))
+                }
+            }
+
+            #[cfg(target_has_atomic = $cfg)]
+            impl<Ctx> ProtocolWrite<Ctx> for std::sync::atomic::$ty {
+                fn write(
+                    &self,
+                    write: &mut dyn BitWrite,
+                    byte_order: ByteOrder,
+                    ctx: &mut Ctx,
+                ) -> Result<()> {
+                    self.load(Ordering::Relaxed).write(write, byte_order, ctx)
+                }
+            }
+        )*
+    };
+}
+
+impl_protocol_for_atomic!(
+    "8" => AtomicBool(bool),
+    "8" => AtomicU8(u8),
+    "8" => AtomicI8(i8),
+    "16" => AtomicU16(u16),
+    "16" => AtomicI16(i16),
+    "32" => AtomicU32(u32),
+    "32" => AtomicI32(i32),
+    "64" => AtomicU64(u64),
+    "64" => AtomicI64(i64),
+    "ptr" => AtomicUsize(usize),
+    "ptr" => AtomicIsize(isize),
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+    use std::sync::atomic::{AtomicBool, AtomicI16, AtomicU32, AtomicU64, AtomicUsize};
+
+    use super::*;
+
+    #[test]
+    fn atomic_bool_round_trips() {
+        let value = AtomicBool::new(true);
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, [1]);
+        assert!(AtomicBool::from_bytes(&bytes, ByteOrder::BigEndian)
+            .unwrap()
+            .into_inner());
+    }
+
+    #[test]
+    fn atomic_integers_round_trip() {
+        let value = AtomicU32::new(0x0102_0304);
+        value.fetch_add(1, Ordering::Relaxed);
+        let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+        assert_eq!(bytes, [0x05, 0x03, 0x02, 0x01]);
+        assert_eq!(
+            AtomicU32::from_bytes(&bytes, ByteOrder::LittleEndian)
+                .unwrap()
+                .into_inner(),
+            0x0102_0305
+        );
+
+        let value = AtomicI16::new(-2);
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, [0xff, 0xfe]);
+        assert_eq!(
+            AtomicI16::from_bytes(&bytes, ByteOrder::BigEndian)
+                .unwrap()
+                .into_inner(),
+            -2
+        );
+    }
+
+    #[test]
+    fn atomic_u64_round_trips() {
+        let value = AtomicU64::new(u64::MAX - 1);
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(
+            AtomicU64::from_bytes(&bytes, ByteOrder::BigEndian)
+                .unwrap()
+                .into_inner(),
+            u64::MAX - 1
+        );
+    }
+
+    #[test]
+    fn atomic_usize_matches_usize() {
+        let value = AtomicUsize::new(42);
+        assert_eq!(
+            value.bytes(ByteOrder::BigEndian).unwrap(),
+            42usize.bytes(ByteOrder::BigEndian).unwrap()
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::{mem, ops::Deref};
+
+use crate::{
+    BitFieldRead, BitFieldWrite, BitRead, BitWrite, ByteOrder, FixedSize, ProtocolRead,
+    ProtocolWrite, Result,
+};
+
+/// An integer occupying exactly `N` bits, so that the width of a bit field is
+/// part of its type rather than of a `#[protocol(bits = N)]` attribute.
+///
+/// The value is checked to fit in `N` bits when it is constructed, and `N`
+/// is checked to be at most the width of `T` at compile time.
+///
+/// ```
+/// # use bin_proto::{Bits, ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// type Version = Bits<4, u8>;
+///
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// struct Header {
+///     version: Version,
+///     header_length: Bits<4, u8>,
+///     length: u16,
+/// }
+///
+/// let header = Header {
+///     version: Version::new(4).unwrap(),
+///     header_length: Bits::new(5).unwrap(),
+///     length: 20,
+/// };
+/// let bytes = header.bytes(ByteOrder::BigEndian).unwrap();
+/// assert_eq!(bytes, [0x45, 0x00, 0x14]);
+/// assert_eq!(Header::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(), header);
+/// assert!(Version::new(16).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bits<const N: u32, T = u32>(T);
+
+impl<const N: u32, T> Deref for Bits<N, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<const N: u32, T> FixedSize for Bits<N, T> {
+    const SIZE_BITS: usize = N as usize;
+}
+
+impl<const N: u32, T> Bits<N, T> {
+    const WIDTH_CHECK: () = assert!(
+        N > 0 && N as usize <= mem::size_of::<T>() * 8,
+        "Bits width must be between 1 and the width of its type",
+    );
+
+    /// The wrapped value.
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<const N: u32, T> Bits<N, T>
+where
+    T: Copy + Into<i128> + TryFrom<i128>,
+{
+    /// Wraps `value`, or returns `None` if it does not fit in `N` bits.
+    #[must_use]
+    pub fn new(value: T) -> Option<Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::WIDTH_CHECK;
+        let signed = T::try_from(-1).is_ok();
+        let value_bits = if signed { N - 1 } else { N };
+        let value_range = -(i128::from(signed) << value_bits)..(1 << value_bits);
+        value_range.contains(&value.into()).then_some(Self(value))
+    }
+}
+
+macro_rules! impl_from_bits {
+    ($($ty:ty),*) => {
+        $(
+            impl<const N: u32> From<Bits<N, $ty>> for $ty {
+                fn from(value: Bits<N, $ty>) -> Self {
+                    value.0
+                }
+            }
+        )*
+    };
+}
+
+impl_from_bits!(u8, i8, u16, i16, u32, i32);
+
+impl<Ctx, const N: u32, T> ProtocolRead<Ctx> for Bits<N, T>
+where
+    T: BitFieldRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::WIDTH_CHECK;
+        Ok(Self(T::read(read, byte_order, ctx, N)?))
+    }
+}
+
+impl<Ctx, const N: u32, T> ProtocolWrite<Ctx> for Bits<N, T>
+where
+    T: BitFieldWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.0.write(write, byte_order, ctx, N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+
+    use super::*;
+
+    #[test]
+    fn new_checks_range() {
+        assert_eq!(Bits::<3, u8>::new(7).map(u8::from), Some(7));
+        assert!(Bits::<3, u8>::new(8).is_none());
+        assert_eq!(Bits::<8, u8>::new(0xff).map(u8::from), Some(0xff));
+        assert_eq!(Bits::<4, i8>::new(-8).map(i8::from), Some(-8));
+        assert_eq!(Bits::<4, i8>::new(7).map(i8::from), Some(7));
+        assert!(Bits::<4, i8>::new(8).is_none());
+        assert!(Bits::<4, i8>::new(-9).is_none());
+        assert_eq!(*Bits::<20>::new(0xf_ffff).unwrap(), 0xf_ffff);
+    }
+
+    #[test]
+    fn reads_signed_values() {
+        let value =
+            <(Bits<4, i8>, Bits<4, i8>)>::from_bytes(&[0xf7], ByteOrder::BigEndian).unwrap();
+        assert_eq!((value.0.get(), value.1.get()), (-1, 7));
+        assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), [0xf7]);
+    }
+}
+
+/// ```compile_fail
+/// # use bin_proto::Bits;
+/// let _ = Bits::<9, u8>::new(0);
+/// ```
+#[cfg(doctest)]
+#[allow(unused)]
+fn compile_fail_if_bits_wider_than_type() {}
@@ -0,0 +1,126 @@
+use crate::{
+    BitFieldRead, BitFieldWrite, BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite,
+    Result,
+};
+
+macro_rules! bool_wrapper {
+    ($(#[$attr:meta])* $name:ident, $from_u8:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub bool);
+
+        impl From<bool> for $name {
+            fn from(value: bool) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for bool {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl<Ctx> ProtocolRead<Ctx> for $name {
+            fn read(read: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx) -> Result<Self> {
+                let from_u8: fn(u8) -> Result<bool> = $from_u8;
+                Ok(Self(from_u8(read.read_u8()?)?))
+            }
+        }
+
+        impl<Ctx> ProtocolWrite<Ctx> for $name {
+            fn write(&self, write: &mut dyn BitWrite, _: ByteOrder, _: &mut Ctx) -> Result<()> {
+                write.write_u8(self.0.into())?;
+                Ok(())
+            }
+        }
+
+        impl<Ctx> BitFieldRead<Ctx> for $name {
+            fn read(read: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx, bits: u32) -> Result<Self> {
+                let from_u8: fn(u8) -> Result<bool> = $from_u8;
+                Ok(Self(from_u8(read.read_u8_bf(bits)?)?))
+            }
+        }
+
+        impl<Ctx> BitFieldWrite<Ctx> for $name {
+            fn write(
+                &self,
+                write: &mut dyn BitWrite,
+                _: ByteOrder,
+                _: &mut Ctx,
+                bits: u32,
+            ) -> Result<()> {
+                write.write_u8_bf(bits, self.0.into())?;
+                Ok(())
+            }
+        }
+    };
+}
+
+bool_wrapper!(
+    /// A `bool` that fails to read with [`Error::InvalidBool`] unless it is
+    /// encoded as exactly 0 or 1.
+    ///
+    /// Plain `bool` reads any nonzero value as `true`.
+    StrictBool,
+    |value| match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(Error::InvalidBool(value)),
+    }
+);
+
+bool_wrapper!(
+    /// A `bool` that reads any nonzero value as `true`, and always writes 0 or
+    /// 1. This is how plain `bool` behaves, spelled out in the type.
+    LenientBool,
+    |value| Ok(value != 0)
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+
+    use super::*;
+
+    #[test]
+    fn strict_bool() {
+        assert_eq!(
+            StrictBool::from_bytes(&[0], ByteOrder::BigEndian).unwrap(),
+            StrictBool(false)
+        );
+        assert_eq!(
+            StrictBool::from_bytes(&[1], ByteOrder::BigEndian).unwrap(),
+            StrictBool(true)
+        );
+        for value in [0x02, 0xff] {
+            assert!(matches!(
+                StrictBool::from_bytes(&[value], ByteOrder::BigEndian),
+                Err(Error::InvalidBool(v)) if v == value
+            ));
+        }
+    }
+
+    #[test]
+    fn lenient_bool() {
+        for (value, expected) in [(0, false), (1, true), (0x02, true), (0xff, true)] {
+            let read = LenientBool::from_bytes(&[value], ByteOrder::BigEndian).unwrap();
+            assert_eq!(read, LenientBool(expected));
+            assert_eq!(
+                read.bytes(ByteOrder::BigEndian).unwrap(),
+                [u8::from(expected)]
+            );
+        }
+    }
+
+    #[test]
+    fn strict_bool_bitfield() {
+        let read: Result<StrictBool> = BitFieldRead::read(
+            &mut bitstream_io::BitReader::endian([0b1100_0000].as_slice(), bitstream_io::BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            2,
+        );
+        assert!(matches!(read, Err(Error::InvalidBool(3))));
+    }
+}
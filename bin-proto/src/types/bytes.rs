@@ -0,0 +1,38 @@
+use bytes::Bytes;
+
+use crate::{
+    BitRead, BitWrite, ByteOrder, Error, FlexibleArrayMemberRead, Result, TaggedRead, UntaggedWrite,
+};
+
+impl<Tag, Ctx> TaggedRead<Tag, Ctx> for Bytes
+where
+    Tag: TryInto<usize>,
+{
+    fn read(read: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx, tag: Tag) -> Result<Self> {
+        Ok(read.read_to_shared(tag.try_into().map_err(|_| Error::TagConvert)?)?)
+    }
+}
+
+impl<Ctx> UntaggedWrite<Ctx> for Bytes {
+    fn write(&self, write: &mut dyn BitWrite, _: ByteOrder, _: &mut Ctx) -> Result<()> {
+        write.write_bytes(self)?;
+        Ok(())
+    }
+
+    fn tag_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<Ctx> FlexibleArrayMemberRead<Ctx> for Bytes {
+    fn read(read: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx) -> Result<Self> {
+        Ok(read.read_remaining_shared()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_externally_tagged!(Bytes => [[0x01, 0x02, 0x03], Bytes::from_static(&[0x01, 0x02, 0x03])]);
+}
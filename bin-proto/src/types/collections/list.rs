@@ -1,5 +1,8 @@
 macro_rules! impl_list_type {
     ($ty:ident => T: $( $ty_pred:ident ),*) => {
+        impl_list_type!($ty => T: $( $ty_pred ),* ; $crate::util::write_items);
+    };
+    ($ty:ident => T: $( $ty_pred:ident ),* ; $write_items:path) => {
         impl<Tag, Ctx, T> $crate::TaggedRead<Tag, Ctx> for $ty<T>
             where
                 T: $crate::ProtocolRead<Ctx> $( + $ty_pred )*,
@@ -13,6 +16,20 @@ macro_rules! impl_list_type {
                 let elements = $crate::util::read_items(tag.try_into().map_err(|_| $crate::Error::TagConvert)?, read, byte_order, ctx)?;
                 Ok(elements.into_iter().collect())
             }
+
+            fn read_replace(&mut self,
+                            read: &mut dyn $crate::BitRead,
+                            byte_order: $crate::ByteOrder,
+                            ctx: &mut Ctx,
+                            tag: Tag,
+                            ) -> $crate::Result<()> {
+                let count = tag.try_into().map_err(|_| $crate::Error::TagConvert)?;
+                self.clear();
+                for _ in 0..count {
+                    self.extend(::std::iter::once(T::read(read, byte_order, ctx)?));
+                }
+                Ok(())
+            }
         }
 
         impl<Ctx, T> $crate::UntaggedWrite<Ctx> for $ty<T>
@@ -23,7 +40,11 @@ macro_rules! impl_list_type {
                      byte_order: $crate::ByteOrder,
                      ctx: &mut Ctx,
                      ) -> $crate::Result<()> {
-                $crate::util::write_items(self.iter(), write, byte_order, ctx)
+                $write_items(self.iter(), write, byte_order, ctx)
+            }
+
+            fn tag_len(&self) -> Option<usize> {
+                Some(self.len())
             }
         }
 
@@ -79,11 +100,54 @@ mod hash_set {
     use std::collections::HashSet;
     use std::hash::Hash;
 
-    impl_list_type!(HashSet => T: Hash, Eq);
+    impl_list_type!(HashSet => T: Hash, Eq; crate::util::write_unordered_items);
 }
 
 mod binary_heap {
     use std::collections::BinaryHeap;
 
     impl_list_type!(BinaryHeap => T: Ord);
+
+    #[cfg(test)]
+    mod tests {
+        use bitstream_io::{BigEndian, BitReader, BitWriter};
+
+        use super::*;
+        use crate::{ByteOrder, TaggedRead, UntaggedWrite};
+
+        #[test]
+        fn read_builds_heap_from_serialized_order() {
+            let bytes = [0x00, 0x02, 0x00, 0x03, 0x00, 0x01];
+            let heap: BinaryHeap<u16> = TaggedRead::read(
+                &mut BitReader::endian(bytes.as_slice(), BigEndian),
+                ByteOrder::BigEndian,
+                &mut (),
+                3usize,
+            )
+            .unwrap();
+            assert_eq!(heap.peek(), Some(&3));
+            assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
+        }
+
+        #[test]
+        fn round_trips_contents() {
+            let heap = BinaryHeap::from([5u16, 1, 4, 2, 3]);
+            let mut buffer = Vec::new();
+            UntaggedWrite::write(
+                &heap,
+                &mut BitWriter::endian(&mut buffer, BigEndian),
+                ByteOrder::BigEndian,
+                &mut (),
+            )
+            .unwrap();
+            let read_back: BinaryHeap<u16> = TaggedRead::read(
+                &mut BitReader::endian(buffer.as_slice(), BigEndian),
+                ByteOrder::BigEndian,
+                &mut (),
+                heap.len(),
+            )
+            .unwrap();
+            assert_eq!(read_back.into_sorted_vec(), heap.into_sorted_vec());
+        }
+    }
 }
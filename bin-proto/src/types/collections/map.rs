@@ -1,5 +1,86 @@
+/// A map that fails to read with [`Error::DuplicateMapKey`] if a key occurs
+/// more than once.
+///
+/// Plain maps keep the last value read for a key.
+///
+/// [`Error::DuplicateMapKey`]: crate::Error::DuplicateMapKey
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StrictMap<M>(pub M);
+
+/// A map that keeps the first value read for a key that occurs more than once,
+/// and ignores the rest.
+///
+/// Plain maps keep the last value read for a key.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeepFirstMap<M>(pub M);
+
+macro_rules! impl_map_wrapper {
+    ( $wrapper:ident, $ty:ident => K: $( $k_pred:ident ),+ ; $insert:expr ) => {
+        impl<Tag, Ctx, K, V> $crate::TaggedRead<Tag, Ctx> for $wrapper<$ty<K, V>>
+        where
+            K: $crate::ProtocolRead<Ctx> + $( $k_pred +)+,
+            V: $crate::ProtocolRead<Ctx>,
+            Tag: TryInto<usize>,
+        {
+            fn read(read: &mut dyn $crate::BitRead,
+                    byte_order: $crate::ByteOrder,
+                    ctx: &mut Ctx,
+                    tag: Tag,
+                    ) -> $crate::Result<Self> {
+                let insert: fn(&mut $ty<K, V>, usize, K, V) -> $crate::Result<()> = $insert;
+                let count = tag.try_into().map_err(|_| $crate::Error::TagConvert)?;
+                let mut map = $ty::new();
+                for index in 0..count {
+                    let key = K::read(read, byte_order, ctx)?;
+                    let value = V::read(read, byte_order, ctx)?;
+                    insert(&mut map, index, key, value)?;
+                }
+                Ok(Self(map))
+            }
+        }
+
+        impl<Ctx, K, V> $crate::UntaggedWrite<Ctx> for $wrapper<$ty<K, V>>
+        where
+            K: $crate::ProtocolWrite<Ctx> + $( $k_pred +)+,
+            V: $crate::ProtocolWrite<Ctx>
+        {
+            fn write(&self, write: &mut dyn $crate::BitWrite,
+                    byte_order: $crate::ByteOrder,
+                    ctx: &mut Ctx,
+                    ) -> $crate::Result<()> {
+                $crate::UntaggedWrite::write(&self.0, write, byte_order, ctx)
+            }
+
+            fn tag_len(&self) -> Option<usize> {
+                Some(self.0.len())
+            }
+        }
+
+        impl<Ctx, K, V> $crate::FlexibleArrayMemberRead<Ctx> for $wrapper<$ty<K, V>>
+        where
+            K: $crate::ProtocolRead<Ctx> + $( $k_pred +)+,
+            V: $crate::ProtocolRead<Ctx>,
+        {
+            fn read(read: &mut dyn $crate::BitRead,
+                    byte_order: $crate::ByteOrder,
+                    ctx: &mut Ctx,
+                    ) -> $crate::Result<Self> {
+                let insert: fn(&mut $ty<K, V>, usize, K, V) -> $crate::Result<()> = $insert;
+                let mut map = $ty::new();
+                for (index, (key, value)) in $crate::util::read_items_to_eof(read, byte_order, ctx)?.into_iter().enumerate() {
+                    insert(&mut map, index, key, value)?;
+                }
+                Ok(Self(map))
+            }
+        }
+    }
+}
+
 macro_rules! impl_map_type {
     ( $ty:ident => K: $( $k_pred:ident ),+ ) => {
+        impl_map_type!($ty => K: $( $k_pred ),+ ; $crate::util::write_entries);
+    };
+    ( $ty:ident => K: $( $k_pred:ident ),+ ; $write_entries:path ) => {
         impl<Tag, Ctx, K, V> $crate::TaggedRead<Tag, Ctx> for $ty<K, V>
         where
             K: $crate::ProtocolRead<Ctx> + $( $k_pred +)+,
@@ -14,6 +95,22 @@ macro_rules! impl_map_type {
                 let elements = $crate::util::read_items(tag.try_into().map_err(|_| $crate::Error::TagConvert)?, read, byte_order, ctx)?;
                 Ok(elements.into_iter().collect())
             }
+
+            fn read_replace(&mut self,
+                            read: &mut dyn $crate::BitRead,
+                            byte_order: $crate::ByteOrder,
+                            ctx: &mut Ctx,
+                            tag: Tag,
+                            ) -> $crate::Result<()> {
+                let count = tag.try_into().map_err(|_| $crate::Error::TagConvert)?;
+                self.clear();
+                for _ in 0..count {
+                    let key = K::read(read, byte_order, ctx)?;
+                    let value = V::read(read, byte_order, ctx)?;
+                    self.insert(key, value);
+                }
+                Ok(())
+            }
         }
 
         impl<Ctx, K, V> $crate::UntaggedWrite<Ctx> for $ty<K, V>
@@ -25,12 +122,11 @@ macro_rules! impl_map_type {
                     byte_order: $crate::ByteOrder,
                     ctx: &mut Ctx,
                     ) -> $crate::Result<()> {
-                for (key, value) in self.iter() {
-                    key.write(write, byte_order, ctx)?;
-                    value.write(write, byte_order, ctx)?;
-                }
+                $write_entries(self.iter(), write, byte_order, ctx)
+            }
 
-                Ok(())
+            fn tag_len(&self) -> Option<usize> {
+                Some(self.len())
             }
         }
 
@@ -46,6 +142,19 @@ macro_rules! impl_map_type {
                 Ok($crate::util::read_items_to_eof(read, byte_order, ctx)?.into_iter().collect())
             }
         }
+
+        impl_map_wrapper!(StrictMap, $ty => K: $( $k_pred ),+ ; |map, index, key, value| {
+            if map.contains_key(&key) {
+                return Err($crate::Error::DuplicateMapKey { index });
+            }
+            map.insert(key, value);
+            Ok(())
+        });
+
+        impl_map_wrapper!(KeepFirstMap, $ty => K: $( $k_pred ),+ ; |map, _, key, value| {
+            map.entry(key).or_insert(value);
+            Ok(())
+        });
     }
 }
 
@@ -62,13 +171,15 @@ macro_rules! test_map_type {
 }
 
 mod hash_map {
+    use super::{KeepFirstMap, StrictMap};
     use std::collections::HashMap;
     use std::hash::Hash;
 
-    impl_map_type!(HashMap => K: Hash, Eq);
+    impl_map_type!(HashMap => K: Hash, Eq; crate::util::write_unordered_entries);
 }
 
 mod b_tree_map {
+    use super::{KeepFirstMap, StrictMap};
     use std::collections::BTreeMap;
 
     impl_map_type!(BTreeMap => K: Ord);
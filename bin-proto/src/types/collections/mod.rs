@@ -1,2 +1,4 @@
 mod list;
 mod map;
+
+pub use self::map::{KeepFirstMap, StrictMap};
@@ -0,0 +1,135 @@
+use crate::{BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite, Result};
+use std::io;
+use time::{Date, Duration, OffsetDateTime, UtcOffset};
+
+/// The Unix epoch, 1970-01-01.
+fn epoch() -> Date {
+    OffsetDateTime::UNIX_EPOCH.date()
+}
+
+fn component_range(e: time::error::ComponentRange) -> Error {
+    Error::Other(Box::new(e))
+}
+
+/// Encoded as an `i64` Unix timestamp in seconds, a `u32` number of
+/// nanoseconds within that second, and an `i16` UTC offset in minutes.
+impl<Ctx> ProtocolRead<Ctx> for OffsetDateTime {
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let timestamp = i64::read(read, byte_order, ctx)?;
+        let nanosecond = u32::read(read, byte_order, ctx)?;
+        let offset_minutes = i16::read(read, byte_order, ctx)?;
+
+        let offset = UtcOffset::from_whole_seconds(i32::from(offset_minutes) * 60)
+            .map_err(component_range)?;
+        Self::from_unix_timestamp(timestamp)
+            .map_err(component_range)?
+            .replace_nanosecond(nanosecond)
+            .map_err(component_range)?
+            .checked_to_offset(offset)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "date-time out of range").into()
+            })
+    }
+}
+
+impl<Ctx> ProtocolWrite<Ctx> for OffsetDateTime {
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        let offset = self.offset().whole_seconds();
+        if offset % 60 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "UTC offset is not a whole number of minutes",
+            )
+            .into());
+        }
+        self.unix_timestamp().write(write, byte_order, ctx)?;
+        self.nanosecond().write(write, byte_order, ctx)?;
+        i16::try_from(offset / 60)?.write(write, byte_order, ctx)
+    }
+}
+
+/// Encoded as an `i32` number of days since the Unix epoch.
+impl<Ctx> ProtocolRead<Ctx> for Date {
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let days = i32::read(read, byte_order, ctx)?;
+        epoch()
+            .checked_add(Duration::days(days.into()))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "date out of range").into())
+    }
+}
+
+impl<Ctx> ProtocolWrite<Ctx> for Date {
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        i32::try_from((*self - epoch()).whole_days())?.write(write, byte_order, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+    use time::Month;
+
+    use super::*;
+
+    #[test]
+    fn utc_date_time_round_trips() {
+        let value = OffsetDateTime::from_unix_timestamp(1_700_000_000)
+            .unwrap()
+            .replace_nanosecond(123_456_789)
+            .unwrap();
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(
+            bytes,
+            [0x00, 0x00, 0x00, 0x00, 0x65, 0x53, 0xf1, 0x00, 0x07, 0x5b, 0xcd, 0x15, 0x00, 0x00]
+        );
+        assert_eq!(
+            OffsetDateTime::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn offset_date_time_round_trips() {
+        let value = OffsetDateTime::from_unix_timestamp(-86_400)
+            .unwrap()
+            .to_offset(UtcOffset::from_hms(-5, -30, 0).unwrap());
+        let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+        assert_eq!(bytes[12..], (-330i16).to_le_bytes());
+        let read = OffsetDateTime::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(read, value);
+        assert_eq!(read.offset(), value.offset());
+        assert_eq!(read.date(), value.date());
+    }
+
+    #[test]
+    fn rejects_invalid_offset() {
+        let mut bytes = OffsetDateTime::UNIX_EPOCH
+            .bytes(ByteOrder::BigEndian)
+            .unwrap();
+        bytes[12..].copy_from_slice(&(26 * 60i16).to_be_bytes());
+        assert!(matches!(
+            OffsetDateTime::from_bytes(&bytes, ByteOrder::BigEndian),
+            Err(Error::Other(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_nanosecond() {
+        let mut bytes = OffsetDateTime::UNIX_EPOCH
+            .bytes(ByteOrder::BigEndian)
+            .unwrap();
+        bytes[8..12].copy_from_slice(&1_000_000_000u32.to_be_bytes());
+        assert!(OffsetDateTime::from_bytes(&bytes, ByteOrder::BigEndian).is_err());
+    }
+
+    #[test]
+    fn date_round_trips() {
+        let value = Date::from_calendar_date(1969, Month::December, 31).unwrap();
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, (-1i32).to_be_bytes());
+        assert_eq!(
+            Date::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+}
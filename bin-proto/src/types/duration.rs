@@ -0,0 +1,109 @@
+use crate::{BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result};
+use std::time::Duration;
+
+macro_rules! duration_unit {
+    ($(#[$attr:meta])* $name:ident, $as_unit:ident, $from_unit:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub Duration);
+
+        impl From<Duration> for $name {
+            fn from(value: Duration) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for Duration {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl<Ctx> ProtocolRead<Ctx> for $name {
+            fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+                Ok(Self(Duration::$from_unit(u64::read(read, byte_order, ctx)?)))
+            }
+        }
+
+        impl<Ctx> ProtocolWrite<Ctx> for $name {
+            fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+                u64::try_from(self.0.$as_unit())?.write(write, byte_order, ctx)
+            }
+        }
+    };
+}
+
+duration_unit!(
+    /// A `Duration` encoded as a `u64` number of milliseconds, truncating
+    /// sub-millisecond precision.
+    Millis,
+    as_millis,
+    from_millis
+);
+
+duration_unit!(
+    /// A `Duration` encoded as a `u64` number of microseconds, truncating
+    /// sub-microsecond precision.
+    Micros,
+    as_micros,
+    from_micros
+);
+
+duration_unit!(
+    /// A `Duration` encoded as a `u64` number of nanoseconds.
+    Nanos,
+    as_nanos,
+    from_nanos
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, ProtocolNoCtx};
+
+    use super::*;
+
+    #[test]
+    fn millis_round_trip_truncates() {
+        let value = Millis(Duration::new(2, 345_678_901));
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, 2345u64.to_be_bytes());
+        assert_eq!(
+            Millis::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            Millis(Duration::from_millis(2345))
+        );
+    }
+
+    #[test]
+    fn micros_round_trip_truncates() {
+        let value = Micros(Duration::new(2, 345_678_901));
+        let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+        assert_eq!(bytes, 2_345_678u64.to_le_bytes());
+        assert_eq!(
+            Micros::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+            Micros(Duration::from_micros(2_345_678))
+        );
+    }
+
+    #[test]
+    fn nanos_round_trip() {
+        let value = Nanos(Duration::new(2, 345_678_901));
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, 2_345_678_901u64.to_be_bytes());
+        assert_eq!(
+            Nanos::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn overflow_fails_to_write() {
+        assert!(matches!(
+            Nanos(Duration::MAX).bytes(ByteOrder::BigEndian),
+            Err(Error::TryFromIntError(_))
+        ));
+        assert!(matches!(
+            Millis(Duration::MAX).bytes(ByteOrder::BigEndian),
+            Err(Error::TryFromIntError(_))
+        ));
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    util, BitRead, BitWrite, ByteOrder, Error, FixedSize, ProtocolRead, ProtocolWrite, Result,
+    TaggedRead, UntaggedWrite,
+};
+use std::ops::{Deref, DerefMut};
+
+/// Up to `N` elements in `N` slots. The slots after the elements are filled
+/// with `T::default()` when writing.
+///
+/// With a tag, as in `#[protocol(tag = "<expr>")]`, the tag is the number of
+/// elements, and the filler after them is read and discarded. Without one,
+/// all `N` slots are read as elements, and [`FixedList::trim_defaults`]
+/// removes the filler. Writing more than `N` elements fails with
+/// `Error::TooManyElements`.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, FixedList, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// struct Neighbors {
+///     #[protocol(write_value = "self.ids.len() as u8")]
+///     count: u8,
+///     #[protocol(tag = "count")]
+///     ids: FixedList<u16, 4>,
+/// }
+///
+/// let neighbors = Neighbors {
+///     count: 2,
+///     ids: FixedList(vec![7, 9]),
+/// };
+/// let bytes = neighbors.bytes(ByteOrder::BigEndian).unwrap();
+/// assert_eq!(bytes, [2, 0, 7, 0, 9, 0, 0, 0, 0]);
+/// assert_eq!(Neighbors::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(), neighbors);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedList<T, const N: usize>(pub Vec<T>);
+
+impl<T, const N: usize> FixedList<T, N> {
+    /// Removes the elements equal to `T::default()` from the end.
+    pub fn trim_defaults(&mut self)
+    where
+        T: Default + PartialEq,
+    {
+        let default = T::default();
+        while self.0.last() == Some(&default) {
+            self.0.pop();
+        }
+    }
+
+    fn check_len(len: usize) -> Result<()> {
+        if len > N {
+            Err(Error::TooManyElements {
+                max: N,
+                actual: len,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T, const N: usize> Deref for FixedList<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> DerefMut for FixedList<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T, const N: usize> From<Vec<T>> for FixedList<T, N> {
+    fn from(value: Vec<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, const N: usize> From<FixedList<T, N>> for Vec<T> {
+    fn from(value: FixedList<T, N>) -> Self {
+        value.0
+    }
+}
+
+impl<Tag, Ctx, T, const N: usize> TaggedRead<Tag, Ctx> for FixedList<T, N>
+where
+    T: ProtocolRead<Ctx>,
+    Tag: TryInto<usize>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<Self> {
+        let count = tag.try_into().map_err(|_| Error::TagConvert)?;
+        Self::check_len(count)?;
+        let elements = util::read_items(count, read, byte_order, ctx)?;
+        for _ in count..N {
+            T::read(read, byte_order, ctx)?;
+        }
+        Ok(Self(elements))
+    }
+}
+
+impl<Ctx, T, const N: usize> ProtocolRead<Ctx> for FixedList<T, N>
+where
+    T: ProtocolRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        Ok(Self(util::read_items(N, read, byte_order, ctx)?))
+    }
+}
+
+impl<Ctx, T, const N: usize> UntaggedWrite<Ctx> for FixedList<T, N>
+where
+    T: ProtocolWrite<Ctx> + Default,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        ProtocolWrite::write(self, write, byte_order, ctx)
+    }
+}
+
+impl<Ctx, T, const N: usize> ProtocolWrite<Ctx> for FixedList<T, N>
+where
+    T: ProtocolWrite<Ctx> + Default,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        Self::check_len(self.0.len())?;
+        util::write_items(&self.0, write, byte_order, ctx)?;
+        let filler = T::default();
+        for _ in self.0.len()..N {
+            filler.write(write, byte_order, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: FixedSize, const N: usize> FixedSize for FixedList<T, N> {
+    const SIZE_BITS: usize = T::SIZE_BITS * N;
+}
+
+#[cfg(test)]
+mod tests {
+    use bitstream_io::{BigEndian, BitReader};
+
+    use crate::ProtocolNoCtx;
+
+    use super::*;
+
+    #[test]
+    fn writes_filler() {
+        assert_eq!(
+            FixedList::<u16, 3>(vec![0x0102])
+                .bytes(ByteOrder::LittleEndian)
+                .unwrap(),
+            [0x02, 0x01, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            FixedList::<u8, 2>(vec![])
+                .bytes(ByteOrder::BigEndian)
+                .unwrap(),
+            [0, 0]
+        );
+    }
+
+    #[test]
+    fn tagged_read_skips_filler() {
+        let bytes: &[u8] = &[1, 2, 0xff, 0xff, 3];
+        let mut reader = BitReader::endian(bytes, BigEndian);
+        let list: FixedList<u8, 4> =
+            TaggedRead::read(&mut reader, ByteOrder::BigEndian, &mut (), 2usize).unwrap();
+        assert_eq!(*list, [1, 2]);
+        assert_eq!(
+            u8::read(&mut reader, ByteOrder::BigEndian, &mut ()).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn untagged_read_trims_defaults() {
+        let mut list = FixedList::<u8, 4>::from_bytes(&[1, 0, 2, 0], ByteOrder::BigEndian).unwrap();
+        assert_eq!(*list, [1, 0, 2, 0]);
+        list.trim_defaults();
+        assert_eq!(*list, [1, 0, 2]);
+    }
+
+    #[test]
+    fn round_trips_full() {
+        let list = FixedList::<u32, 2>(vec![1, 2]);
+        let bytes = list.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, [0, 0, 0, 1, 0, 0, 0, 2]);
+        assert_eq!(
+            FixedList::<u32, 2>::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            list
+        );
+    }
+
+    #[test]
+    fn rejects_too_many_elements() {
+        assert!(matches!(
+            FixedList::<u8, 2>(vec![1, 2, 3]).bytes(ByteOrder::BigEndian),
+            Err(Error::TooManyElements { max: 2, actual: 3 })
+        ));
+        let bytes: &[u8] = &[1, 2, 3];
+        assert!(matches!(
+            <FixedList<u8, 2> as TaggedRead<_>>::read(
+                &mut BitReader::endian(bytes, BigEndian),
+                ByteOrder::BigEndian,
+                &mut (),
+                3usize
+            ),
+            Err(Error::TooManyElements { max: 2, actual: 3 })
+        ));
+    }
+
+    #[test]
+    fn fixed_size() {
+        assert_eq!(FixedList::<u16, 3>::SIZE_BITS, 48);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    BitFieldRead, BitFieldWrite, BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result,
+};
+
+/// A fixed-point number stored in the integer `I`, of which the lowest
+/// `FRAC_BITS` bits are the fractional part. The value is encoded exactly as
+/// its raw integer, so it can also be used as a bit field.
+///
+/// `FRAC_BITS` should be at most the number of bits in `I`.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, FixedPoint, ProtocolNoCtx, ProtocolRead, ProtocolWrite, Q8_8};
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// struct Reading {
+///     temperature: Q8_8,
+///     #[protocol(bits = 12)]
+///     offset: FixedPoint<i16, 8>,
+///     #[protocol(bits = 4)]
+///     channel: u8,
+/// }
+///
+/// let reading = Reading {
+///     temperature: Q8_8::from_f32(21.5),
+///     offset: FixedPoint::<i16, 8>::from_f32(-1.25),
+///     channel: 3,
+/// };
+/// let bytes = reading.bytes(ByteOrder::BigEndian).unwrap();
+/// assert_eq!(bytes, [0x15, 0x80, 0xec, 0x03]);
+/// assert_eq!(Reading::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(), reading);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedPoint<I, const FRAC_BITS: u32>(pub I);
+
+/// Signed Q8.8 fixed-point: 8 integer bits, including the sign, and 8
+/// fractional bits.
+pub type Q8_8 = FixedPoint<i16, 8>;
+
+/// Signed Q16.16 fixed-point: 16 integer bits, including the sign, and 16
+/// fractional bits.
+pub type Q16_16 = FixedPoint<i32, 16>;
+
+fn scale(frac_bits: u32) -> f64 {
+    #[allow(clippy::cast_possible_wrap)]
+    2f64.powi(frac_bits as i32)
+}
+
+macro_rules! impl_fixed_point {
+    ($($ty:ty),*) => {
+        $(
+            impl<const FRAC_BITS: u32> FixedPoint<$ty, FRAC_BITS> {
+                /// Wraps a raw integer.
+                #[must_use]
+                pub const fn from_raw(raw: $ty) -> Self {
+                    Self(raw)
+                }
+
+                /// The raw integer.
+                #[must_use]
+                pub const fn raw(self) -> $ty {
+                    self.0
+                }
+
+                /// Converts to the nearest `f64`. This is exact unless the raw
+                /// integer needs more than 53 significant bits.
+                #[must_use]
+                #[allow(clippy::cast_lossless, clippy::cast_precision_loss)]
+                pub fn to_f64(self) -> f64 {
+                    self.0 as f64 / scale(FRAC_BITS)
+                }
+
+                /// Converts to the nearest `f32`. This is exact unless the raw
+                /// integer needs more than 24 significant bits.
+                #[must_use]
+                #[allow(clippy::cast_possible_truncation)]
+                pub fn to_f32(self) -> f32 {
+                    self.to_f64() as f32
+                }
+
+                /// Converts from an `f64`, rounding to the nearest
+                /// representable value with ties away from zero. Values out of
+                /// range saturate to the minimum or maximum, and NaN becomes 0.
+                #[must_use]
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                )]
+                pub fn from_f64(value: f64) -> Self {
+                    Self((value * scale(FRAC_BITS)).round() as $ty)
+                }
+
+                /// Converts from an `f32`, with the rounding and saturation of
+                /// [`Self::from_f64`].
+                #[must_use]
+                pub fn from_f32(value: f32) -> Self {
+                    Self::from_f64(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_point!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+impl<Ctx, I, const FRAC_BITS: u32> ProtocolRead<Ctx> for FixedPoint<I, FRAC_BITS>
+where
+    I: ProtocolRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        Ok(Self(I::read(read, byte_order, ctx)?))
+    }
+}
+
+impl<Ctx, I, const FRAC_BITS: u32> ProtocolWrite<Ctx> for FixedPoint<I, FRAC_BITS>
+where
+    I: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.0.write(write, byte_order, ctx)
+    }
+}
+
+impl<Ctx, I, const FRAC_BITS: u32> BitFieldRead<Ctx> for FixedPoint<I, FRAC_BITS>
+where
+    I: BitFieldRead<Ctx>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        bits: u32,
+    ) -> Result<Self> {
+        Ok(Self(I::read(read, byte_order, ctx, bits)?))
+    }
+}
+
+impl<Ctx, I, const FRAC_BITS: u32> BitFieldWrite<Ctx> for FixedPoint<I, FRAC_BITS>
+where
+    I: BitFieldWrite<Ctx>,
+{
+    fn write(
+        &self,
+        write: &mut dyn BitWrite,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        bits: u32,
+    ) -> Result<()> {
+        self.0.write(write, byte_order, ctx, bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+
+    use super::*;
+
+    #[test]
+    fn q8_8_round_trips() {
+        let value = Q8_8::from_f32(-2.75);
+        assert_eq!(value.raw(), -704);
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, (-704i16).to_be_bytes());
+        let read = Q8_8::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+        assert_eq!(read, value);
+        assert!((read.to_f32() - -2.75).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn q16_16_round_trips() {
+        let value = Q16_16::from_f64(1234.5678);
+        let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+        let read = Q16_16::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(read, value);
+        assert!((read.to_f64() - 1234.5678).abs() <= 0.5 / 65536.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn boundaries_are_exact() {
+        assert_eq!(Q8_8::from_raw(i16::MAX).to_f64(), 127.0 + 255.0 / 256.0);
+        assert_eq!(Q8_8::from_raw(i16::MIN).to_f64(), -128.0);
+        assert_eq!(Q8_8::from_raw(1).to_f64(), 1.0 / 256.0);
+        assert_eq!(Q8_8::from_f64(127.0 + 255.0 / 256.0).raw(), i16::MAX);
+        assert_eq!(Q8_8::from_f64(-128.0).raw(), i16::MIN);
+        assert_eq!(FixedPoint::<u8, 4>::from_raw(u8::MAX).to_f32(), 15.9375);
+    }
+
+    #[test]
+    fn rounds_to_nearest() {
+        assert_eq!(Q8_8::from_f64(1.0 / 512.0).raw(), 1);
+        assert_eq!(Q8_8::from_f64(-1.0 / 512.0).raw(), -1);
+        assert_eq!(Q8_8::from_f64(1.0 / 1024.0).raw(), 0);
+    }
+
+    #[test]
+    fn saturates_out_of_range() {
+        assert_eq!(Q8_8::from_f64(1000.0).raw(), i16::MAX);
+        assert_eq!(Q8_8::from_f64(-1000.0).raw(), i16::MIN);
+        assert_eq!(FixedPoint::<u16, 8>::from_f64(-1.0).raw(), 0);
+        assert_eq!(Q8_8::from_f64(f64::INFINITY).raw(), i16::MAX);
+        assert_eq!(Q8_8::from_f64(f64::NAN).raw(), 0);
+    }
+
+    #[test]
+    fn q4_8_bit_field() {
+        let value = FixedPoint::<i16, 8>::from_f32(-1.25);
+        let mut data = Vec::new();
+        BitFieldWrite::write(
+            &value,
+            &mut bitstream_io::BitWriter::endian(&mut data, bitstream_io::BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            16,
+        )
+        .unwrap();
+        assert_eq!(data, [0xfe, 0xc0]);
+
+        let read: FixedPoint<i16, 8> = BitFieldRead::read(
+            &mut bitstream_io::BitReader::endian([0xec, 0x00].as_slice(), bitstream_io::BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            12,
+        )
+        .unwrap();
+        assert_eq!(read, value);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result};
+use half::f16;
+
+impl<Ctx> ProtocolRead<Ctx> for f16 {
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, _: &mut Ctx) -> Result<Self> {
+        Ok(Self::from_bits(byte_order.read_u16(read)?))
+    }
+}
+
+impl<Ctx> ProtocolWrite<Ctx> for f16 {
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, _: &mut Ctx) -> Result<()> {
+        byte_order.write_u16(self.to_bits(), write)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+
+    use super::*;
+
+    #[test]
+    fn can_read_f16() {
+        assert_eq!(
+            f16::from_bytes(&[0x3c, 0x00], ByteOrder::BigEndian).unwrap(),
+            f16::ONE
+        );
+        assert_eq!(
+            f16::from_bytes(&[0x00, 0x3c], ByteOrder::LittleEndian).unwrap(),
+            f16::ONE
+        );
+    }
+
+    #[test]
+    fn can_write_f16() {
+        assert_eq!(
+            f16::from_f32(-2.0).bytes(ByteOrder::BigEndian).unwrap(),
+            &[0xc0, 0x00]
+        );
+        assert_eq!(
+            f16::from_f32(-2.0).bytes(ByteOrder::LittleEndian).unwrap(),
+            &[0x00, 0xc0]
+        );
+    }
+
+    #[test]
+    fn special_values_round_trip() {
+        for value in [
+            f16::INFINITY,
+            f16::NEG_INFINITY,
+            f16::MIN_POSITIVE_SUBNORMAL,
+            f16::NEG_ZERO,
+            f16::MAX,
+        ] {
+            let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+            let read_back = f16::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap();
+            assert_eq!(read_back.to_bits(), value.to_bits());
+        }
+    }
+
+    #[test]
+    fn nan_round_trips() {
+        let nan = f16::from_bits(0x7e01);
+        let bytes = nan.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, &[0x7e, 0x01]);
+        let read_back = f16::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+        assert!(read_back.is_nan());
+        assert_eq!(read_back.to_bits(), 0x7e01);
+    }
+}
@@ -0,0 +1,64 @@
+// generic-array 0.14 deprecates itself in favour of 1.x, which requires a
+// newer Rust than this crate supports. Crypto crates still use 0.14.
+#![allow(deprecated)]
+
+use crate::{util, BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite, Result};
+use generic_array::{ArrayLength, GenericArray};
+
+/// Encoded as its `N` elements, without a length prefix.
+impl<Ctx, T, N> ProtocolRead<Ctx> for GenericArray<T, N>
+where
+    T: ProtocolRead<Ctx>,
+    N: ArrayLength<T>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let elements = util::read_items(N::USIZE, read, byte_order, ctx)?;
+        let actual = elements.len();
+        Self::from_exact_iter(elements).ok_or(Error::CountMismatch {
+            expected: N::USIZE,
+            actual,
+        })
+    }
+}
+
+impl<Ctx, T, N> ProtocolWrite<Ctx> for GenericArray<T, N>
+where
+    T: ProtocolWrite<Ctx>,
+    N: ArrayLength<T>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        util::write_items(self.iter(), write, byte_order, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+    use generic_array::typenum::{U2, U32};
+
+    use super::*;
+
+    #[test]
+    fn hash_round_trips() {
+        let bytes: Vec<u8> = (0..32).collect();
+        let hash = GenericArray::<u8, U32>::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+        assert_eq!(hash.as_slice(), bytes.as_slice());
+        assert_eq!(hash.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+    }
+
+    #[test]
+    fn elements_use_byte_order() {
+        let value = GenericArray::<u16, U2>::from([0x0102, 0x0304]);
+        let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+        assert_eq!(bytes, [0x02, 0x01, 0x04, 0x03]);
+        assert_eq!(
+            GenericArray::<u16, U2>::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn short_input_fails() {
+        assert!(GenericArray::<u8, U32>::from_bytes(&[0; 31], ByteOrder::BigEndian).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use bitstream_io::{BigEndian, Endianness, LittleEndian};
+
+use crate::{
+    bit_read::ChunkedReader, BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite,
+    Result, TaggedRead, UntaggedWrite,
+};
+use std::io;
+
+/// A value occupying a number of bytes given by its tag. The value is read
+/// from exactly that many bytes, so that fields reading until eof, such as
+/// those with `#[protocol(remaining)]`, stop at its end.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LengthDelimited<T>(pub T);
+
+impl<Tag, Ctx, T> TaggedRead<Tag, Ctx> for LengthDelimited<T>
+where
+    T: ProtocolRead<Ctx>,
+    Tag: TryInto<usize>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<Self> {
+        let bytes = read.read_to_vec(tag.try_into().map_err(|_| Error::TagConvert)?)?;
+        Ok(Self(T::from_bytes_ctx(&bytes, byte_order, ctx)?))
+    }
+}
+
+impl<Ctx, T> UntaggedWrite<Ctx> for LengthDelimited<T>
+where
+    T: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.0.write(write, byte_order, ctx)
+    }
+}
+
+/// A collection whose tag is its length in bytes, rather than its number of
+/// elements. Elements are read until the bytes are used up, and an element
+/// extending past them is an error.
+///
+/// ```
+/// # use bin_proto::{ByteCounted, ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+/// # use std::collections::BTreeMap;
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// struct Attributes {
+///     #[protocol(write_value = "(self.entries.0.len() * 3) as u8")]
+///     len: u8,
+///     #[protocol(tag = "len")]
+///     entries: ByteCounted<BTreeMap<u8, u16>>,
+/// }
+///
+/// let attributes = Attributes::from_bytes(&[6, 1, 0, 2, 3, 0, 4], ByteOrder::BigEndian).unwrap();
+/// assert_eq!(attributes.entries.0, BTreeMap::from([(1, 2), (3, 4)]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteCounted<T>(pub T);
+
+impl<Tag, Ctx, T> TaggedRead<Tag, Ctx> for ByteCounted<T>
+where
+    T: IntoIterator + FromIterator<<T as IntoIterator>::Item>,
+    T::Item: ProtocolRead<Ctx>,
+    Tag: TryInto<usize>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<Self> {
+        let bytes = read.read_to_vec(tag.try_into().map_err(|_| Error::TagConvert)?)?;
+        let items = match byte_order {
+            ByteOrder::LittleEndian => read_all(&bytes, LittleEndian, byte_order, ctx)?,
+            ByteOrder::BigEndian => read_all(&bytes, BigEndian, byte_order, ctx)?,
+        };
+        Ok(Self(items.into_iter().collect()))
+    }
+}
+
+impl<Ctx, T> UntaggedWrite<Ctx> for ByteCounted<T>
+where
+    T: UntaggedWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.0.write(write, byte_order, ctx)
+    }
+}
+
+/// Reads items until `bytes` is used up.
+fn read_all<Ctx, T, E>(
+    bytes: &[u8],
+    endianness: E,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+) -> Result<Vec<T>>
+where
+    T: ProtocolRead<Ctx>,
+    E: Endianness,
+{
+    let len = bytes.len() as u64 * 8;
+    let mut reader = ChunkedReader::new(io::Cursor::new(bytes), endianness);
+    let mut items = Vec::new();
+    let mut position = 0;
+    while position < len {
+        match T::read(&mut reader, byte_order, ctx) {
+            Ok(item) => items.push(item),
+            Err(Error::IO(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "element extends past its length prefix",
+                )
+                .into());
+            }
+            Err(e) => return Err(e),
+        }
+        let next = reader.inner.position_in_bits()?;
+        if next == position {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "zero-sized elements cannot be counted in bytes",
+            )
+            .into());
+        }
+        position = next;
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use bitstream_io::{BigEndian, BitReader};
+
+    use super::*;
+
+    #[test]
+    fn reads_only_tagged_length() {
+        let mut reader = BitReader::endian([0x12, 0x34, 0x56].as_slice(), BigEndian);
+        assert_eq!(
+            <LengthDelimited<u8> as TaggedRead<_, _>>::read(
+                &mut reader,
+                ByteOrder::BigEndian,
+                &mut (),
+                2usize
+            )
+            .unwrap(),
+            LengthDelimited(0x12)
+        );
+        assert_eq!(BitRead::read_u8(&mut reader).unwrap(), 0x56);
+    }
+}
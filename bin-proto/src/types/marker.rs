@@ -1,5 +1,9 @@
 use crate::{BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result};
-use std::marker::{PhantomData, PhantomPinned};
+use std::{
+    convert::Infallible,
+    io,
+    marker::{PhantomData, PhantomPinned},
+};
 
 impl<Ctx, T> ProtocolRead<Ctx> for PhantomData<T> {
     fn read(_: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx) -> Result<Self> {
@@ -25,7 +29,27 @@ impl<Ctx> ProtocolWrite<Ctx> for PhantomPinned {
     }
 }
 
+/// Always fails to read, as there are no values to read. Lets enums with
+/// uninhabited variants derive `ProtocolRead`.
+impl<Ctx> ProtocolRead<Ctx> for Infallible {
+    fn read(_: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx) -> Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "cannot read an uninhabited type",
+        )
+        .into())
+    }
+}
+
+impl<Ctx> ProtocolWrite<Ctx> for Infallible {
+    fn write(&self, _: &mut dyn BitWrite, _: ByteOrder, _: &mut Ctx) -> Result<()> {
+        match *self {}
+    }
+}
+
 #[cfg(test)]
+// Written before clippy flagged a trailing `assert_eq!` without a semicolon.
+#[allow(clippy::semicolon_if_nothing_returned)]
 mod tests {
     use crate::ProtocolNoCtx;
 
@@ -56,4 +80,12 @@ mod tests {
     fn can_write_phantom_pinned() {
         assert_eq!(PhantomPinned.bytes(ByteOrder::BigEndian).unwrap(), &[])
     }
+
+    #[test]
+    fn cannot_read_infallible() {
+        assert!(matches!(
+            Infallible::from_bytes(&[0x00], ByteOrder::BigEndian),
+            Err(crate::Error::IO(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
 }
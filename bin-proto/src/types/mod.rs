@@ -1,12 +1,54 @@
 //! Utility types
 
 mod array;
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+mod atomic;
+mod bits;
+mod boolean;
+#[cfg(feature = "bytes")]
+mod bytes;
 mod collections;
 mod cstring;
+#[cfg(feature = "time")]
+mod datetime;
+mod duration;
+mod fixed_list;
+mod fixed_point;
+#[cfg(feature = "half")]
+mod float16;
+#[cfg(feature = "generic-array")]
+mod generic_array;
+mod length_delimited;
 mod marker;
+mod nan_boxed;
 mod net;
 mod numerics;
 mod option;
+#[cfg(feature = "ordered-float")]
+mod ordered;
+mod path;
+mod range;
+#[cfg(feature = "smallvec")]
+mod smallvec;
 mod smart_ptr;
 mod string;
 mod tuple;
+#[cfg(feature = "uuid")]
+mod uuid;
+mod varint;
+
+pub use self::bits::Bits;
+pub use self::boolean::{LenientBool, StrictBool};
+pub use self::collections::{KeepFirstMap, StrictMap};
+pub use self::duration::{Micros, Millis, Nanos};
+pub use self::fixed_list::FixedList;
+pub use self::fixed_point::{FixedPoint, Q16_16, Q8_8};
+pub use self::length_delimited::{ByteCounted, LengthDelimited};
+pub use self::nan_boxed::NanBoxed;
+pub use self::option::read_if_remaining;
+pub use self::range::OrderedRange;
+pub use self::string::LossyString;
+#[cfg(feature = "uuid")]
+pub use self::uuid::Guid;
+pub use self::varint::{VarInt, ZigZag};
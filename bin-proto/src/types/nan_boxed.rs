@@ -0,0 +1,131 @@
+use crate::{BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result};
+use std::io;
+
+/// An optional float that encodes `None` as the NaN bit pattern `BITS`, as
+/// used for "not available" readings. All other bit patterns, including other
+/// NaNs, are read as `Some` and written back unchanged.
+///
+/// For `f32`, `BITS` must fit in 32 bits.
+///
+/// ```
+/// # use bin_proto::{ByteOrder, NanBoxed, ProtocolNoCtx};
+/// type Reading = NanBoxed<f32, 0x7fa0_0000>;
+///
+/// assert_eq!(
+///     Reading::from_bytes(&[0x7f, 0xa0, 0x00, 0x00], ByteOrder::BigEndian).unwrap(),
+///     NanBoxed(None)
+/// );
+/// assert_eq!(
+///     NanBoxed::<f32, 0x7fa0_0000>(Some(1.0)).bytes(ByteOrder::BigEndian).unwrap(),
+///     [0x3f, 0x80, 0x00, 0x00]
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NanBoxed<T, const BITS: u64>(pub Option<T>);
+
+impl<T, const BITS: u64> From<Option<T>> for NanBoxed<T, BITS> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, const BITS: u64> From<NanBoxed<T, BITS>> for Option<T> {
+    fn from(value: NanBoxed<T, BITS>) -> Self {
+        value.0
+    }
+}
+
+fn reserved_bits() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "value has the bit pattern reserved for None",
+    )
+}
+
+macro_rules! impl_nan_boxed {
+    ($ty:ty => $bits:ty) => {
+        impl<Ctx, const BITS: u64> ProtocolRead<Ctx> for NanBoxed<$ty, BITS> {
+            fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+                let bits = <$bits>::read(read, byte_order, ctx)?;
+                if u64::from(bits) == BITS {
+                    Ok(Self(None))
+                } else {
+                    Ok(Self(Some(<$ty>::from_bits(bits))))
+                }
+            }
+        }
+
+        impl<Ctx, const BITS: u64> ProtocolWrite<Ctx> for NanBoxed<$ty, BITS> {
+            fn write(
+                &self,
+                write: &mut dyn BitWrite,
+                byte_order: ByteOrder,
+                ctx: &mut Ctx,
+            ) -> Result<()> {
+                let bits = match self.0 {
+                    Some(value) if u64::from(value.to_bits()) == BITS => {
+                        return Err(reserved_bits().into())
+                    }
+                    Some(value) => value.to_bits(),
+                    None => <$bits>::try_from(BITS).ok().ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "None bit pattern does not fit in the float",
+                        )
+                    })?,
+                };
+                bits.write(write, byte_order, ctx)
+            }
+        }
+    };
+}
+
+impl_nan_boxed!(f32 => u32);
+impl_nan_boxed!(f64 => u64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, ProtocolNoCtx};
+
+    use super::*;
+
+    type Reading = NanBoxed<f64, 0x7ff4_0000_0000_0001>;
+
+    #[test]
+    fn none_round_trips() {
+        let bytes = Reading::default().bytes(ByteOrder::LittleEndian).unwrap();
+        assert_eq!(bytes, 0x7ff4_0000_0000_0001u64.to_le_bytes());
+        assert_eq!(
+            Reading::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+            NanBoxed(None)
+        );
+    }
+
+    #[test]
+    fn other_nans_keep_their_bits() {
+        let bits = 0x7ff4_0000_0000_0002u64;
+        let read = Reading::from_bytes(&bits.to_be_bytes(), ByteOrder::BigEndian).unwrap();
+        assert_eq!(read.0.map(f64::to_bits), Some(bits));
+        assert_eq!(
+            read.bytes(ByteOrder::BigEndian).unwrap(),
+            bits.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn rejects_value_with_reserved_bits() {
+        let value = Reading::from(Some(f64::from_bits(0x7ff4_0000_0000_0001)));
+        assert!(matches!(
+            value.bytes(ByteOrder::BigEndian),
+            Err(Error::IO(e)) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn f32_pattern_must_fit() {
+        assert!(matches!(
+            NanBoxed::<f32, 0x1_0000_0000>(None).bytes(ByteOrder::BigEndian),
+            Err(Error::IO(e)) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
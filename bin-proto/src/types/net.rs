@@ -33,6 +33,8 @@ impl<Ctx> ProtocolWrite<Ctx> for Ipv6Addr {
 }
 
 #[cfg(test)]
+// Written before clippy flagged a trailing `assert_eq!` without a semicolon.
+#[allow(clippy::semicolon_if_nothing_returned)]
 mod tests {
     use bitstream_io::{BigEndian, BitReader, BitWriter};
 
@@ -17,6 +17,10 @@ impl<Ctx> BitFieldWrite<Ctx> for bool {
         write.write_u8_bf(bits, (*self).into())?;
         Ok(())
     }
+
+    fn validate_bits(&self, bits: u32) -> Result<()> {
+        BitFieldWrite::<Ctx>::validate_bits(&u8::from(*self), bits)
+    }
 }
 
 impl<Ctx> ProtocolRead<Ctx> for bool {
@@ -71,7 +75,7 @@ macro_rules! impl_protocol_for_numeric {
                 byte_order: $crate::ByteOrder,
                 _: &mut Ctx,
             ) -> $crate::Result<Self> {
-                Ok(byte_order.$read_fn(read)?.try_into().unwrap())
+                Ok(byte_order.$read_fn(read)?.try_into()?)
             }
         }
 
@@ -82,7 +86,7 @@ macro_rules! impl_protocol_for_numeric {
                 byte_order: $crate::ByteOrder,
                 _: &mut Ctx,
             ) -> $crate::Result<()> {
-                byte_order.$write_fn((*self).try_into().unwrap(), write)?;
+                byte_order.$write_fn((*self).try_into()?, write)?;
                 Ok(())
             }
         }
@@ -98,7 +102,7 @@ macro_rules! impl_bitfield_for_numeric {
                 _: &mut Ctx,
                 bits: u32,
             ) -> $crate::Result<Self> {
-                Ok($crate::BitRead::$read_fn(read, bits)?.try_into().unwrap())
+                Ok($crate::BitRead::$read_fn(read, bits)?.try_into()?)
             }
         }
 
@@ -110,9 +114,26 @@ macro_rules! impl_bitfield_for_numeric {
                 _: &mut Ctx,
                 bits: u32,
             ) -> $crate::Result<()> {
-                $crate::BitWrite::$write_fn(write, bits, (*self).try_into().unwrap())?;
+                $crate::BitWrite::$write_fn(write, bits, (*self).try_into()?)?;
                 Ok(())
             }
+
+            fn validate_bits(&self, bits: u32) -> $crate::Result<()> {
+                let value: i128 = (*self).try_into()?;
+                let fits = if bits >= <$ty>::BITS {
+                    bits == <$ty>::BITS
+                } else if <$ty>::MIN == 0 {
+                    value >> bits == 0
+                } else {
+                    // The bits above the sign bit must all equal it.
+                    bits > 0 && matches!(value >> (bits - 1), 0 | -1)
+                };
+                if fits {
+                    Ok(())
+                } else {
+                    Err($crate::bit_field::excessive_value())
+                }
+            }
         }
     };
 }
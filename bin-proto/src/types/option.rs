@@ -1,7 +1,10 @@
+use bitstream_io::{BigEndian, BitReader, LittleEndian};
+
 use crate::{
-    BitRead, BitWrite, ByteOrder, Error, TaggedRead, UntaggedWrite, ProtocolRead,
-    ProtocolWrite, Result,
+    BitFieldRead, BitFieldWrite, BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite,
+    Result, TaggedRead, UntaggedWrite,
 };
+use std::io::{self, Read};
 
 impl<Tag, Ctx, T> TaggedRead<Tag, Ctx> for Option<T>
 where
@@ -33,9 +36,110 @@ where
         }
         Ok(())
     }
+
+    fn tag_len(&self) -> Option<usize> {
+        Some(self.is_some().into())
+    }
+}
+
+/// A presence flag of `bits` bits, directly followed by the value if the flag
+/// is nonzero.
+impl<Ctx, T> BitFieldRead<Ctx> for Option<T>
+where
+    T: ProtocolRead<Ctx>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        bits: u32,
+    ) -> Result<Self> {
+        let present = <bool as BitFieldRead<Ctx>>::read(read, byte_order, ctx, bits)?;
+        <Self as TaggedRead<_, _>>::read(read, byte_order, ctx, present)
+    }
+}
+
+impl<Ctx, T> BitFieldWrite<Ctx> for Option<T>
+where
+    T: ProtocolWrite<Ctx>,
+{
+    fn write(
+        &self,
+        write: &mut dyn BitWrite,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        bits: u32,
+    ) -> Result<()> {
+        BitFieldWrite::write(&self.is_some(), write, byte_order, ctx, bits)?;
+        UntaggedWrite::write(self, write, byte_order, ctx)
+    }
+
+    fn validate_bits(&self, bits: u32) -> Result<()> {
+        BitFieldWrite::<Ctx>::validate_bits(&self.is_some(), bits)
+    }
+}
+
+/// Reads a value if the stream has not reached its end. Used by derived
+/// implementations for fields with `#[protocol(present_if_remaining)]`.
+///
+/// The end of the stream is detected by reading a single byte, which is then
+/// replayed ahead of the rest of the stream when reading the value.
+#[doc(hidden)]
+pub fn read_if_remaining<Ctx, T>(
+    read: &mut dyn BitRead,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+) -> Result<Option<T>>
+where
+    T: ProtocolRead<Ctx>,
+{
+    if !read.byte_aligned() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "present_if_remaining field is not byte-aligned",
+        )
+        .into());
+    }
+    let first = match read.read_u8() {
+        Ok(first) => first,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let first = [first];
+    let replayed = first.as_slice().chain(Remaining(read));
+    let value = match byte_order {
+        ByteOrder::LittleEndian => T::read(
+            &mut BitReader::endian(replayed, LittleEndian),
+            byte_order,
+            ctx,
+        )?,
+        ByteOrder::BigEndian => {
+            T::read(&mut BitReader::endian(replayed, BigEndian), byte_order, ctx)?
+        }
+    };
+    Ok(Some(value))
+}
+
+/// Exposes the byte-aligned remainder of a `BitRead` as an `io::Read`.
+struct Remaining<'a>(&'a mut dyn BitRead);
+
+impl Read for Remaining<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        for (i, byte) in buf.iter_mut().enumerate() {
+            match self.0.read_u8() {
+                Ok(value) => *byte = value,
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(i),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(buf.len())
+    }
 }
 
 #[cfg(test)]
+// Written before clippy flagged a trailing `assert_eq!` without a semicolon.
+#[allow(clippy::semicolon_if_nothing_returned)]
 mod tests {
     use bitstream_io::{BigEndian, BitReader, BitWriter};
 
@@ -94,4 +198,25 @@ mod tests {
         .unwrap();
         assert_eq!(data, vec![])
     }
+
+    #[test]
+    fn bit_flag_precedes_value() {
+        let mut data: Vec<u8> = Vec::new();
+        let mut writer = BitWriter::endian(&mut data, BigEndian);
+        BitFieldWrite::write(&Some(0x7fu8), &mut writer, ByteOrder::BigEndian, &mut (), 1).unwrap();
+        BitFieldWrite::write(&None::<u8>, &mut writer, ByteOrder::BigEndian, &mut (), 7).unwrap();
+        assert_eq!(data, vec![0xbf, 0x80]);
+
+        let mut reader = BitReader::endian(data.as_slice(), BigEndian);
+        assert_eq!(
+            <Option<u8> as BitFieldRead>::read(&mut reader, ByteOrder::BigEndian, &mut (), 1)
+                .unwrap(),
+            Some(0x7f)
+        );
+        assert_eq!(
+            <Option<u8> as BitFieldRead>::read(&mut reader, ByteOrder::BigEndian, &mut (), 7)
+                .unwrap(),
+            None
+        );
+    }
 }
@@ -0,0 +1,93 @@
+use crate::{BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite, Result};
+use ordered_float::{NotNan, OrderedFloat};
+
+impl<Ctx, T> ProtocolRead<Ctx> for OrderedFloat<T>
+where
+    T: ProtocolRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        Ok(Self(T::read(read, byte_order, ctx)?))
+    }
+}
+
+impl<Ctx, T> ProtocolWrite<Ctx> for OrderedFloat<T>
+where
+    T: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.0.write(write, byte_order, ctx)
+    }
+}
+
+macro_rules! impl_protocol_for_not_nan {
+    ($ty:ty) => {
+        impl<Ctx> ProtocolRead<Ctx> for NotNan<$ty> {
+            fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+                NotNan::new(<$ty>::read(read, byte_order, ctx)?)
+                    .map_err(|e| Error::Other(Box::new(e)))
+            }
+        }
+
+        impl<Ctx> ProtocolWrite<Ctx> for NotNan<$ty> {
+            fn write(
+                &self,
+                write: &mut dyn BitWrite,
+                byte_order: ByteOrder,
+                ctx: &mut Ctx,
+            ) -> Result<()> {
+                self.into_inner().write(write, byte_order, ctx)
+            }
+        }
+    };
+}
+
+impl_protocol_for_not_nan!(f32);
+impl_protocol_for_not_nan!(f64);
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+
+    use super::*;
+
+    #[test]
+    fn ordered_float_round_trips() {
+        let value = OrderedFloat(1.5f32);
+        let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+        assert_eq!(bytes, [0x3f, 0xc0, 0x00, 0x00]);
+        assert_eq!(
+            OrderedFloat::<f32>::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn ordered_float_preserves_nan() {
+        let bytes = f64::NAN.bytes(ByteOrder::LittleEndian).unwrap();
+        assert!(
+            OrderedFloat::<f64>::from_bytes(&bytes, ByteOrder::LittleEndian)
+                .unwrap()
+                .is_nan()
+        );
+    }
+
+    #[test]
+    fn not_nan_round_trips() {
+        let value = NotNan::new(-2.25f64).unwrap();
+        let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+        assert_eq!(bytes, (-2.25f64).to_le_bytes());
+        assert_eq!(
+            NotNan::<f64>::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn not_nan_rejects_nan() {
+        let bytes = f32::NAN.bytes(ByteOrder::BigEndian).unwrap();
+        assert!(matches!(
+            NotNan::<f32>::from_bytes(&bytes, ByteOrder::BigEndian),
+            Err(Error::Other(_))
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    util, BitRead, BitWrite, ByteOrder, FlexibleArrayMemberRead, Result, TaggedRead, UntaggedWrite,
+};
+use std::{io, path::PathBuf};
+
+/// Encoded as its UTF-8 representation, like `String`.
+impl<Tag, Ctx> TaggedRead<Tag, Ctx> for PathBuf
+where
+    Tag: TryInto<usize>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<Self> {
+        let path: String = TaggedRead::read(read, byte_order, ctx, tag)?;
+        Ok(path.into())
+    }
+}
+
+/// Fails with `io::ErrorKind::InvalidInput` if the path is not valid UTF-8.
+impl<Ctx> UntaggedWrite<Ctx> for PathBuf {
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        let path = self.to_str().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "path is not valid UTF-8")
+        })?;
+        util::write_items::<Ctx, u8>(path.as_bytes(), write, byte_order, ctx)
+    }
+}
+
+impl<Ctx> FlexibleArrayMemberRead<Ctx> for PathBuf {
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let path: String = FlexibleArrayMemberRead::read(read, byte_order, ctx)?;
+        Ok(path.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitstream_io::{BigEndian, BitReader};
+
+    use crate::Error;
+
+    use super::*;
+
+    fn round_trip(path: &str) {
+        let mut bytes = Vec::new();
+        UntaggedWrite::write(
+            &PathBuf::from(path),
+            &mut bitstream_io::BitWriter::endian(&mut bytes, BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        )
+        .unwrap();
+        assert_eq!(bytes, path.as_bytes());
+
+        let read: PathBuf = TaggedRead::read(
+            &mut BitReader::endian(bytes.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            bytes.len(),
+        )
+        .unwrap();
+        assert_eq!(read, PathBuf::from(path));
+    }
+
+    #[test]
+    fn unix_path_round_trips() {
+        round_trip("/etc/ssh/sshd_config");
+    }
+
+    #[test]
+    fn windows_path_round_trips() {
+        round_trip("C:\\Users\\admin\\config.ini");
+    }
+
+    #[test]
+    fn flexible_array_member() {
+        let read: PathBuf = FlexibleArrayMemberRead::read(
+            &mut BitReader::endian(b"relative/path".as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        )
+        .unwrap();
+        assert_eq!(read, PathBuf::from("relative/path"));
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        let bytes = [b'/', 0xff];
+        let result: Result<PathBuf> = TaggedRead::read(
+            &mut BitReader::endian(bytes.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            bytes.len(),
+        );
+        assert!(matches!(result, Err(Error::FromUtf8(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_rejects_non_utf8_path() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let path = PathBuf::from(OsStr::from_bytes(&[b'/', 0xff]));
+        let result = UntaggedWrite::write(
+            &path,
+            &mut bitstream_io::BitWriter::endian(Vec::new(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        );
+        assert!(matches!(result, Err(Error::IO(e)) if e.kind() == io::ErrorKind::InvalidInput));
+    }
+}
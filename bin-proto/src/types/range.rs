@@ -0,0 +1,198 @@
+use crate::{BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite, Result};
+use std::ops::{Bound, Range, RangeInclusive};
+
+impl<Ctx, T> ProtocolRead<Ctx> for Range<T>
+where
+    T: ProtocolRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let start = T::read(read, byte_order, ctx)?;
+        let end = T::read(read, byte_order, ctx)?;
+        Ok(start..end)
+    }
+}
+
+impl<Ctx, T> ProtocolWrite<Ctx> for Range<T>
+where
+    T: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.start.write(write, byte_order, ctx)?;
+        self.end.write(write, byte_order, ctx)
+    }
+}
+
+impl<Ctx, T> ProtocolRead<Ctx> for RangeInclusive<T>
+where
+    T: ProtocolRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let start = T::read(read, byte_order, ctx)?;
+        let end = T::read(read, byte_order, ctx)?;
+        Ok(start..=end)
+    }
+}
+
+impl<Ctx, T> ProtocolWrite<Ctx> for RangeInclusive<T>
+where
+    T: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.start().write(write, byte_order, ctx)?;
+        self.end().write(write, byte_order, ctx)
+    }
+}
+
+/// A range that fails to read with [`Error::InvertedRange`] if its start is
+/// after its end.
+///
+/// Plain ranges are read as they are, and are empty if inverted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OrderedRange<T>(pub Range<T>);
+
+impl<Ctx, T> ProtocolRead<Ctx> for OrderedRange<T>
+where
+    T: ProtocolRead<Ctx> + Ord,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let range = Range::read(read, byte_order, ctx)?;
+        if range.start > range.end {
+            return Err(Error::InvertedRange);
+        }
+        Ok(Self(range))
+    }
+}
+
+impl<Ctx, T> ProtocolWrite<Ctx> for OrderedRange<T>
+where
+    T: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        self.0.write(write, byte_order, ctx)
+    }
+}
+
+impl<T> From<Range<T>> for OrderedRange<T> {
+    fn from(value: Range<T>) -> Self {
+        Self(value)
+    }
+}
+
+const BOUND_INCLUDED: u8 = 0;
+const BOUND_EXCLUDED: u8 = 1;
+const BOUND_UNBOUNDED: u8 = 2;
+
+impl<Ctx, T> ProtocolRead<Ctx> for Bound<T>
+where
+    T: ProtocolRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        match read.read_u8()? {
+            BOUND_INCLUDED => Ok(Bound::Included(T::read(read, byte_order, ctx)?)),
+            BOUND_EXCLUDED => Ok(Bound::Excluded(T::read(read, byte_order, ctx)?)),
+            BOUND_UNBOUNDED => Ok(Bound::Unbounded),
+            unknown => Err(Error::UnknownEnumDiscriminant {
+                enum_name: "Bound",
+                discriminant: unknown.to_string(),
+                expected: [BOUND_INCLUDED, BOUND_EXCLUDED, BOUND_UNBOUNDED]
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl<Ctx, T> ProtocolWrite<Ctx> for Bound<T>
+where
+    T: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        match self {
+            Bound::Included(value) => {
+                write.write_u8(BOUND_INCLUDED)?;
+                value.write(write, byte_order, ctx)
+            }
+            Bound::Excluded(value) => {
+                write.write_u8(BOUND_EXCLUDED)?;
+                value.write(write, byte_order, ctx)
+            }
+            Bound::Unbounded => Ok(write.write_u8(BOUND_UNBOUNDED)?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+
+    use super::*;
+
+    #[test]
+    fn can_read_range() {
+        assert_eq!(
+            Range::<u16>::from_bytes(&[0, 1, 0, 5], ByteOrder::BigEndian).unwrap(),
+            1..5
+        );
+    }
+
+    #[test]
+    fn can_write_range() {
+        assert_eq!(
+            (1u16..5).bytes(ByteOrder::BigEndian).unwrap(),
+            &[0, 1, 0, 5]
+        );
+    }
+
+    #[test]
+    fn range_inclusive_round_trips() {
+        let range = 3u16..=0x1ff;
+        let bytes = range.bytes(ByteOrder::LittleEndian).unwrap();
+        assert_eq!(bytes, &[3, 0, 0xff, 1]);
+        assert_eq!(
+            RangeInclusive::<u16>::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+            range
+        );
+    }
+
+    #[test]
+    fn ordered_range_reads_ordered() {
+        for bytes in [[0, 1, 0, 5], [0, 5, 0, 5]] {
+            let range = OrderedRange::<u16>::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+            assert_eq!(range.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn ordered_range_rejects_inverted() {
+        assert!(matches!(
+            OrderedRange::<u16>::from_bytes(&[0, 5, 0, 1], ByteOrder::BigEndian),
+            Err(Error::InvertedRange)
+        ));
+        let range = Range::<u16>::from_bytes(&[0, 5, 0, 1], ByteOrder::BigEndian).unwrap();
+        assert_eq!((range.start, range.end), (5, 1));
+    }
+
+    #[test]
+    fn bound_round_trips() {
+        for (bound, bytes) in [
+            (Bound::Included(7u8), vec![0, 7]),
+            (Bound::Excluded(9), vec![1, 9]),
+            (Bound::Unbounded, vec![2]),
+        ] {
+            assert_eq!(bound.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+            assert_eq!(
+                Bound::<u8>::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+                bound
+            );
+        }
+    }
+
+    #[test]
+    fn bound_rejects_unknown_tag() {
+        assert!(matches!(
+            Bound::<u8>::from_bytes(&[3, 0], ByteOrder::BigEndian),
+            Err(Error::UnknownEnumDiscriminant { .. })
+        ));
+    }
+}
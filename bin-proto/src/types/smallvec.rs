@@ -0,0 +1,75 @@
+use crate::{
+    util, BitRead, BitWrite, ByteOrder, Error, FlexibleArrayMemberRead, ProtocolRead,
+    ProtocolWrite, Result, TaggedRead, UntaggedWrite,
+};
+use smallvec::{Array, SmallVec};
+
+impl<Tag, Ctx, A> TaggedRead<Tag, Ctx> for SmallVec<A>
+where
+    A: Array,
+    A::Item: ProtocolRead<Ctx>,
+    Tag: TryInto<usize>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<Self> {
+        let count = tag.try_into().map_err(|_| Error::TagConvert)?;
+        let mut elements = Self::new();
+        for _ in 0..count {
+            elements.push(A::Item::read(read, byte_order, ctx)?);
+        }
+        Ok(elements)
+    }
+}
+
+impl<Ctx, A> UntaggedWrite<Ctx> for SmallVec<A>
+where
+    A: Array,
+    A::Item: ProtocolWrite<Ctx>,
+{
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        util::write_items(self.iter(), write, byte_order, ctx)
+    }
+
+    fn tag_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<Ctx, A> FlexibleArrayMemberRead<Ctx> for SmallVec<A>
+where
+    A: Array,
+    A::Item: ProtocolRead<Ctx>,
+{
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        Ok(util::read_items_to_eof(read, byte_order, ctx)?
+            .into_iter()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitstream_io::{BigEndian, BitReader};
+
+    use super::*;
+
+    test_externally_tagged!(SmallVec<[u8; 4]> => [[1, 2, 3], SmallVec::<[u8; 4]>::from_slice(&[1, 2, 3])]);
+
+    #[test]
+    fn spills_beyond_inline_capacity() {
+        let bytes = [0, 1, 0, 2, 0, 3];
+        let read: SmallVec<[u16; 2]> = TaggedRead::read(
+            &mut BitReader::endian(bytes.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            3usize,
+        )
+        .unwrap();
+        assert!(read.spilled());
+        assert_eq!(read.as_slice(), [1, 2, 3]);
+    }
+}
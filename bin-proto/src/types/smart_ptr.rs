@@ -72,7 +72,62 @@ macro_rules! impl_smart_ptr_type {
 }
 
 mod box_ {
+    use crate::{
+        util, BitRead, BitWrite, ByteOrder, FlexibleArrayMemberRead, ProtocolRead, ProtocolWrite,
+        Result, UntaggedWrite,
+    };
+
     impl_smart_ptr_type!(Box);
+
+    impl<Ctx, T> FlexibleArrayMemberRead<Ctx> for Box<T>
+    where
+        T: FlexibleArrayMemberRead<Ctx>,
+    {
+        fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+            Ok(Box::new(T::read(read, byte_order, ctx)?))
+        }
+    }
+
+    impl<Ctx, T> FlexibleArrayMemberRead<Ctx> for Box<[T]>
+    where
+        T: ProtocolRead<Ctx>,
+    {
+        fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+            Ok(util::read_items_to_eof(read, byte_order, ctx)?.into_boxed_slice())
+        }
+    }
+
+    impl<Ctx, T> UntaggedWrite<Ctx> for Box<T>
+    where
+        T: UntaggedWrite<Ctx>,
+    {
+        fn write(
+            &self,
+            write: &mut dyn BitWrite,
+            byte_order: ByteOrder,
+            ctx: &mut Ctx,
+        ) -> Result<()> {
+            T::write(self, write, byte_order, ctx)
+        }
+    }
+
+    impl<Ctx, T> UntaggedWrite<Ctx> for Box<[T]>
+    where
+        T: ProtocolWrite<Ctx>,
+    {
+        fn write(
+            &self,
+            write: &mut dyn BitWrite,
+            byte_order: ByteOrder,
+            ctx: &mut Ctx,
+        ) -> Result<()> {
+            util::write_items(self.iter(), write, byte_order, ctx)
+        }
+
+        fn tag_len(&self) -> Option<usize> {
+            Some(self.len())
+        }
+    }
 }
 
 mod rc {
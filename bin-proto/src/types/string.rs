@@ -22,6 +22,21 @@ where
 
         Ok(String::from_utf8(bytes)?)
     }
+
+    fn read_replace(
+        &mut self,
+        read: &mut dyn BitRead,
+        _: ByteOrder,
+        _: &mut Ctx,
+        tag: Tag,
+    ) -> Result<()> {
+        let len = tag.try_into().map_err(|_| Error::TagConvert)?;
+        self.clear();
+        let mut bytes = std::mem::take(self).into_bytes();
+        util::read_bytes_into(len, read, &mut bytes)?;
+        *self = String::from_utf8(bytes)?;
+        Ok(())
+    }
 }
 
 impl<Ctx> UntaggedWrite<Ctx> for String {
@@ -29,6 +44,10 @@ impl<Ctx> UntaggedWrite<Ctx> for String {
         let bytes: Vec<u8> = str::bytes(self).collect();
         util::write_items::<Ctx, u8>(&bytes, write, byte_order, ctx)
     }
+
+    fn tag_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
 }
 
 impl<Ctx> FlexibleArrayMemberRead<Ctx> for String {
@@ -38,7 +57,116 @@ impl<Ctx> FlexibleArrayMemberRead<Ctx> for String {
     }
 }
 
+/// A `String` that replaces invalid UTF-8 with `U+FFFD REPLACEMENT CHARACTER`
+/// on read, instead of failing.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LossyString(pub String);
+
+impl From<String> for LossyString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<LossyString> for String {
+    fn from(value: LossyString) -> Self {
+        value.0
+    }
+}
+
+impl std::ops::Deref for LossyString {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.0
+    }
+}
+
+impl<Tag, Ctx> TaggedRead<Tag, Ctx> for LossyString
+where
+    Tag: TryInto<usize>,
+{
+    fn read(
+        read: &mut dyn BitRead,
+        byte_order: ByteOrder,
+        ctx: &mut Ctx,
+        tag: Tag,
+    ) -> Result<Self> {
+        let bytes: Vec<u8> = util::read_items(
+            tag.try_into().map_err(|_| Error::TagConvert)?,
+            read,
+            byte_order,
+            ctx,
+        )?;
+
+        Ok(Self(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
+
+impl<Ctx> UntaggedWrite<Ctx> for LossyString {
+    fn write(&self, write: &mut dyn BitWrite, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<()> {
+        UntaggedWrite::write(&self.0, write, byte_order, ctx)
+    }
+
+    fn tag_len(&self) -> Option<usize> {
+        UntaggedWrite::<Ctx>::tag_len(&self.0)
+    }
+}
+
+impl<Ctx> FlexibleArrayMemberRead<Ctx> for LossyString {
+    fn read(read: &mut dyn BitRead, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Self> {
+        let bytes: Vec<u8> = util::read_items_to_eof(read, byte_order, ctx)?;
+        Ok(Self(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use bitstream_io::{BigEndian, BitReader};
+
+    use super::*;
+
     test_externally_tagged!(String => [[b'a', b'b', b'c', b'd'], String::from("abcd")]);
+
+    const INVALID: [u8; 4] = [b'a', 0xff, 0xfe, b'b'];
+
+    #[test]
+    fn string_rejects_invalid_utf8() {
+        let result: Result<String> = TaggedRead::read(
+            &mut BitReader::endian(INVALID.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            INVALID.len(),
+        );
+        assert!(matches!(result, Err(Error::FromUtf8(_))));
+    }
+
+    #[test]
+    fn lossy_string_replaces_invalid_utf8() {
+        let read_back: LossyString = TaggedRead::read(
+            &mut BitReader::endian(INVALID.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+            INVALID.len(),
+        )
+        .unwrap();
+        assert_eq!(read_back.as_str(), "a\u{fffd}\u{fffd}b");
+    }
+
+    #[test]
+    fn lossy_string_flexible_array_member() {
+        let read_back: LossyString = FlexibleArrayMemberRead::read(
+            &mut BitReader::endian(INVALID.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        )
+        .unwrap();
+        assert_eq!(read_back, LossyString(String::from("a\u{fffd}\u{fffd}b")));
+    }
+
+    mod lossy {
+        use super::*;
+
+        test_externally_tagged!(LossyString => [[b'a', b'b'], LossyString(String::from("ab"))]);
+    }
 }
@@ -43,3 +43,34 @@ impl_protocol_for_tuple!(0 A, 1 B, 2 C);
 impl_protocol_for_tuple!(0 A, 1 B);
 impl_protocol_for_tuple!(0 A);
 impl_protocol_for_tuple!();
+
+#[cfg(test)]
+mod tests {
+    use crate::{ByteOrder, ProtocolNoCtx};
+
+    #[test]
+    fn unit_is_empty() {
+        assert!(().bytes(ByteOrder::BigEndian).unwrap().is_empty());
+        <()>::from_bytes(&[], ByteOrder::BigEndian).unwrap();
+    }
+
+    #[test]
+    fn unit_reads_nothing() {
+        assert_eq!(
+            <((), u8, ())>::from_bytes(&[0x01], ByteOrder::BigEndian).unwrap(),
+            ((), 1, ())
+        );
+    }
+
+    #[test]
+    fn one_tuple_matches_element() {
+        assert_eq!(
+            (0x0102u16,).bytes(ByteOrder::BigEndian).unwrap(),
+            0x0102u16.bytes(ByteOrder::BigEndian).unwrap()
+        );
+        assert_eq!(
+            <(u16,)>::from_bytes(&[0x01, 0x02], ByteOrder::BigEndian).unwrap(),
+            (0x0102,)
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! `Uuid` is read and written in the RFC 4122 layout, as its 16 bytes in order
+//! regardless of the byte order. [`Guid`] uses the Microsoft layout instead.
+
+use crate::{BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result};
+use uuid::Uuid;
+
+impl<Ctx> ProtocolRead<Ctx> for Uuid {
+    fn read(read: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx) -> Result<Self> {
+        let mut bytes = [0; 16];
+        read.read_bytes(&mut bytes)?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+impl<Ctx> ProtocolWrite<Ctx> for Uuid {
+    fn write(&self, write: &mut dyn BitWrite, _: ByteOrder, _: &mut Ctx) -> Result<()> {
+        write.write_bytes(self.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// A `Uuid` in the mixed-endian layout of a Microsoft `GUID`, as used by
+/// Windows-originated protocols: the first three fields are little-endian,
+/// and the last eight bytes are in order. The byte order does not affect it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guid(pub Uuid);
+
+impl From<Uuid> for Guid {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Guid> for Uuid {
+    fn from(value: Guid) -> Self {
+        value.0
+    }
+}
+
+impl<Ctx> ProtocolRead<Ctx> for Guid {
+    fn read(read: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx) -> Result<Self> {
+        let mut bytes = [0; 16];
+        read.read_bytes(&mut bytes)?;
+        Ok(Self(Uuid::from_bytes_le(bytes)))
+    }
+}
+
+impl<Ctx> ProtocolWrite<Ctx> for Guid {
+    fn write(&self, write: &mut dyn BitWrite, _: ByteOrder, _: &mut Ctx) -> Result<()> {
+        write.write_bytes(&self.0.to_bytes_le())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolNoCtx;
+
+    use super::*;
+
+    const UUID: Uuid = Uuid::from_u128(0x0011_2233_4455_6677_8899_aabb_ccdd_eeff);
+
+    #[test]
+    fn uuid_is_rfc_4122() {
+        let rfc_4122 = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            assert_eq!(UUID.bytes(byte_order).unwrap(), rfc_4122);
+            assert_eq!(
+                <Uuid as ProtocolNoCtx>::from_bytes(&rfc_4122, byte_order).unwrap(),
+                UUID
+            );
+        }
+    }
+
+    #[test]
+    fn guid_is_mixed_endian() {
+        let guid = [
+            0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            assert_eq!(Guid(UUID).bytes(byte_order).unwrap(), guid);
+            assert_eq!(Guid::from_bytes(&guid, byte_order).unwrap(), Guid(UUID));
+        }
+    }
+}
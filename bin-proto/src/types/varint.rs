@@ -0,0 +1,209 @@
+use crate::{BitRead, BitWrite, ByteOrder, ProtocolRead, ProtocolWrite, Result};
+use std::io;
+
+/// An unsigned integer encoded as an LEB128 variable-length integer, as used
+/// by protobuf, with the least significant group of 7 bits first.
+///
+/// Can be used as a length prefix:
+///
+/// ```
+/// # use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite, VarInt};
+/// #[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+/// struct Message {
+///     #[protocol(tag(type = "VarInt<u32>", write_value = "VarInt(self.name.len() as u32)"))]
+///     name: String,
+/// }
+///
+/// let message = Message { name: "bin".into() };
+/// assert_eq!(message.bytes(ByteOrder::BigEndian).unwrap(), b"\x03bin");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarInt<T>(pub T);
+
+/// A signed integer encoded as a zigzag LEB128 variable-length integer, as
+/// used by protobuf's `sint` types, so that small negative values are short.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZag<T>(pub T);
+
+/// Reads an LEB128 integer that fits in `bits` bits, which must be at most 64.
+fn read_leb128(read: &mut dyn BitRead, bits: u32) -> Result<u128> {
+    let mut value = 0u128;
+    let mut shift = 0;
+    loop {
+        let byte = read.read_u8()?;
+        let group = u128::from(byte & 0x7f);
+        if shift >= bits || (group << shift) >> bits != 0 {
+            return Err(
+                io::Error::new(io::ErrorKind::InvalidData, "varint overflows its type").into(),
+            );
+        }
+        value |= group << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+fn write_leb128(mut value: u128, write: &mut dyn BitWrite) -> Result<()> {
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let group = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            write.write_u8(group)?;
+            return Ok(());
+        }
+        write.write_u8(group | 0x80)?;
+    }
+}
+
+macro_rules! impl_varint {
+    ($ty:ty) => {
+        impl<Ctx> ProtocolRead<Ctx> for VarInt<$ty> {
+            fn read(read: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx) -> Result<Self> {
+                Ok(Self(<$ty>::try_from(read_leb128(read, <$ty>::BITS)?)?))
+            }
+        }
+
+        impl<Ctx> ProtocolWrite<Ctx> for VarInt<$ty> {
+            fn write(&self, write: &mut dyn BitWrite, _: ByteOrder, _: &mut Ctx) -> Result<()> {
+                #[allow(clippy::cast_lossless)]
+                write_leb128(self.0 as u128, write)
+            }
+        }
+
+        impl From<$ty> for VarInt<$ty> {
+            fn from(value: $ty) -> Self {
+                Self(value)
+            }
+        }
+    };
+}
+
+impl_varint!(u16);
+impl_varint!(u32);
+impl_varint!(u64);
+impl_varint!(usize);
+
+impl From<VarInt<u16>> for usize {
+    fn from(value: VarInt<u16>) -> Self {
+        value.0.into()
+    }
+}
+
+impl From<VarInt<usize>> for usize {
+    fn from(value: VarInt<usize>) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<VarInt<u32>> for usize {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: VarInt<u32>) -> std::result::Result<Self, Self::Error> {
+        value.0.try_into()
+    }
+}
+
+impl TryFrom<VarInt<u64>> for usize {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: VarInt<u64>) -> std::result::Result<Self, Self::Error> {
+        value.0.try_into()
+    }
+}
+
+macro_rules! impl_zigzag {
+    ($ty:ty => $unsigned:ty) => {
+        impl<Ctx> ProtocolRead<Ctx> for ZigZag<$ty> {
+            fn read(read: &mut dyn BitRead, _: ByteOrder, _: &mut Ctx) -> Result<Self> {
+                let value = <$unsigned>::try_from(read_leb128(read, <$unsigned>::BITS)?)?;
+                #[allow(clippy::cast_possible_wrap)]
+                Ok(Self((value >> 1) as $ty ^ -((value & 1) as $ty)))
+            }
+        }
+
+        impl<Ctx> ProtocolWrite<Ctx> for ZigZag<$ty> {
+            fn write(&self, write: &mut dyn BitWrite, _: ByteOrder, _: &mut Ctx) -> Result<()> {
+                #[allow(clippy::cast_sign_loss)]
+                let value = ((self.0 << 1) ^ (self.0 >> (<$ty>::BITS - 1))) as $unsigned;
+                #[allow(clippy::cast_lossless)]
+                write_leb128(value as u128, write)
+            }
+        }
+
+        impl From<$ty> for ZigZag<$ty> {
+            fn from(value: $ty) -> Self {
+                Self(value)
+            }
+        }
+    };
+}
+
+impl_zigzag!(i16 => u16);
+impl_zigzag!(i32 => u32);
+impl_zigzag!(i64 => u64);
+impl_zigzag!(isize => usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, ProtocolNoCtx};
+
+    use super::*;
+
+    #[test]
+    fn varint_round_trips() {
+        for (value, bytes) in [
+            (0u32, &[0x00][..]),
+            (1, &[0x01]),
+            (127, &[0x7f]),
+            (128, &[0x80, 0x01]),
+            (300, &[0xac, 0x02]),
+            (u32::MAX, &[0xff, 0xff, 0xff, 0xff, 0x0f]),
+        ] {
+            assert_eq!(VarInt(value).bytes(ByteOrder::BigEndian).unwrap(), bytes);
+            assert_eq!(
+                VarInt::<u32>::from_bytes(bytes, ByteOrder::LittleEndian).unwrap(),
+                VarInt(value)
+            );
+        }
+    }
+
+    #[test]
+    fn varint_rejects_overflow() {
+        assert!(matches!(
+            VarInt::<u32>::from_bytes(&[0xff, 0xff, 0xff, 0xff, 0x1f], ByteOrder::BigEndian),
+            Err(Error::IO(_))
+        ));
+        assert!(matches!(
+            VarInt::<u16>::from_bytes(&[0x80, 0x80, 0x80, 0x01], ByteOrder::BigEndian),
+            Err(Error::IO(_))
+        ));
+    }
+
+    #[test]
+    fn varint_fails_on_truncation() {
+        assert!(VarInt::<u64>::from_bytes(&[0x80], ByteOrder::BigEndian).is_err());
+    }
+
+    #[test]
+    fn zigzag_round_trips() {
+        for (value, bytes) in [
+            (0i32, &[0x00][..]),
+            (-1, &[0x01]),
+            (1, &[0x02]),
+            (-2, &[0x03]),
+            (-64, &[0x7f]),
+            (64, &[0x80, 0x01]),
+            (i32::MAX, &[0xfe, 0xff, 0xff, 0xff, 0x0f]),
+            (i32::MIN, &[0xff, 0xff, 0xff, 0xff, 0x0f]),
+        ] {
+            assert_eq!(ZigZag(value).bytes(ByteOrder::BigEndian).unwrap(), bytes);
+            assert_eq!(
+                ZigZag::<i32>::from_bytes(bytes, ByteOrder::BigEndian).unwrap(),
+                ZigZag(value)
+            );
+        }
+    }
+}
@@ -1,8 +1,18 @@
 //! Helper functions for dealing with sets or lists of parcels.
 
-use crate::{BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite, Result};
+use bitstream_io::{BigEndian, BitWriter, LittleEndian};
 
-use std::io;
+use crate::{
+    canonical::CanonicalWrite, BitRead, BitWrite, ByteOrder, Error, ProtocolRead, ProtocolWrite,
+    Result,
+};
+
+use std::{io, mem};
+
+/// The most memory preallocated for items whose count comes from the input,
+/// so that a huge count fails when its items run out rather than when
+/// allocating.
+const MAX_PREALLOCATION: usize = 64 * 1024;
 
 /// Reads a specified number of items from a stream.
 pub fn read_items<Ctx, T>(
@@ -14,7 +24,8 @@ pub fn read_items<Ctx, T>(
 where
     T: ProtocolRead<Ctx>,
 {
-    let mut elements = Vec::with_capacity(item_count);
+    let capacity = item_count.min(MAX_PREALLOCATION / mem::size_of::<T>().max(1));
+    let mut elements = Vec::with_capacity(capacity);
     for _ in 0..item_count {
         let element = T::read(read, byte_order, ctx)?;
         elements.push(element);
@@ -22,6 +33,20 @@ where
     Ok(elements)
 }
 
+/// Appends `len` bytes from a stream to `bytes`, growing it only as the bytes
+/// are read rather than by `len` up front.
+pub fn read_bytes_into(len: usize, read: &mut dyn BitRead, bytes: &mut Vec<u8>) -> Result<()> {
+    let mut remaining = len;
+    while remaining > 0 {
+        let start = bytes.len();
+        let chunk = remaining.min(MAX_PREALLOCATION);
+        bytes.resize(start + chunk, 0);
+        read.read_bytes(&mut bytes[start..])?;
+        remaining -= chunk;
+    }
+    Ok(())
+}
+
 /// `BitWrites` an iterator of parcels to the stream.
 ///
 /// Does not include a length prefix.
@@ -40,6 +65,111 @@ where
     Ok(())
 }
 
+/// `BitWrites` the entries of a map to the stream, each key followed by its
+/// value.
+///
+/// Does not include a length prefix.
+pub fn write_entries<'a, Ctx, K, V>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    write: &mut dyn BitWrite,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+) -> Result<()>
+where
+    K: ProtocolWrite<Ctx> + 'a,
+    V: ProtocolWrite<Ctx> + 'a,
+{
+    for (key, value) in entries {
+        key.write(write, byte_order, ctx)?;
+        value.write(write, byte_order, ctx)?;
+    }
+    Ok(())
+}
+
+/// Writes the items of an unordered collection. If `write` is canonical,
+/// they are written in the order of their canonical encodings instead of
+/// their iteration order, so that the order does not depend on how unordered
+/// collections nested in the items iterate.
+///
+/// Sorting writes each item twice, so `ctx` sees each item written twice.
+pub fn write_unordered_items<'a, Ctx, T>(
+    items: impl IntoIterator<Item = &'a T>,
+    write: &mut dyn BitWrite,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+) -> Result<()>
+where
+    T: ProtocolWrite<Ctx> + 'a,
+{
+    if !write.canonical() {
+        return write_items(items, write, byte_order, ctx);
+    }
+    let mut items = items
+        .into_iter()
+        .map(|item| Ok((canonical_bytes(item, byte_order, ctx)?, item)))
+        .collect::<Result<Vec<_>>>()?;
+    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+    write_items(
+        items.into_iter().map(|(_, item)| item),
+        write,
+        byte_order,
+        ctx,
+    )
+}
+
+/// Writes the entries of an unordered map. If `write` is canonical, they are
+/// written in the order of the canonical encodings of their keys instead of
+/// their iteration order.
+///
+/// Sorting writes each key twice, so `ctx` sees each key written twice.
+pub fn write_unordered_entries<'a, Ctx, K, V>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    write: &mut dyn BitWrite,
+    byte_order: ByteOrder,
+    ctx: &mut Ctx,
+) -> Result<()>
+where
+    K: ProtocolWrite<Ctx> + 'a,
+    V: ProtocolWrite<Ctx> + 'a,
+{
+    if !write.canonical() {
+        return write_entries(entries, write, byte_order, ctx);
+    }
+    let mut entries = entries
+        .into_iter()
+        .map(|(key, value)| Ok((canonical_bytes(key, byte_order, ctx)?, key, value)))
+        .collect::<Result<Vec<_>>>()?;
+    entries.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+    write_entries(
+        entries.into_iter().map(|(_, key, value)| (key, value)),
+        write,
+        byte_order,
+        ctx,
+    )
+}
+
+/// The canonical encoding of `value` in `byte_order`, by which unordered
+/// collections are sorted.
+fn canonical_bytes<Ctx, T>(value: &T, byte_order: ByteOrder, ctx: &mut Ctx) -> Result<Vec<u8>>
+where
+    T: ProtocolWrite<Ctx> + ?Sized,
+{
+    let mut bytes = Vec::new();
+    match byte_order {
+        ByteOrder::LittleEndian => {
+            let mut writer = BitWriter::endian(&mut bytes, LittleEndian);
+            value.write(&mut CanonicalWrite::new(&mut writer), byte_order, ctx)?;
+            writer.byte_align()?;
+        }
+        ByteOrder::BigEndian => {
+            let mut writer = BitWriter::endian(&mut bytes, BigEndian);
+            value.write(&mut CanonicalWrite::new(&mut writer), byte_order, ctx)?;
+            writer.byte_align()?;
+        }
+    }
+    Ok(bytes)
+}
+
 pub fn read_items_to_eof<Ctx, T>(
     read: &mut dyn BitRead,
     byte_order: ByteOrder,
@@ -0,0 +1,12 @@
+/// A trait for contexts that carry a negotiated protocol version, used by
+/// `enum` variants with `#[protocol(added_in = <version>)]` or
+/// `#[protocol(removed_in = <version>)]`.
+pub trait ProtocolVersion {
+    fn version(&self) -> u32;
+}
+
+impl ProtocolVersion for u32 {
+    fn version(&self) -> u32 {
+        *self
+    }
+}
@@ -0,0 +1,78 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+struct Aligned {
+    #[protocol(bits = 3)]
+    flags: u8,
+    #[protocol(align_before)]
+    value: u8,
+    #[protocol(bits = 4)]
+    nibble: u8,
+    #[protocol(bits = 4, align_before)]
+    second_nibble: u8,
+}
+
+#[test]
+fn align_before_pads_to_byte_boundary() {
+    let value = Aligned {
+        flags: 0b101,
+        value: 0x42,
+        nibble: 0b1001,
+        second_nibble: 0b0110,
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0b1010_0000, 0x42, 0b1001_0000, 0b0110_0000]);
+    assert_eq!(
+        Aligned::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn align_before_discards_bits_on_read() {
+    assert_eq!(
+        Aligned::from_bytes(
+            &[0b1011_1111, 0x42, 0b1001_1111, 0b0110_0000],
+            ByteOrder::BigEndian
+        )
+        .unwrap(),
+        Aligned {
+            flags: 0b101,
+            value: 0x42,
+            nibble: 0b1001,
+            second_nibble: 0b0110,
+        }
+    );
+}
+
+#[test]
+fn align_before_counts_toward_bit_len() {
+    let value = Aligned {
+        flags: 0,
+        value: 0,
+        nibble: 0,
+        second_nibble: 0,
+    };
+    assert_eq!(value.bit_len(ByteOrder::BigEndian).unwrap(), 28);
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+struct AlreadyAligned {
+    first: u8,
+    #[protocol(align_before)]
+    second: u8,
+}
+
+#[test]
+fn align_before_is_noop_when_aligned() {
+    let value = AlreadyAligned {
+        first: 1,
+        second: 2,
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [1, 2]);
+    assert_eq!(
+        AlreadyAligned::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
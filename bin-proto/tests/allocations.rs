@@ -0,0 +1,134 @@
+use bin_proto::{
+    framed::{self, FrameConfig, PrefixWidth},
+    ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite, TaggedRead,
+};
+use bitstream_io::{BigEndian, BitReader};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Counts the bytes allocated by each thread, so that tests running in
+/// parallel do not see each other's allocations.
+struct Counting;
+
+thread_local! {
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Returns the value of `f` and the number of bytes it allocated.
+fn allocated<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.with(Cell::get);
+    let value = f();
+    (value, ALLOCATED.with(Cell::get) - before)
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Frame {
+    #[protocol(bits = 5)]
+    channel: u8,
+    #[protocol(bits = 3)]
+    priority: u8,
+    #[protocol(tag(type = "u8", write_value = "self.data.len() as u8"))]
+    data: Vec<u8>,
+}
+
+fn read_replace_string(value: &mut String, bytes: &[u8], len: usize) -> bin_proto::Result<()> {
+    TaggedRead::<usize, ()>::read_replace(
+        value,
+        &mut BitReader::endian(bytes, BigEndian),
+        ByteOrder::BigEndian,
+        &mut (),
+        len,
+    )
+}
+
+#[test]
+fn string_read_replace_does_not_trust_length() {
+    let mut value = String::from("old");
+    let (result, bytes) = allocated(|| read_replace_string(&mut value, b"abc", usize::MAX / 2));
+    assert!(result.is_err());
+    assert!(bytes <= 128 * 1024, "allocated {bytes} bytes");
+    assert_eq!(value, "");
+}
+
+#[test]
+fn string_read_replace_reuses_allocation() {
+    let mut value = String::new();
+    read_replace_string(&mut value, b"first", 5).unwrap();
+    read_replace_string(&mut value, b"other", 5).unwrap();
+    let (result, bytes) = allocated(|| read_replace_string(&mut value, b"third", 5));
+    result.unwrap();
+    assert_eq!(value, "third");
+    assert_eq!(bytes, 0);
+}
+
+#[test]
+fn string_read_replace_clears_contents_on_invalid_utf8() {
+    let mut value = String::from("old");
+    assert!(read_replace_string(&mut value, &[0xff, 0xfe], 2).is_err());
+    assert_eq!(value, "");
+}
+
+#[test]
+fn read_frame_does_not_trust_length() {
+    let config = FrameConfig::new(PrefixWidth::U32).max_frame_size(usize::MAX);
+    let (result, bytes) = allocated(|| {
+        framed::read_frame::<u32>(
+            &mut [0xff, 0xff, 0xff, 0xff, 1, 2, 3].as_slice(),
+            ByteOrder::BigEndian,
+            &config,
+        )
+    });
+    assert!(matches!(result, Err(Error::IO(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof));
+    assert!(bytes <= 1024, "allocated {bytes} bytes");
+}
+
+#[test]
+fn validate_does_not_allocate() {
+    let valid = Frame {
+        channel: 31,
+        priority: 7,
+        data: vec![0; 255],
+    };
+    let out_of_range = Frame {
+        channel: 40,
+        priority: 0,
+        data: Vec::new(),
+    };
+    let overflowing = Frame {
+        channel: 0,
+        priority: 0,
+        data: vec![0; 300],
+    };
+
+    let (result, bytes) = allocated(|| valid.validate(ByteOrder::BigEndian));
+    assert!(result.is_ok());
+    assert_eq!(bytes, 0);
+    let (result, bytes) = allocated(|| out_of_range.validate(ByteOrder::BigEndian));
+    assert!(matches!(result, Err(Error::IO(_))));
+    assert_eq!(bytes, 0);
+    let (result, bytes) = allocated(|| overflowing.validate(ByteOrder::BigEndian));
+    assert!(matches!(
+        result,
+        Err(Error::TagMismatch {
+            field: "data",
+            len: 300
+        })
+    ));
+    assert_eq!(bytes, 0);
+}
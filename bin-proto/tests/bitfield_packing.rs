@@ -0,0 +1,98 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+/// Layout produced by GCC on x86_64 for:
+///
+/// ```c
+/// struct Flags {
+///     uint8_t a : 3;   // bits 0..3 of byte 0
+///     uint8_t b : 4;   // bits 3..7 of byte 0
+///     uint8_t c : 3;   // would straddle byte 0, so bits 0..3 of byte 1
+///     uint16_t d : 12; // would straddle bytes 0..2, so bits 0..12 of bytes 2..4
+///     uint8_t e;       // byte 4
+/// };
+/// ```
+///
+/// The trailing padding byte of the C struct is not part of the encoding.
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(bitfield_packing = "lsb0")]
+struct Flags {
+    #[protocol(bits = 3)]
+    a: u8,
+    #[protocol(bits = 4)]
+    b: u8,
+    #[protocol(bits = 3)]
+    c: u8,
+    #[protocol(bits = 12)]
+    d: u16,
+    e: u8,
+}
+
+/// Layout produced by GCC on x86_64 for:
+///
+/// ```c
+/// struct Signed {
+///     int8_t lo : 4;  // bits 0..4 of byte 0
+///     int8_t hi : 4;  // bits 4..8 of byte 0
+///     bool flag : 1;  // bit 0 of byte 1
+/// };
+/// ```
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(bitfield_packing = "lsb0")]
+struct Signed(
+    #[protocol(bits = 4)] i8,
+    #[protocol(bits = 4)] i8,
+    #[protocol(bits = 1)] bool,
+);
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(bitfield_packing = "msb0")]
+struct Msb0 {
+    #[protocol(bits = 3)]
+    a: u8,
+    #[protocol(bits = 5)]
+    b: u8,
+}
+
+const FLAGS: Flags = Flags {
+    a: 5,
+    b: 0xa,
+    c: 6,
+    d: 0xabc,
+    e: 0x42,
+};
+
+const FLAGS_BYTES: [u8; 5] = [0x55, 0x06, 0xbc, 0x0a, 0x42];
+
+#[test]
+fn lsb0_matches_c_layout() {
+    for byte_order in [ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+        assert_eq!(FLAGS.bytes(byte_order).unwrap(), FLAGS_BYTES);
+        assert_eq!(Flags::from_bytes(&FLAGS_BYTES, byte_order).unwrap(), FLAGS);
+    }
+}
+
+#[test]
+fn lsb0_ignores_padding_bits_on_read() {
+    let bytes = [0xd5, 0xfe, 0xbc, 0xfa, 0x42];
+    assert_eq!(
+        Flags::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+        FLAGS
+    );
+}
+
+#[test]
+fn lsb0_signed_bitfields() {
+    let value = Signed(-3, 2, true);
+    let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+    assert_eq!(bytes, [0x2d, 0x01]);
+    assert_eq!(
+        Signed::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn msb0_is_default_packing() {
+    let value = Msb0 { a: 5, b: 3 };
+    assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), [0xa3]);
+}
@@ -0,0 +1,70 @@
+#![cfg(test)]
+
+use bin_proto::{Bits, ByteOrder, FixedSize, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Flags {
+    kind: Bits<3, u8>,
+    urgent: Bits<1, u8>,
+    channel: Bits<12, u16>,
+    sequence: u16,
+    offset: Bits<5, i8>,
+    reserved: Bits<3, u8>,
+    length: Bits<24>,
+}
+
+fn flags() -> Flags {
+    Flags {
+        kind: Bits::new(0b101).unwrap(),
+        urgent: Bits::new(1).unwrap(),
+        channel: Bits::new(0xabc).unwrap(),
+        sequence: 0x1234,
+        offset: Bits::new(-3).unwrap(),
+        reserved: Bits::default(),
+        length: Bits::new(0x01_0203).unwrap(),
+    }
+}
+
+#[test]
+fn packs_bits_fields() {
+    let bytes = flags().bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(
+        bytes,
+        [0xba, 0xbc, 0x12, 0x34, 0b1110_1000, 0x01, 0x02, 0x03]
+    );
+    assert_eq!(
+        Flags::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        flags()
+    );
+}
+
+#[test]
+fn bits_fields_are_fixed_size() {
+    assert_eq!(Flags::SIZE_BITS, 64);
+}
+
+#[test]
+fn bits_fields_behave_like_bits_attribute() {
+    #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+    struct Attribute {
+        #[protocol(bits = 3)]
+        kind: u8,
+        #[protocol(bits = 1)]
+        urgent: u8,
+        #[protocol(bits = 12)]
+        channel: u16,
+        sequence: u16,
+    }
+
+    let flags = flags();
+    let attribute = Attribute {
+        kind: flags.kind.get(),
+        urgent: *flags.urgent,
+        channel: flags.channel.into(),
+        sequence: flags.sequence,
+    };
+    assert_eq!(
+        attribute.bytes(ByteOrder::LittleEndian).unwrap(),
+        flags.bytes(ByteOrder::LittleEndian).unwrap()[..4]
+    );
+}
@@ -0,0 +1,76 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead};
+
+mod wire {
+    use bin_proto::{ProtocolRead, ProtocolWrite};
+
+    /// A percentage, which is at most 100.
+    #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+    #[protocol(builder = "Self::new")]
+    pub struct Percent {
+        id: u16,
+        value: u8,
+    }
+
+    impl Percent {
+        pub fn new(id: u16, value: u8) -> Self {
+            Self {
+                id,
+                value: value.min(100),
+            }
+        }
+
+        pub fn id(&self) -> u16 {
+            self.id
+        }
+
+        pub fn value(&self) -> u8 {
+            self.value
+        }
+    }
+
+    #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+    #[protocol(builder = "sorted")]
+    pub struct Pair(u8, u8);
+
+    fn sorted(a: u8, b: u8) -> Pair {
+        Pair(a.min(b), a.max(b))
+    }
+
+    impl Pair {
+        pub fn get(&self) -> (u8, u8) {
+            (self.0, self.1)
+        }
+    }
+}
+
+use wire::{Pair, Percent};
+
+#[test]
+fn builds_named_fields() {
+    let percent = Percent::from_bytes(&[0x01, 0x02, 150], ByteOrder::BigEndian).unwrap();
+    assert_eq!(percent.id(), 0x0102);
+    assert_eq!(percent.value(), 100);
+    assert_eq!(
+        percent.bytes(ByteOrder::BigEndian).unwrap(),
+        [0x01, 0x02, 100]
+    );
+}
+
+#[test]
+fn builds_unnamed_fields() {
+    let pair = Pair::from_bytes(&[9, 3], ByteOrder::BigEndian).unwrap();
+    assert_eq!(pair.get(), (3, 9));
+}
+
+#[test]
+fn read_replace_uses_builder() {
+    let mut percent = Percent::new(1, 1);
+    percent
+        .read_replace(
+            &mut *bin_proto::from_read([0x00, 0x07, 255].as_slice(), ByteOrder::BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        )
+        .unwrap();
+    assert_eq!(percent, Percent::new(7, 100));
+}
@@ -0,0 +1,126 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(byte_order = "le")]
+struct LittleEndianHeader {
+    magic: u32,
+    #[protocol(tag(type = "u16", write_value = "self.data.len() as u16"))]
+    data: Vec<u16>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(byte_order = "be")]
+struct BigEndianPacket {
+    header: LittleEndianHeader,
+    checksum: u16,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u16", byte_order = "le")]
+enum LittleEndianMessage {
+    #[protocol(discriminant = "1")]
+    Value(u32),
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Inherited {
+    value: u16,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(byte_order = "le")]
+struct Outer {
+    inherited: Inherited,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Mixed {
+    ambient: u16,
+    #[protocol(byte_order = "be")]
+    network: u16,
+    #[protocol(byte_order = "le")]
+    vendor: u32,
+    #[protocol(byte_order = "be")]
+    nested: Inherited,
+    #[protocol(
+        byte_order = "le",
+        tag(type = "u16", write_value = "self.words.len() as u16")
+    )]
+    words: Vec<u16>,
+}
+
+fn header() -> LittleEndianHeader {
+    LittleEndianHeader {
+        magic: 0x0102_0304,
+        data: vec![0x0506],
+    }
+}
+
+const HEADER_BYTES: [u8; 8] = [0x04, 0x03, 0x02, 0x01, 0x01, 0x00, 0x06, 0x05];
+
+#[test]
+fn struct_ignores_byte_order_passed_in() {
+    for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+        assert_eq!(header().bytes(byte_order).unwrap(), HEADER_BYTES);
+        assert_eq!(
+            LittleEndianHeader::from_bytes(&HEADER_BYTES, byte_order).unwrap(),
+            header()
+        );
+    }
+}
+
+#[test]
+fn nested_struct_keeps_own_byte_order() {
+    let packet = BigEndianPacket {
+        header: header(),
+        checksum: 0x0708,
+    };
+    let mut bytes = HEADER_BYTES.to_vec();
+    bytes.extend([0x07, 0x08]);
+    assert_eq!(packet.bytes(ByteOrder::LittleEndian).unwrap(), bytes);
+    assert_eq!(
+        BigEndianPacket::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+        packet
+    );
+}
+
+#[test]
+fn fields_inherit_byte_order() {
+    let outer = Outer {
+        inherited: Inherited { value: 1 },
+    };
+    assert_eq!(outer.bytes(ByteOrder::BigEndian).unwrap(), [0x01, 0x00]);
+}
+
+#[test]
+fn enum_ignores_byte_order_passed_in() {
+    let message = LittleEndianMessage::Value(1);
+    let bytes = [0x01, 0x00, 0x01, 0x00, 0x00, 0x00];
+    assert_eq!(message.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+    assert_eq!(
+        LittleEndianMessage::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        message
+    );
+}
+
+#[test]
+fn field_overrides_byte_order_passed_in() {
+    let mixed = Mixed {
+        ambient: 0x0102,
+        network: 0x0304,
+        vendor: 0x0506_0708,
+        nested: Inherited { value: 0x090a },
+        words: vec![0x0b0c],
+    };
+    for (byte_order, ambient) in [
+        (ByteOrder::BigEndian, [0x01, 0x02]),
+        (ByteOrder::LittleEndian, [0x02, 0x01]),
+    ] {
+        let mut bytes = ambient.to_vec();
+        bytes.extend([
+            0x03, 0x04, 0x08, 0x07, 0x06, 0x05, 0x09, 0x0a, 0x01, 0x00, 0x0c, 0x0b,
+        ]);
+        assert_eq!(mixed.bytes(byte_order).unwrap(), bytes);
+        assert_eq!(Mixed::from_bytes(&bytes, byte_order).unwrap(), mixed);
+    }
+}
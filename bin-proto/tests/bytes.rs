@@ -0,0 +1,76 @@
+#![cfg(feature = "bytes")]
+
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use bytes::Bytes;
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Packet {
+    len: u8,
+    #[protocol(tag = "len as usize")]
+    header: Bytes,
+    #[protocol(flexible_array_member)]
+    payload: Bytes,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Unaligned {
+    #[protocol(bits = 4)]
+    len: u8,
+    #[protocol(tag = "len as usize")]
+    data: Bytes,
+    #[protocol(bits = 4)]
+    trailer: u8,
+}
+
+#[test]
+fn reads_without_copying() {
+    let source = Bytes::from_static(&[0x02, 0xaa, 0xbb, 0x01, 0x02, 0x03]);
+    let packet = Packet::from_buf(source.clone(), ByteOrder::BigEndian).unwrap();
+    assert_eq!(packet.header, [0xaa, 0xbb][..]);
+    assert_eq!(packet.payload, [0x01, 0x02, 0x03][..]);
+    assert_eq!(packet.header.as_ptr(), source[1..].as_ptr());
+    assert_eq!(packet.payload.as_ptr(), source[3..].as_ptr());
+}
+
+#[test]
+fn reads_from_any_buf() {
+    let source: &[u8] = &[0x01, 0xaa, 0x01];
+    assert_eq!(
+        Packet::from_buf(source, ByteOrder::BigEndian).unwrap(),
+        Packet {
+            len: 1,
+            header: Bytes::from_static(&[0xaa]),
+            payload: Bytes::from_static(&[0x01]),
+        }
+    );
+}
+
+#[test]
+fn roundtrips() {
+    let packet = Packet {
+        len: 2,
+        header: Bytes::from_static(&[0xaa, 0xbb]),
+        payload: Bytes::from_static(&[0x01, 0x02]),
+    };
+    let bytes = packet.to_bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, packet.bytes(ByteOrder::BigEndian).unwrap());
+    assert_eq!(
+        Packet::from_buf(bytes, ByteOrder::BigEndian).unwrap(),
+        packet
+    );
+}
+
+#[test]
+fn roundtrips_unaligned() {
+    let value = Unaligned {
+        len: 2,
+        data: Bytes::from_static(&[0xab, 0xcd]),
+        trailer: 0xe,
+    };
+    let bytes = value.to_bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0x2a, 0xbc, 0xde][..]);
+    assert_eq!(
+        Unaligned::from_buf(bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
@@ -0,0 +1,114 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Inventory {
+    version: u16,
+    sealed: bool,
+    #[protocol(tag(type = "u8", write_value = "self.stock.len() as u8"))]
+    stock: HashMap<u8, u16>,
+    #[protocol(tag(type = "u8", write_value = "self.tags.len() as u8"))]
+    #[protocol(checksum = "bin_proto::Crc16")]
+    tags: HashSet<u16>,
+}
+
+fn inventory() -> Inventory {
+    Inventory {
+        version: 0x0102,
+        sealed: true,
+        stock: (0..8)
+            .map(|item| (7 - item, u16::from(item) * 0x101))
+            .collect(),
+        tags: [0x0300, 0x0001, 0x0200, 0x0100].into_iter().collect(),
+    }
+}
+
+/// A set of tags, which hashes its tags in sorted order.
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+struct Group {
+    #[protocol(tag(type = "u8", write_value = "self.tags.len() as u8"))]
+    tags: HashSet<u16>,
+}
+
+impl Hash for Group {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut tags: Vec<_> = self.tags.iter().collect();
+        tags.sort();
+        tags.hash(state);
+    }
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Groups {
+    #[protocol(tag(type = "u8", write_value = "self.groups.len() as u8"))]
+    groups: HashSet<Group>,
+}
+
+const GOLDEN: &[u8] = &[
+    0x01, 0x02, // version
+    0x01, // sealed
+    0x08, // stock count
+    0x00, 0x07, 0x07, // 0 => 0x0707
+    0x01, 0x06, 0x06, // 1 => 0x0606
+    0x02, 0x05, 0x05, // 2 => 0x0505
+    0x03, 0x04, 0x04, // 3 => 0x0404
+    0x04, 0x03, 0x03, // 4 => 0x0303
+    0x05, 0x02, 0x02, // 5 => 0x0202
+    0x06, 0x01, 0x01, // 6 => 0x0101
+    0x07, 0x00, 0x00, // 7 => 0x0000
+    0x04, // tags count
+    0x00, 0x01, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, // tags
+    0x74, 0xc4, // checksum of tags
+];
+
+#[test]
+fn canonical_bytes_match_golden() {
+    // Each map hashes with its own random keys, so the iteration order
+    // differs between maps with the same entries.
+    for _ in 0..16 {
+        assert_eq!(inventory().canonical_bytes().unwrap(), GOLDEN);
+    }
+}
+
+#[test]
+fn nested_unordered_items_are_sorted_canonically() {
+    let groups = || Groups {
+        groups: [vec![0x0200, 0x0001], vec![0x0100, 0x0003]]
+            .into_iter()
+            .map(|tags| Group {
+                tags: tags.into_iter().collect(),
+            })
+            .collect(),
+    };
+    for _ in 0..16 {
+        assert_eq!(
+            groups().canonical_bytes().unwrap(),
+            [
+                0x02, // group count
+                0x02, 0x00, 0x01, 0x02, 0x00, // {0x0001, 0x0200}
+                0x02, 0x00, 0x03, 0x01, 0x00, // {0x0003, 0x0100}
+            ]
+        );
+    }
+}
+
+#[test]
+fn canonical_bytes_round_trip() {
+    assert_eq!(
+        Inventory::from_bytes(GOLDEN, ByteOrder::BigEndian).unwrap(),
+        inventory()
+    );
+}
+
+#[test]
+fn canonical_hash_hashes_canonical_bytes() {
+    let mut hasher = DefaultHasher::new();
+    inventory().canonical_hash(&mut hasher).unwrap();
+    let mut expected = DefaultHasher::new();
+    expected.write(GOLDEN);
+    assert_eq!(hasher.finish(), expected.finish());
+}
@@ -0,0 +1,63 @@
+use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Checksummed {
+    #[protocol(
+        tag(type = "u8", write_value = "self.payload.len() as u8"),
+        checksum = "::bin_proto::Crc16"
+    )]
+    payload: Vec<u8>,
+    trailer: u8,
+}
+
+#[test]
+fn writes_length_and_checksum() {
+    assert_eq!(
+        Checksummed {
+            payload: b"123456789".to_vec(),
+            trailer: 0xaa,
+        }
+        .bytes(ByteOrder::BigEndian)
+        .unwrap(),
+        vec![9, b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', 0x29, 0xb1, 0xaa]
+    );
+}
+
+#[test]
+fn reads_length_and_checksum() {
+    assert_eq!(
+        Checksummed::from_bytes(
+            &[9, b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', 0x29, 0xb1, 0xaa],
+            ByteOrder::BigEndian
+        )
+        .unwrap(),
+        Checksummed {
+            payload: b"123456789".to_vec(),
+            trailer: 0xaa,
+        }
+    );
+}
+
+#[test]
+fn round_trips_little_endian() {
+    let value = Checksummed {
+        payload: vec![0, 1, 2, 3],
+        trailer: 7,
+    };
+    let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+    assert_eq!(
+        Checksummed::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn rejects_corrupted_payload() {
+    assert!(matches!(
+        Checksummed::from_bytes(
+            &[9, b'1', b'2', b'3', b'4', b'0', b'6', b'7', b'8', b'9', 0x29, 0xb1, 0xaa],
+            ByteOrder::BigEndian
+        ),
+        Err(Error::ChecksumMismatch)
+    ));
+}
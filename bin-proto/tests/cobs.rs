@@ -0,0 +1,81 @@
+use bin_proto::{cobs, ByteOrder, Error, ProtocolRead, ProtocolWrite};
+use std::io;
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Message {
+    id: u16,
+    #[protocol(flexible_array_member)]
+    payload: Vec<u8>,
+}
+
+#[test]
+fn frames_round_trip() {
+    let messages = [
+        Message {
+            id: 0x0100,
+            payload: vec![3, 0, 5],
+        },
+        Message {
+            id: 0,
+            payload: vec![0; 600],
+        },
+        Message {
+            id: 0x0102,
+            payload: (1..=255).collect(),
+        },
+    ];
+    let mut stream = Vec::new();
+    for message in &messages {
+        cobs::write_frame(&mut stream, message, ByteOrder::BigEndian).unwrap();
+    }
+    assert_eq!(
+        stream.iter().filter(|&&byte| byte == 0).count(),
+        messages.len()
+    );
+    assert_eq!(stream.last(), Some(&0));
+
+    let mut reader = stream.as_slice();
+    for message in &messages {
+        assert_eq!(
+            &cobs::read_frame::<Message>(&mut reader, ByteOrder::BigEndian, usize::MAX).unwrap(),
+            message
+        );
+    }
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn zero_payload_round_trips() {
+    let zeros = vec![0u8; 300];
+    let encoded = cobs::encode(&zeros);
+    assert_eq!(encoded, vec![1; 301]);
+    assert_eq!(cobs::decode(&encoded).unwrap(), zeros);
+}
+
+#[test]
+fn malformed_frame() {
+    assert!(matches!(
+        cobs::read_frame::<Message>(&mut [0x05, 0x01, 0x02, 0x00].as_slice(), ByteOrder::BigEndian, usize::MAX),
+        Err(Error::IO(e)) if e.kind() == io::ErrorKind::InvalidData
+    ));
+}
+
+#[test]
+fn unterminated_frame() {
+    assert!(matches!(
+        cobs::read_frame::<Message>(&mut [0x03, 0x01, 0x02].as_slice(), ByteOrder::BigEndian, usize::MAX),
+        Err(Error::IO(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+    ));
+}
+
+#[test]
+fn frame_too_large() {
+    assert!(matches!(
+        cobs::read_frame::<Message>(
+            &mut [0x05, 1, 2, 3, 4, 0x00].as_slice(),
+            ByteOrder::BigEndian,
+            4
+        ),
+        Err(Error::FrameTooLarge { size: 5, max: 4 })
+    ));
+}
@@ -0,0 +1,200 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Conditional {
+    flags: u8,
+    #[protocol(condition = "flags & 0x01 != 0", default = "0")]
+    defaulted: u16,
+    #[protocol(condition = "flags & 0x02 != 0", default = "0xff")]
+    with_default: u8,
+    #[protocol(tag = "flags & 0x04 != 0")]
+    optional: Option<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct ConditionalOption {
+    flags: u8,
+    #[protocol(
+        condition = "flags & 0x01 != 0",
+        tag(type = "bool", write_value = "self.value.is_some()")
+    )]
+    value: Option<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Extended {
+    #[protocol(write_value = "u8::from(self.extension != 0)")]
+    has_extension: u8,
+    #[protocol(condition = "has_extension != 0", default = "0")]
+    extension: u16,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Flags {
+    bits: u8,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Nested {
+    flags: Flags,
+    #[protocol(condition = "flags.bits & 0x01 != 0", default = "0")]
+    value: u8,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+enum ConditionalEnum {
+    #[protocol(discriminant = "1")]
+    Variant {
+        present: bool,
+        #[protocol(condition = "present", default = "7")]
+        value: u32,
+    },
+}
+
+#[test]
+fn reads_present_conditional_fields() {
+    assert_eq!(
+        Conditional::from_bytes(&[0x07, 0x00, 0x02, 0x03, 0x04], ByteOrder::BigEndian).unwrap(),
+        Conditional {
+            flags: 0x07,
+            defaulted: 2,
+            with_default: 3,
+            optional: Some(4),
+        }
+    );
+}
+
+#[test]
+fn reads_absent_conditional_fields_as_defaults() {
+    assert_eq!(
+        Conditional::from_bytes(&[0x00], ByteOrder::BigEndian).unwrap(),
+        Conditional {
+            flags: 0x00,
+            defaulted: 0,
+            with_default: 0xff,
+            optional: None,
+        }
+    );
+}
+
+#[test]
+fn reads_absent_conditional_option_as_none() {
+    assert_eq!(
+        ConditionalOption::from_bytes(&[0x00], ByteOrder::BigEndian).unwrap(),
+        ConditionalOption {
+            flags: 0x00,
+            value: None,
+        }
+    );
+    assert_eq!(
+        ConditionalOption::from_bytes(&[0x01, 0x01, 0x02], ByteOrder::BigEndian).unwrap(),
+        ConditionalOption {
+            flags: 0x01,
+            value: Some(2),
+        }
+    );
+}
+
+#[test]
+fn writes_present_conditional_fields() {
+    assert_eq!(
+        Conditional {
+            flags: 0x03,
+            defaulted: 2,
+            with_default: 3,
+            optional: None,
+        }
+        .bytes(ByteOrder::BigEndian)
+        .unwrap(),
+        vec![0x03, 0x00, 0x02, 0x03]
+    );
+}
+
+#[test]
+fn does_not_write_absent_conditional_fields() {
+    assert_eq!(
+        Conditional {
+            flags: 0x00,
+            defaulted: 2,
+            with_default: 3,
+            optional: None,
+        }
+        .bytes(ByteOrder::BigEndian)
+        .unwrap(),
+        vec![0x00]
+    );
+}
+
+#[test]
+fn conditional_variant_field_round_trips() {
+    let present = ConditionalEnum::Variant {
+        present: true,
+        value: 1,
+    };
+    let bytes = present.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, vec![1, 1, 0, 0, 0, 1]);
+    assert_eq!(
+        ConditionalEnum::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        present
+    );
+
+    let absent = ConditionalEnum::Variant {
+        present: false,
+        value: 1,
+    };
+    let bytes = absent.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, vec![1, 0]);
+    assert_eq!(
+        ConditionalEnum::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        ConditionalEnum::Variant {
+            present: false,
+            value: 7,
+        }
+    );
+}
+
+#[test]
+fn condition_uses_written_value() {
+    let extended = Extended {
+        has_extension: 0,
+        extension: 5,
+    };
+    let bytes = extended.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, vec![1, 0, 5]);
+    assert_eq!(
+        Extended::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        Extended {
+            has_extension: 1,
+            extension: 5,
+        }
+    );
+
+    let plain = Extended {
+        has_extension: 1,
+        extension: 0,
+    };
+    let bytes = plain.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, vec![0]);
+    assert_eq!(
+        Extended::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        Extended {
+            has_extension: 0,
+            extension: 0,
+        }
+    );
+}
+
+#[test]
+fn condition_reads_fields_of_earlier_field() {
+    let nested = Nested {
+        flags: Flags { bits: 0x01 },
+        value: 2,
+    };
+    let bytes = nested.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, vec![0x01, 0x02]);
+    assert_eq!(
+        Nested::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        nested
+    );
+}
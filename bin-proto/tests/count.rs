@@ -0,0 +1,52 @@
+use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Samples {
+    #[protocol(count = 3)]
+    values: Vec<u16>,
+    trailer: u8,
+}
+
+#[test]
+fn reads_fixed_count() {
+    assert_eq!(
+        Samples::from_bytes(&[0, 1, 0, 2, 0, 3, 9], ByteOrder::BigEndian).unwrap(),
+        Samples {
+            values: vec![1, 2, 3],
+            trailer: 9,
+        }
+    );
+}
+
+#[test]
+fn writes_fixed_count() {
+    assert_eq!(
+        Samples {
+            values: vec![1, 2, 3],
+            trailer: 9,
+        }
+        .bytes(ByteOrder::LittleEndian)
+        .unwrap(),
+        [1, 0, 2, 0, 3, 0, 9]
+    );
+}
+
+#[test]
+fn write_rejects_wrong_count() {
+    assert!(matches!(
+        Samples {
+            values: vec![1, 2],
+            trailer: 9,
+        }
+        .bytes(ByteOrder::BigEndian),
+        Err(Error::CountMismatch {
+            expected: 3,
+            actual: 2
+        })
+    ));
+}
+
+#[test]
+fn read_fails_on_short_input() {
+    assert!(Samples::from_bytes(&[0, 1, 0, 2], ByteOrder::BigEndian).is_err());
+}
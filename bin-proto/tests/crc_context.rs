@@ -0,0 +1,71 @@
+use bin_proto::{ByteOrder, CrcContext, Error, MessageChecksum, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(ctx = "CrcContext")]
+struct Header {
+    #[protocol(bits = 4)]
+    version: u8,
+    #[protocol(bits = 4)]
+    flags: u8,
+    len: u8,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(ctx = "CrcContext")]
+struct Message {
+    header: Header,
+    #[protocol(tag = "header.len as usize")]
+    payload: Vec<u8>,
+    crc: MessageChecksum,
+}
+
+fn message() -> Message {
+    Message {
+        header: Header {
+            version: 1,
+            flags: 0,
+            len: 7,
+        },
+        payload: b"3456789".to_vec(),
+        crc: MessageChecksum::default(),
+    }
+}
+
+#[test]
+fn writes_checksum_of_whole_message() {
+    assert_eq!(
+        CrcContext::new()
+            .bytes(&message(), ByteOrder::BigEndian)
+            .unwrap(),
+        b"\x10\x073456789\x6b\x0c"
+    );
+}
+
+#[test]
+fn reads_checksum_of_whole_message() {
+    assert_eq!(
+        CrcContext::new()
+            .from_bytes::<Message>(b"\x10\x073456789\x6b\x0c", ByteOrder::BigEndian)
+            .unwrap(),
+        message()
+    );
+}
+
+#[test]
+fn round_trips_little_endian() {
+    let mut ctx = CrcContext::new();
+    let bytes = ctx.bytes(&message(), ByteOrder::LittleEndian).unwrap();
+    assert_eq!(
+        ctx.from_bytes::<Message>(&bytes, ByteOrder::LittleEndian)
+            .unwrap(),
+        message()
+    );
+}
+
+#[test]
+fn rejects_corrupted_header() {
+    assert!(matches!(
+        CrcContext::new().from_bytes::<Message>(b"\x11\x073456789\x6b\x0c", ByteOrder::BigEndian),
+        Err(Error::ChecksumMismatch)
+    ));
+}
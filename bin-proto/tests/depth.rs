@@ -0,0 +1,143 @@
+use std::{rc::Rc, sync::Arc, thread};
+
+use bin_proto::{ByteOrder, Error, MaxDepth, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Node {
+    value: u32,
+    #[protocol(tag(type = "u8", write_value = "self.children.len() as u8"))]
+    children: Vec<Node>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct BoxList {
+    value: u8,
+    #[protocol(tag(type = "bool", write_value = "self.next.is_some()"))]
+    next: Option<Box<BoxList>>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct RcList {
+    value: u8,
+    #[protocol(tag(type = "bool", write_value = "self.next.is_some()"))]
+    next: Option<Rc<RcList>>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct ArcList {
+    value: u8,
+    #[protocol(tag(type = "bool", write_value = "self.next.is_some()"))]
+    next: Option<Arc<ArcList>>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+enum Expr {
+    #[protocol(discriminant = "0")]
+    Literal(u8),
+    #[protocol(discriminant = "1")]
+    Neg(Box<Expr>),
+}
+
+/// Bytes of `depth` nested nodes, each with a single child, except the last.
+fn nested_nodes(depth: usize) -> Vec<u8> {
+    let mut bytes = [0, 0, 0, 1, 1].repeat(depth - 1);
+    bytes.extend([0, 0, 0, 1, 0]);
+    bytes
+}
+
+fn round_trip<T: ProtocolRead + ProtocolWrite + PartialEq + std::fmt::Debug>(value: &T) {
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(&T::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(), value);
+}
+
+#[test]
+fn recursive_types_round_trip() {
+    round_trip(&Node {
+        value: 1,
+        children: vec![
+            Node {
+                value: 2,
+                children: vec![],
+            },
+            Node {
+                value: 3,
+                children: vec![Node {
+                    value: 4,
+                    children: vec![],
+                }],
+            },
+        ],
+    });
+    round_trip(&BoxList {
+        value: 1,
+        next: Some(Box::new(BoxList {
+            value: 2,
+            next: None,
+        })),
+    });
+    round_trip(&RcList {
+        value: 1,
+        next: Some(Rc::new(RcList {
+            value: 2,
+            next: None,
+        })),
+    });
+    round_trip(&ArcList {
+        value: 1,
+        next: Some(Arc::new(ArcList {
+            value: 2,
+            next: None,
+        })),
+    });
+    round_trip(&Expr::Neg(Box::new(Expr::Neg(Box::new(Expr::Literal(5))))));
+}
+
+#[test]
+fn unlimited_without_depth_ctx() {
+    let bytes = nested_nodes(200);
+    assert!(Node::from_bytes(&bytes, ByteOrder::BigEndian).is_ok());
+}
+
+#[test]
+fn configured_limit() {
+    let read = |depth| {
+        Node::from_bytes_ctx(
+            &nested_nodes(depth),
+            ByteOrder::BigEndian,
+            &mut MaxDepth::new(3),
+        )
+    };
+    assert!(read(3).is_ok());
+    assert!(matches!(read(4), Err(Error::MaxDepthExceeded { max: 3 })));
+    assert!(matches!(
+        Expr::from_bytes_ctx(&[1, 1, 0, 5], ByteOrder::BigEndian, &mut MaxDepth::new(1)),
+        Err(Error::MaxDepthExceeded { max: 1 })
+    ));
+}
+
+#[test]
+fn non_recursive_types_are_not_limited() {
+    #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+    struct Inner(u8);
+
+    #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+    struct Outer(Inner, Inner);
+
+    assert_eq!(
+        Outer::from_bytes_ctx(&[1, 2], ByteOrder::BigEndian, &mut MaxDepth::new(0)).unwrap(),
+        Outer(Inner(1), Inner(2))
+    );
+}
+
+#[test]
+fn deep_input_fails_without_overflowing_stack() {
+    let bytes = nested_nodes(1_000_000);
+    let result = thread::Builder::new()
+        .stack_size(1024 * 1024)
+        .spawn(move || Node::from_bytes_ctx(&bytes, ByteOrder::BigEndian, &mut MaxDepth::new(128)))
+        .unwrap()
+        .join()
+        .unwrap();
+    assert!(matches!(result, Err(Error::MaxDepthExceeded { max: 128 })));
+}
@@ -0,0 +1,115 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+#[protocol(bits = 4)]
+enum Opcode {
+    Query = 0,
+    InverseQuery = 1,
+    Status = 2,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct DnsHeader {
+    id: u16,
+    #[protocol(bits = 1)]
+    is_response: bool,
+    opcode: Opcode,
+    #[protocol(bits = 1)]
+    authoritative_answer: bool,
+    #[protocol(bits = 1)]
+    truncated: bool,
+    #[protocol(bits = 1)]
+    recursion_desired: bool,
+    #[protocol(bits = 1)]
+    recursion_available: bool,
+    #[protocol(bits = 3)]
+    reserved: u8,
+    #[protocol(bits = 4)]
+    response_code: u8,
+    question_count: u16,
+    answer_count: u16,
+    authority_count: u16,
+    additional_count: u16,
+}
+
+#[test]
+fn can_encode_decode_query_header() {
+    // A recursive query for one record, as sent by `dig`.
+    let raw = [
+        0x1a, 0x2b, // ID: 0x1a2b
+        0x01, 0x00, // Flags: standard query, recursion desired
+        0x00, 0x01, // Questions: 1
+        0x00, 0x00, // Answers: 0
+        0x00, 0x00, // Authority records: 0
+        0x00, 0x00, // Additional records: 0
+    ];
+    let parsed = DnsHeader {
+        id: 0x1a2b,
+        is_response: false,
+        opcode: Opcode::Query,
+        authoritative_answer: false,
+        truncated: false,
+        recursion_desired: true,
+        recursion_available: false,
+        reserved: 0,
+        response_code: 0,
+        question_count: 1,
+        answer_count: 0,
+        authority_count: 0,
+        additional_count: 0,
+    };
+    assert_eq!(
+        DnsHeader::from_bytes(&raw, ByteOrder::BigEndian).unwrap(),
+        parsed
+    );
+    assert_eq!(parsed.bytes(ByteOrder::BigEndian).unwrap(), raw);
+}
+
+#[test]
+fn can_encode_decode_response_header() {
+    // The answer to the query above, from a recursive resolver.
+    let raw = [
+        0x1a, 0x2b, // ID: 0x1a2b
+        0x81, 0x80, // Flags: standard response, recursion desired and available
+        0x00, 0x01, // Questions: 1
+        0x00, 0x01, // Answers: 1
+        0x00, 0x00, // Authority records: 0
+        0x00, 0x01, // Additional records: 1
+    ];
+    let parsed = DnsHeader {
+        id: 0x1a2b,
+        is_response: true,
+        opcode: Opcode::Query,
+        authoritative_answer: false,
+        truncated: false,
+        recursion_desired: true,
+        recursion_available: true,
+        reserved: 0,
+        response_code: 0,
+        question_count: 1,
+        answer_count: 1,
+        authority_count: 0,
+        additional_count: 1,
+    };
+    assert_eq!(
+        DnsHeader::from_bytes(&raw, ByteOrder::BigEndian).unwrap(),
+        parsed
+    );
+    assert_eq!(parsed.bytes(ByteOrder::BigEndian).unwrap(), raw);
+}
+
+#[test]
+fn decodes_name_error_status() {
+    // NXDOMAIN from an authoritative server.
+    let raw = [
+        0xbe, 0xef, 0x84, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+    ];
+    let parsed = DnsHeader::from_bytes(&raw, ByteOrder::BigEndian).unwrap();
+    assert!(parsed.is_response);
+    assert!(parsed.authoritative_answer);
+    assert!(!parsed.recursion_desired);
+    assert_eq!(parsed.response_code, 3);
+    assert_eq!(parsed.authority_count, 1);
+    assert_eq!(parsed.bytes(ByteOrder::BigEndian).unwrap(), raw);
+}
@@ -1,6 +1,7 @@
 use std::marker::PhantomData;
 
-use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite, TaggedRead};
+use bitstream_io::{BigEndian, BitReader};
 
 #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
 #[protocol(discriminant_type = "u8")]
@@ -50,6 +51,14 @@ pub struct BitFieldTaggedEnumContainer {
     e: Enum2,
 }
 
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+pub struct HeaderTaggedEnumContainer {
+    #[protocol(write_value = "::bin_proto::Discriminable::discriminant(&self.payload)")]
+    kind: u8,
+    #[protocol(tag = "kind")]
+    payload: Enum2,
+}
+
 #[test]
 fn read_enum_variant() {
     assert_eq!(
@@ -139,3 +148,695 @@ fn write_enum_variant_in_container_tagged_bitfield() {
         vec![64, 63, 224]
     );
 }
+
+#[test]
+fn header_tagged_enum_round_trips() {
+    for (value, bytes) in [
+        (
+            HeaderTaggedEnumContainer {
+                kind: 1,
+                payload: Enum2::Variant1(7),
+            },
+            vec![1, 7],
+        ),
+        (
+            HeaderTaggedEnumContainer {
+                kind: 2,
+                payload: Enum2::Variant2(0x1234),
+            },
+            vec![2, 0x12, 0x34],
+        ),
+    ] {
+        assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+        assert_eq!(
+            HeaderTaggedEnumContainer::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+}
+
+#[test]
+fn read_discriminant_then_full_enum() {
+    let bytes = [0b1000_0000, 0b0000_0001, 0b0000_0000];
+    let discriminant = Enum2::read_discriminant(
+        &mut BitReader::endian(bytes.as_slice(), BigEndian),
+        ByteOrder::BigEndian,
+        &mut (),
+    )
+    .unwrap();
+    assert_eq!(discriminant, 2);
+    assert_eq!(
+        Enum2::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        Enum2::Variant2(4)
+    );
+}
+
+#[test]
+fn read_discriminant_then_tagged_read() {
+    let bytes = [1, 0, 7, 2, 8, 9];
+    let mut reader = BitReader::endian(bytes.as_slice(), BigEndian);
+    let discriminant =
+        Enum::<u16>::read_discriminant(&mut reader, ByteOrder::BigEndian, &mut ()).unwrap();
+    assert_eq!(discriminant, 1);
+    assert_eq!(
+        <Enum<u16> as TaggedRead<_>>::read(
+            &mut reader,
+            ByteOrder::BigEndian,
+            &mut (),
+            discriminant
+        )
+        .unwrap(),
+        Enum::Variant1 {
+            a: 7,
+            len: 2,
+            arr: vec![8, 9],
+        }
+    );
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u8")]
+pub enum Video {
+    H264 = 0x1,
+    Vp9 = 0x2,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u8")]
+pub enum Audio {
+    #[protocol(discriminant = "0x1")]
+    Opus { bitrate: u16 },
+    #[protocol(discriminant = "0x3")]
+    Flac,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u8", discriminant_mask = "0xf0")]
+pub enum Media {
+    #[protocol(discriminant = "0x10", inner_tag)]
+    Video(Video),
+    #[protocol(discriminant = "0x20", inner_tag)]
+    Audio { audio: Audio },
+    #[protocol(discriminant = "0x30")]
+    Ping,
+}
+
+#[test]
+fn flattened_tag_round_trips() {
+    for (value, bytes) in [
+        (Media::Video(Video::H264), vec![0x11]),
+        (Media::Video(Video::Vp9), vec![0x12]),
+        (
+            Media::Audio {
+                audio: Audio::Opus { bitrate: 0x100 },
+            },
+            vec![0x21, 0x01, 0x00],
+        ),
+        (Media::Audio { audio: Audio::Flac }, vec![0x23]),
+        (Media::Ping, vec![0x30]),
+    ] {
+        assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+        assert_eq!(
+            Media::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+}
+
+#[test]
+fn flattened_tag_rejects_unknown_subtype() {
+    assert!(matches!(
+        Media::from_bytes(&[0x13], ByteOrder::BigEndian),
+        Err(bin_proto::Error::UnknownEnumDiscriminant { .. })
+    ));
+    assert!(matches!(
+        Media::from_bytes(&[0x40], ByteOrder::BigEndian),
+        Err(bin_proto::Error::UnknownEnumDiscriminant { .. })
+    ));
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u8")]
+pub enum Extended {
+    #[protocol(discriminant = "0x21")]
+    Wide,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u8", discriminant_mask = "0xf0")]
+pub enum Overlapping {
+    #[protocol(discriminant = "0x10", inner_tag)]
+    Extended(Extended),
+}
+
+#[test]
+fn flattened_tag_rejects_overflowing_subtype() {
+    assert!(matches!(
+        Overlapping::Extended(Extended::Wide).bytes(ByteOrder::BigEndian),
+        Err(bin_proto::Error::DiscriminantOverflow)
+    ));
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u16")]
+#[repr(u8)]
+pub enum NarrowRepr {
+    A = 1,
+    B = 0xff,
+}
+
+#[test]
+fn discriminant_type_overrides_repr() {
+    for (value, bytes) in [(NarrowRepr::A, [0x00, 0x01]), (NarrowRepr::B, [0x00, 0xff])] {
+        assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+        assert_eq!(
+            NarrowRepr::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+    assert!(matches!(
+        NarrowRepr::from_bytes(&[0x01, 0x01], ByteOrder::BigEndian),
+        Err(bin_proto::Error::UnknownEnumDiscriminant { .. })
+    ));
+}
+
+#[cfg(feature = "proptest")]
+mod proptests {
+    use super::*;
+    use bin_proto::testing;
+    use proptest::prelude::*;
+
+    impl Arbitrary for Enum<'static, u8> {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            prop_oneof![
+                (any::<u8>(), any::<Vec<u8>>()).prop_map(|(a, arr)| Enum::Variant1 {
+                    a,
+                    len: arr.len().try_into().unwrap_or(u8::MAX),
+                    arr: arr.into_iter().take(u8::MAX.into()).collect(),
+                }),
+                (any::<u32>(), any::<bool>()).prop_map(|(a, b)| Enum::Variant2(a, b, PhantomData)),
+            ]
+            .boxed()
+        }
+    }
+
+    impl Arbitrary for Enum2 {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            prop_oneof![
+                any::<u8>().prop_map(Enum2::Variant1),
+                any::<u16>().prop_map(Enum2::Variant2),
+            ]
+            .boxed()
+        }
+    }
+
+    impl Arbitrary for HeaderTaggedEnumContainer {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            any::<Enum2>()
+                .prop_map(|payload| Self {
+                    kind: bin_proto::Discriminable::discriminant(&payload),
+                    payload,
+                })
+                .boxed()
+        }
+    }
+
+    #[test]
+    fn enum_round_trips() {
+        testing::roundtrip::<Enum<'static, u8>>(ByteOrder::BigEndian);
+    }
+
+    #[test]
+    fn bitfield_enum_round_trips() {
+        testing::roundtrip::<Enum2>(ByteOrder::BigEndian);
+    }
+
+    #[test]
+    fn header_tagged_enum_round_trips() {
+        testing::roundtrip::<HeaderTaggedEnumContainer>(ByteOrder::LittleEndian);
+        testing::reparse::<HeaderTaggedEnumContainer>(ByteOrder::LittleEndian);
+    }
+}
+
+const TYPE_DATA: u8 = 0x10;
+
+struct MessageType;
+
+impl MessageType {
+    const ACK: u8 = 0x40 | 3;
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+enum ConstDiscriminants {
+    #[protocol(discriminant = "TYPE_DATA")]
+    Data(u8),
+    #[protocol(discriminant = "MessageType::ACK")]
+    Ack,
+    #[protocol(discriminant = "TYPE_DATA * 2")]
+    Close,
+    #[protocol(discriminant = "1")]
+    Ping,
+}
+
+#[test]
+fn const_discriminants_round_trip() {
+    for (value, bytes) in [
+        (ConstDiscriminants::Data(7), vec![0x10, 7]),
+        (ConstDiscriminants::Ack, vec![0x43]),
+        (ConstDiscriminants::Close, vec![0x20]),
+        (ConstDiscriminants::Ping, vec![0x01]),
+    ] {
+        assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+        assert_eq!(
+            ConstDiscriminants::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+}
+
+#[test]
+fn const_discriminants_reject_unknown() {
+    for tag in [0x03, 0x40, 0x11] {
+        assert!(matches!(
+            ConstDiscriminants::from_bytes(&[tag], ByteOrder::BigEndian),
+            Err(bin_proto::Error::UnknownEnumDiscriminant { .. })
+        ));
+    }
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+pub enum Never {}
+
+#[test]
+fn empty_enum_never_reads() {
+    for tag in [0x00, 0xff] {
+        assert!(matches!(
+            Never::from_bytes(&[tag], ByteOrder::BigEndian),
+            Err(bin_proto::Error::UnknownEnumDiscriminant { .. })
+        ));
+    }
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+pub enum OnlyUnit {
+    #[protocol(discriminant = "7")]
+    Only,
+}
+
+#[test]
+fn single_unit_variant_round_trips() {
+    assert_eq!(OnlyUnit::Only.bytes(ByteOrder::BigEndian).unwrap(), [7]);
+    assert_eq!(
+        OnlyUnit::from_bytes(&[7], ByteOrder::BigEndian).unwrap(),
+        OnlyUnit::Only
+    );
+    assert!(matches!(
+        OnlyUnit::from_bytes(&[0], ByteOrder::BigEndian),
+        Err(bin_proto::Error::UnknownEnumDiscriminant { .. })
+    ));
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Clone, Copy)]
+#[protocol(discriminant_type = "u8")]
+pub enum Color {
+    Red = 1,
+    Green = 2,
+    Blue = 3,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+pub enum Shape {
+    #[protocol(discriminant = "1")]
+    Dot,
+    #[protocol(discriminant = "2")]
+    Circle(u16),
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+pub struct Pixel {
+    color: Color,
+    shade: u8,
+}
+
+#[test]
+fn fieldless_enum_is_its_discriminant() {
+    for (color, byte) in [(Color::Red, 1), (Color::Green, 2), (Color::Blue, 3)] {
+        assert_eq!(color.bytes(ByteOrder::BigEndian).unwrap(), [byte]);
+        assert_eq!(color.bit_len(ByteOrder::BigEndian).unwrap(), 8);
+        assert_eq!(
+            Color::from_bytes(&[byte], ByteOrder::BigEndian).unwrap(),
+            color
+        );
+    }
+    assert_eq!(
+        Color::from_bytes(&[4], ByteOrder::BigEndian)
+            .unwrap_err()
+            .to_string(),
+        "Unknown discriminant 4 for enum Color (expected 1, 2, 3)"
+    );
+}
+
+#[test]
+fn unknown_discriminant_names_enum_and_variants() {
+    assert_eq!(
+        Shape::from_bytes(&[7], ByteOrder::BigEndian)
+            .unwrap_err()
+            .to_string(),
+        "Unknown discriminant 7 for enum Shape (expected 1, 2)"
+    );
+    assert!(matches!(
+        Shape::from_bytes(&[7], ByteOrder::BigEndian),
+        Err(bin_proto::Error::UnknownEnumDiscriminant {
+            enum_name: "Shape",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn unknown_discriminant_caps_expected_list() {
+    #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+    #[protocol(discriminant_type = "u8")]
+    enum Wide {
+        V0 = 0,
+        V1 = 1,
+        V2 = 2,
+        V3 = 3,
+        V4 = 4,
+        V5 = 5,
+        V6 = 6,
+        V7 = 7,
+        V8 = 8,
+        V9 = 9,
+        V10 = 10,
+        V11 = 11,
+        V12 = 12,
+        V13 = 13,
+        V14 = 14,
+        V15 = 15,
+        V16 = 16,
+        V17 = 17,
+    }
+    match Wide::from_bytes(&[0xff], ByteOrder::BigEndian) {
+        Err(bin_proto::Error::UnknownEnumDiscriminant { expected, .. }) => {
+            assert_eq!(expected.len(), bin_proto::MAX_EXPECTED_DISCRIMINANTS + 1);
+            assert_eq!(expected.last().unwrap(), "...");
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn fieldless_enum_as_field() {
+    let pixel = Pixel {
+        color: Color::Blue,
+        shade: 0x80,
+    };
+    let bytes = pixel.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [3, 0x80]);
+    assert_eq!(
+        Pixel::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        pixel
+    );
+}
+
+#[test]
+fn payload_enum_writes_fields_after_discriminant() {
+    assert_eq!(Shape::Dot.bytes(ByteOrder::BigEndian).unwrap(), [1]);
+    let circle = Shape::Circle(0x0102);
+    let bytes = circle.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [2, 1, 2]);
+    assert_eq!(
+        Shape::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        circle
+    );
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+#[protocol(ctx = "()")]
+pub enum MaybeUninhabited<E: ProtocolRead + ProtocolWrite> {
+    #[protocol(discriminant = "1")]
+    Value(u16),
+    #[protocol(discriminant = "2")]
+    Unsupported(E),
+}
+
+#[test]
+fn uninhabited_variant_derives() {
+    type Value = MaybeUninhabited<std::convert::Infallible>;
+    let value = Value::Value(0x0102);
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [1, 0x01, 0x02]);
+    assert_eq!(
+        Value::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+    assert!(matches!(
+        Value::from_bytes(&[2, 0x00], ByteOrder::BigEndian),
+        Err(bin_proto::Error::IO(_))
+    ));
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8", discriminant_base = 0x10)]
+pub enum Command {
+    Reset,
+    Set(u8),
+    #[protocol(discriminant = "0x20")]
+    Clear,
+    Query,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "i8", discriminant_base = -2)]
+pub enum Offset {
+    Back2,
+    Back1,
+    Zero,
+}
+
+#[test]
+fn discriminant_base_numbers_variants() {
+    assert_eq!(Command::Reset.bytes(ByteOrder::BigEndian).unwrap(), [0x10]);
+    assert_eq!(
+        Command::Set(7).bytes(ByteOrder::BigEndian).unwrap(),
+        [0x11, 7]
+    );
+    assert_eq!(Command::Clear.bytes(ByteOrder::BigEndian).unwrap(), [0x20]);
+    assert_eq!(Command::Query.bytes(ByteOrder::BigEndian).unwrap(), [0x21]);
+    assert_eq!(
+        Command::from_bytes(&[0x11, 9], ByteOrder::BigEndian).unwrap(),
+        Command::Set(9)
+    );
+    assert_eq!(
+        Offset::from_bytes(&[0xff], ByteOrder::BigEndian).unwrap(),
+        Offset::Back1
+    );
+    assert_eq!(Offset::Zero.bytes(ByteOrder::BigEndian).unwrap(), [0]);
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "String")]
+pub enum RecordBody {
+    #[protocol(discriminant = "\"TX\"")]
+    Text(u8),
+    #[protocol(discriminant = "\"NM\"")]
+    Number(u16),
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+pub struct Record {
+    #[protocol(write_value = "record_code(&self.body)")]
+    pub code: [u8; 2],
+    #[protocol(tag = "std::str::from_utf8(&code).map_err(|_| bin_proto::Error::TagConvert)?")]
+    pub body: RecordBody,
+}
+
+fn record_code(body: &RecordBody) -> [u8; 2] {
+    let mut code = [0; 2];
+    code.copy_from_slice(bin_proto::Discriminable::discriminant(body).as_bytes());
+    code
+}
+
+#[test]
+fn string_discriminant_selects_variant() {
+    for (record, bytes) in [
+        (
+            Record {
+                code: *b"TX",
+                body: RecordBody::Text(7),
+            },
+            b"TX\x07".to_vec(),
+        ),
+        (
+            Record {
+                code: *b"NM",
+                body: RecordBody::Number(0x0102),
+            },
+            b"NM\x01\x02".to_vec(),
+        ),
+    ] {
+        assert_eq!(record.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+        assert_eq!(
+            Record::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            record
+        );
+    }
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "String")]
+pub enum Verb {
+    Get,
+    #[protocol(discriminant = "\"PUT\"")]
+    Put(u8),
+}
+
+#[test]
+fn string_discriminant_defaults_to_variant_name() {
+    assert_eq!(bin_proto::Discriminable::discriminant(&Verb::Get), "Get");
+    assert_eq!(bin_proto::Discriminable::discriminant(&Verb::Put(1)), "PUT");
+    let verb: Verb = TaggedRead::read(
+        &mut BitReader::endian([].as_slice(), BigEndian),
+        ByteOrder::BigEndian,
+        &mut (),
+        "Get",
+    )
+    .unwrap();
+    assert_eq!(verb, Verb::Get);
+}
+
+#[test]
+fn string_discriminant_rejects_unknown_tag() {
+    assert!(matches!(
+        Record::from_bytes(b"XX\x00", ByteOrder::BigEndian),
+        Err(bin_proto::Error::UnknownEnumDiscriminant { .. })
+    ));
+    let body: RecordBody = TaggedRead::read(
+        &mut BitReader::endian([3u8].as_slice(), BigEndian),
+        ByteOrder::BigEndian,
+        &mut (),
+        String::from("TX"),
+    )
+    .unwrap();
+    assert_eq!(body, RecordBody::Text(3));
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+pub struct MessageHeader {
+    pub version: u8,
+    pub kind: MessageKind,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+pub struct MessageKind {
+    pub flags: u8,
+    pub msg_type: u8,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+pub struct HeaderTagged {
+    pub header: MessageHeader,
+    pub sequence: u16,
+    #[protocol(tag = "header.kind.msg_type")]
+    pub body: Shape,
+}
+
+#[test]
+fn tag_from_nested_earlier_field() {
+    let message = HeaderTagged {
+        header: MessageHeader {
+            version: 1,
+            kind: MessageKind {
+                flags: 0,
+                msg_type: 2,
+            },
+        },
+        sequence: 9,
+        body: Shape::Circle(0x0304),
+    };
+    let bytes = message.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [1, 0, 2, 0, 9, 3, 4]);
+    assert_eq!(
+        HeaderTagged::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        message
+    );
+
+    let mut replaced = HeaderTagged::from_bytes(&[1, 0, 1, 0, 8], ByteOrder::BigEndian).unwrap();
+    assert_eq!(replaced.body, Shape::Dot);
+    replaced
+        .read_replace(
+            &mut BitReader::endian(bytes.as_slice(), BigEndian),
+            ByteOrder::BigEndian,
+            &mut (),
+        )
+        .unwrap();
+    assert_eq!(replaced, message);
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8", discriminant_base = 4)]
+#[protocol(ctx = "()")]
+pub enum Dispatched<T: ProtocolRead + ProtocolWrite> {
+    Implicit,
+    #[protocol(discriminant = "0x20")]
+    Renamed(T),
+    Following {
+        value: T,
+    },
+    #[protocol(discriminant = "message_codes::COMPUTED + 1")]
+    Computed,
+}
+
+mod message_codes {
+    pub const COMPUTED: u8 = 0x40;
+}
+
+#[test]
+fn discriminable_reports_discriminant_and_name() {
+    use bin_proto::Discriminable;
+
+    let values: [(Dispatched<u16>, u8, &str); 4] = [
+        (Dispatched::Implicit, 4, "Implicit"),
+        (Dispatched::Renamed(1), 0x20, "Renamed"),
+        (Dispatched::Following { value: 2 }, 0x21, "Following"),
+        (Dispatched::Computed, 0x41, "Computed"),
+    ];
+    for (value, discriminant, name) in values {
+        assert_eq!(value.discriminant(), discriminant);
+        assert_eq!(value.variant_name(), name);
+    }
+    assert_eq!(RecordBody::Number(0).discriminant(), "NM");
+    assert_eq!(RecordBody::Number(0).variant_name(), "Number");
+}
+
+#[test]
+fn variant_name_defaults_to_type_name() {
+    use bin_proto::Discriminable;
+
+    struct Manual;
+
+    impl Discriminable for Manual {
+        type Discriminant = u8;
+
+        fn discriminant(&self) -> u8 {
+            0
+        }
+    }
+
+    assert!(Manual.variant_name().ends_with("Manual"));
+}
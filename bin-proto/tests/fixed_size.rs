@@ -0,0 +1,115 @@
+use std::marker::PhantomData;
+
+use bin_proto::{ByteOrder, FixedSize, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(ProtocolRead, ProtocolWrite)]
+struct Header {
+    #[protocol(bits = 4)]
+    version: u8,
+    #[protocol(bits = 4)]
+    kind: u8,
+    #[protocol(bits = 3)]
+    flags: u8,
+    #[protocol(bits = 13)]
+    offset: u16,
+}
+
+const _: () = assert!(Header::SIZE_BITS == 24);
+
+#[derive(ProtocolRead, ProtocolWrite)]
+struct Message {
+    header: Header,
+    #[protocol(pad_bits = 8)]
+    payload: [u16; 4],
+}
+
+#[derive(ProtocolRead, ProtocolWrite)]
+#[protocol(ctx = "()")]
+struct Generic<T: ProtocolRead + ProtocolWrite>(T, bool);
+
+#[derive(ProtocolRead, ProtocolWrite)]
+struct Variable {
+    len: u8,
+    #[protocol(tag = "len as usize")]
+    data: Vec<u8>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite)]
+struct Unbounded(u8, #[protocol(flexible_array_member)] Vec<u8>);
+
+#[derive(ProtocolRead, ProtocolWrite)]
+#[protocol(discriminant_type = "u8")]
+enum SameSize {
+    #[protocol(discriminant = "1")]
+    A(u16, [u8; 2]),
+    #[protocol(discriminant = "2")]
+    B(u16, [u8; 2]),
+}
+
+#[derive(ProtocolRead, ProtocolWrite)]
+#[protocol(discriminant_type = "u8")]
+#[protocol(bits = 4)]
+enum Flag {
+    #[protocol(discriminant = "1")]
+    On,
+    #[protocol(discriminant = "2")]
+    Off,
+}
+
+#[derive(ProtocolRead, ProtocolWrite)]
+#[protocol(discriminant_type = "u8")]
+enum DifferentSize {
+    #[protocol(discriminant = "1")]
+    A(u8),
+    #[protocol(discriminant = "2")]
+    B(u16),
+}
+
+trait NotFixedSize {
+    const IS_FIXED_SIZE: bool = false;
+}
+
+impl<T> NotFixedSize for IsFixedSize<T> {}
+
+struct IsFixedSize<T>(PhantomData<T>);
+
+impl<T: FixedSize> IsFixedSize<T> {
+    const IS_FIXED_SIZE: bool = true;
+}
+
+const _: () = {
+    assert!(IsFixedSize::<Header>::IS_FIXED_SIZE);
+    assert!(!IsFixedSize::<Variable>::IS_FIXED_SIZE);
+    assert!(!IsFixedSize::<Unbounded>::IS_FIXED_SIZE);
+    assert!(!IsFixedSize::<Generic<Variable>>::IS_FIXED_SIZE);
+    assert!(!IsFixedSize::<DifferentSize>::IS_FIXED_SIZE);
+};
+
+#[test]
+fn size_matches_encoding() {
+    let message = Message {
+        header: Header {
+            version: 4,
+            kind: 5,
+            flags: 1,
+            offset: 2,
+        },
+        payload: [1, 2, 3, 4],
+    };
+    assert_eq!(Message::SIZE_BITS, 24 + 8 + 64);
+    assert_eq!(
+        message.bytes(ByteOrder::BigEndian).unwrap().len() * 8,
+        Message::SIZE_BITS
+    );
+}
+
+#[test]
+fn generic_size() {
+    assert_eq!(<Generic<u32>>::SIZE_BITS, 40);
+}
+
+#[test]
+fn enum_size() {
+    assert_eq!(SameSize::SIZE_BITS, 40);
+    assert_eq!(Flag::SIZE_BITS, 4);
+}
@@ -1,4 +1,4 @@
-use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use bin_proto::{ByteOrder, LossyString, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
 
 #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
 struct WithFlexibleArrayMember(#[protocol(flexible_array_member)] Vec<u8>);
@@ -20,3 +20,45 @@ fn writes_flexible_array_member() {
         vec![1, 2, 3]
     );
 }
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct BoxedSlice {
+    kind: u8,
+    #[protocol(flexible_array_member)]
+    data: Box<[u16]>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct BoxedString {
+    kind: u8,
+    #[protocol(flexible_array_member)]
+    name: Box<LossyString>,
+}
+
+#[test]
+fn boxed_slice_round_trips() {
+    let value = BoxedSlice {
+        kind: 1,
+        data: vec![0x0203, 0x0405].into_boxed_slice(),
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [1, 2, 3, 4, 5]);
+    assert_eq!(
+        BoxedSlice::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn boxed_flexible_array_member_round_trips() {
+    let value = BoxedString {
+        kind: 2,
+        name: Box::new(LossyString("abc".to_owned())),
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [2, b'a', b'b', b'c']);
+    assert_eq!(
+        BoxedString::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
@@ -0,0 +1,140 @@
+use bin_proto::{ByteOrder, Error, NanBoxed, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Sample {
+    #[protocol(float_policy = "reject_nan")]
+    no_nan: f64,
+    #[protocol(float_policy = "reject_nonfinite")]
+    finite: f32,
+    unchecked: f32,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Tuple(#[protocol(float_policy = "reject_nan")] f32);
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+enum Reading {
+    #[protocol(discriminant = "1")]
+    Value {
+        #[protocol(float_policy = "reject_nonfinite")]
+        value: f32,
+    },
+}
+
+static READINGS: AtomicUsize = AtomicUsize::new(0);
+
+fn next_reading() -> f32 {
+    READINGS.fetch_add(1, Ordering::SeqCst);
+    1.5
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Computed {
+    #[protocol(write_value = "next_reading()", float_policy = "reject_nonfinite")]
+    reading: f32,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Sensor {
+    temperature: NanBoxed<f32, 0x7fc0_0bad>,
+}
+
+fn sample_bytes(no_nan: f64, finite: f32, unchecked: f32) -> Vec<u8> {
+    let mut bytes = no_nan.to_be_bytes().to_vec();
+    bytes.extend(finite.to_be_bytes());
+    bytes.extend(unchecked.to_be_bytes());
+    bytes
+}
+
+#[test]
+fn accepts_allowed_values() {
+    let bytes = sample_bytes(f64::INFINITY, -0.0, f32::NAN);
+    let sample = Sample::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+    assert_eq!(sample.no_nan, f64::INFINITY);
+    assert!(sample.unchecked.is_nan());
+    assert_eq!(sample.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+}
+
+#[test]
+fn preserves_unchecked_nan_bits() {
+    let nan = f32::from_bits(0x7f80_0001);
+    let bytes = sample_bytes(1.0, 2.0, nan);
+    let sample = Sample::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+    assert_eq!(sample.unchecked.to_bits(), 0x7f80_0001);
+    assert_eq!(sample.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+}
+
+#[test]
+fn rejects_on_read() {
+    assert!(matches!(
+        Sample::from_bytes(&sample_bytes(f64::NAN, 0.0, 0.0), ByteOrder::BigEndian),
+        Err(Error::NonFiniteFloat { field: "no_nan" })
+    ));
+    assert!(matches!(
+        Sample::from_bytes(
+            &sample_bytes(0.0, f32::NEG_INFINITY, 0.0),
+            ByteOrder::BigEndian
+        ),
+        Err(Error::NonFiniteFloat { field: "finite" })
+    ));
+    assert!(matches!(
+        Tuple::from_bytes(&f32::NAN.to_le_bytes(), ByteOrder::LittleEndian),
+        Err(Error::NonFiniteFloat { field: "0" })
+    ));
+    let mut bytes = vec![1];
+    bytes.extend(f32::INFINITY.to_be_bytes());
+    assert!(matches!(
+        Reading::from_bytes(&bytes, ByteOrder::BigEndian),
+        Err(Error::NonFiniteFloat { field: "value" })
+    ));
+}
+
+#[test]
+fn rejects_on_write() {
+    let sample = Sample {
+        no_nan: 0.0,
+        finite: f32::NAN,
+        unchecked: 0.0,
+    };
+    assert!(matches!(
+        sample.bytes(ByteOrder::BigEndian),
+        Err(Error::NonFiniteFloat { field: "finite" })
+    ));
+    assert!(matches!(
+        Tuple(f32::NAN).bytes(ByteOrder::BigEndian),
+        Err(Error::NonFiniteFloat { field: "0" })
+    ));
+    assert!(matches!(
+        Reading::Value { value: f32::NAN }.bytes(ByteOrder::BigEndian),
+        Err(Error::NonFiniteFloat { field: "value" })
+    ));
+}
+
+#[test]
+fn write_value_is_evaluated_once() {
+    let bytes = Computed { reading: 0.0 }
+        .bytes(ByteOrder::BigEndian)
+        .unwrap();
+    assert_eq!(bytes, 1.5f32.to_be_bytes());
+    assert_eq!(READINGS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn nan_boxed_field() {
+    let bytes = [0x7f, 0xc0, 0x0b, 0xad];
+    let sensor = Sensor::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+    assert_eq!(sensor.temperature, NanBoxed(None));
+    assert_eq!(sensor.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+
+    let sensor = Sensor {
+        temperature: NanBoxed(Some(21.5)),
+    };
+    let bytes = sensor.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, 21.5f32.to_be_bytes());
+    assert_eq!(
+        Sensor::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        sensor
+    );
+}
@@ -0,0 +1,133 @@
+use bin_proto::{
+    framed::{self, FrameConfig, PrefixWidth},
+    ByteOrder, Error, ProtocolRead, ProtocolWrite,
+};
+use std::io;
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Message {
+    id: u16,
+    #[protocol(flexible_array_member)]
+    payload: Vec<u8>,
+}
+
+/// Returns at most one byte per read.
+struct Trickle<'a>(&'a [u8]);
+
+impl io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.0.len()).min(1);
+        buf[..n].copy_from_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        Ok(n)
+    }
+}
+
+fn message() -> Message {
+    Message {
+        id: 0x0102,
+        payload: vec![3, 4, 5],
+    }
+}
+
+#[test]
+fn frames_round_trip() {
+    for (config, prefix) in [
+        (FrameConfig::new(PrefixWidth::U8), vec![5]),
+        (FrameConfig::new(PrefixWidth::U16), vec![0, 5]),
+        (
+            FrameConfig::new(PrefixWidth::U16).byte_order(ByteOrder::LittleEndian),
+            vec![5, 0],
+        ),
+        (FrameConfig::default(), vec![0, 0, 0, 5]),
+        (FrameConfig::new(PrefixWidth::VarInt), vec![5]),
+    ] {
+        let mut stream = Vec::new();
+        framed::write_frame(&mut stream, &message(), ByteOrder::BigEndian, &config).unwrap();
+        framed::write_frame(&mut stream, &message(), ByteOrder::BigEndian, &config).unwrap();
+        let frame = [prefix, vec![1, 2, 3, 4, 5]].concat();
+        assert_eq!(stream, [frame.clone(), frame].concat());
+
+        let mut reader = Trickle(&stream);
+        for _ in 0..2 {
+            assert_eq!(
+                framed::read_frame::<Message>(&mut reader, ByteOrder::BigEndian, &config).unwrap(),
+                message()
+            );
+        }
+        assert!(reader.0.is_empty());
+    }
+}
+
+#[test]
+fn frame_prefix_includes_itself() {
+    let config = FrameConfig::new(PrefixWidth::U16).includes_prefix();
+    let mut stream = Vec::new();
+    framed::write_frame(&mut stream, &message(), ByteOrder::BigEndian, &config).unwrap();
+    assert_eq!(stream, [0, 7, 1, 2, 3, 4, 5]);
+    assert_eq!(
+        framed::read_frame::<Message>(&mut stream.as_slice(), ByteOrder::BigEndian, &config)
+            .unwrap(),
+        message()
+    );
+}
+
+#[test]
+fn varint_prefix_includes_itself_across_widths() {
+    let config = FrameConfig::new(PrefixWidth::VarInt).includes_prefix();
+    let value = Message {
+        id: 0,
+        payload: vec![0; 125],
+    };
+    let mut stream = Vec::new();
+    framed::write_frame(&mut stream, &value, ByteOrder::BigEndian, &config).unwrap();
+    assert_eq!(stream[..2], [129, 1]);
+    assert_eq!(
+        framed::read_frame::<Message>(&mut stream.as_slice(), ByteOrder::BigEndian, &config)
+            .unwrap(),
+        value
+    );
+}
+
+#[test]
+fn frame_too_large() {
+    let config = FrameConfig::new(PrefixWidth::U16).max_frame_size(4);
+    assert!(matches!(
+        framed::write_frame(&mut Vec::new(), &message(), ByteOrder::BigEndian, &config),
+        Err(Error::FrameTooLarge { size: 5, max: 4 })
+    ));
+    assert!(matches!(
+        framed::read_frame::<Message>(&mut [0xff, 0xff].as_slice(), ByteOrder::BigEndian, &config),
+        Err(Error::FrameTooLarge {
+            size: 0xffff,
+            max: 4
+        })
+    ));
+}
+
+#[test]
+fn default_max_frame_size() {
+    assert!(matches!(
+        framed::read_frame::<Message>(
+            &mut [0x01, 0x00, 0x00, 0x01].as_slice(),
+            ByteOrder::BigEndian,
+            &FrameConfig::default()
+        ),
+        Err(Error::FrameTooLarge {
+            size: 0x0100_0001,
+            max: framed::DEFAULT_MAX_FRAME_SIZE
+        })
+    ));
+}
+
+#[test]
+fn truncated_frame() {
+    assert!(matches!(
+        framed::read_frame::<Message>(
+            &mut [0, 5, 1, 2].as_slice(),
+            ByteOrder::BigEndian,
+            &FrameConfig::new(PrefixWidth::U16)
+        ),
+        Err(Error::IO(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+    ));
+}
@@ -0,0 +1,65 @@
+use std::io::{self, Read};
+
+use bin_proto::{ByteOrder, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(header(fields = "version, msg_type, length"))]
+pub struct Packet {
+    #[protocol(bits = 4)]
+    pub version: u8,
+    #[protocol(bits = 4)]
+    pub msg_type: u8,
+    pub length: u16,
+    #[protocol(tag = "length as usize")]
+    pub payload: Vec<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(ctx = "u8")]
+#[protocol(header(fields = "kind"))]
+struct WithCtx {
+    kind: u8,
+    value: u32,
+}
+
+#[test]
+fn reads_only_header_fields() {
+    let mut bytes = vec![0x27, 0x00, 0x40];
+    bytes.extend(0..0x40);
+    let header = Packet::read_header(&bytes, ByteOrder::BigEndian, &mut ()).unwrap();
+    assert_eq!(header.version, 2);
+    assert_eq!(header.msg_type, 7);
+    assert_eq!(header.length, 0x40);
+}
+
+#[test]
+fn header_consumes_only_its_bytes() {
+    let mut bytes = vec![0x27, 0x00, 0x40];
+    bytes.extend(0..0x40);
+    let mut cursor = io::Cursor::new(bytes.as_slice());
+    let header = PacketHeader::read(
+        &mut *bin_proto::from_read(&mut cursor, ByteOrder::BigEndian),
+        ByteOrder::BigEndian,
+        &mut (),
+    )
+    .unwrap();
+    assert_eq!(header.length, 0x40);
+    assert_eq!(cursor.position(), 3);
+    let mut rest = Vec::new();
+    cursor.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, (0..0x40).collect::<Vec<u8>>());
+}
+
+#[test]
+fn header_ignores_truncated_body() {
+    let header =
+        Packet::read_header(&[0x13, 0xff, 0xff, 1], ByteOrder::LittleEndian, &mut ()).unwrap();
+    assert_eq!((header.version, header.msg_type), (3, 1));
+    assert_eq!(header.length, 0xffff);
+}
+
+#[test]
+fn header_uses_struct_ctx() {
+    let header = WithCtx::read_header(&[5, 0, 0, 0], ByteOrder::BigEndian, &mut 0).unwrap();
+    assert_eq!(header.kind, 5);
+}
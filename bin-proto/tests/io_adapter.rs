@@ -0,0 +1,108 @@
+use std::{
+    fs::File,
+    io::{self, Seek},
+};
+
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Header {
+    #[protocol(bits = 3)]
+    version: u8,
+    #[protocol(bits = 5)]
+    flags: u8,
+    length: u16,
+    #[protocol(tag = "length as usize")]
+    payload: Vec<u8>,
+}
+
+fn header() -> Header {
+    Header {
+        version: 2,
+        flags: 0b10101,
+        length: 3,
+        payload: vec![7, 8, 9],
+    }
+}
+
+#[test]
+fn reads_from_cursor() {
+    for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+        let bytes = header().bytes(byte_order).unwrap();
+        let mut reader = bin_proto::from_read(io::Cursor::new(bytes), byte_order);
+        assert_eq!(
+            Header::read(&mut *reader, byte_order, &mut ()).unwrap(),
+            header()
+        );
+    }
+}
+
+#[test]
+fn writes_to_and_reads_from_file() {
+    let path = std::env::temp_dir().join(format!("bin-proto-io-adapter-{}", std::process::id()));
+    let mut file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap();
+
+    let mut writer = bin_proto::to_write(&mut file, ByteOrder::LittleEndian);
+    header()
+        .write(&mut *writer, ByteOrder::LittleEndian, &mut ())
+        .unwrap();
+    writer.byte_align().unwrap();
+    drop(writer);
+
+    file.rewind().unwrap();
+    let mut reader = bin_proto::from_read(&mut file, ByteOrder::LittleEndian);
+    let read = Header::read(&mut *reader, ByteOrder::LittleEndian, &mut ());
+    drop(reader);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read.unwrap(), header());
+}
+
+/// Counts the calls to `read`.
+struct CountingRead<R> {
+    read: R,
+    calls: usize,
+}
+
+impl<R: io::Read> io::Read for CountingRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.calls += 1;
+        self.read.read(buf)
+    }
+}
+
+#[test]
+fn buffered_reads_match_unbuffered() {
+    for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+        let mut bytes = Vec::new();
+        for _ in 0..100 {
+            bytes.extend(header().bytes(byte_order).unwrap());
+        }
+
+        let mut unbuffered = CountingRead {
+            read: bytes.as_slice(),
+            calls: 0,
+        };
+        let mut buffered = CountingRead {
+            read: bytes.as_slice(),
+            calls: 0,
+        };
+        {
+            let mut unbuffered = bin_proto::from_read(&mut unbuffered, byte_order);
+            let mut buffered = bin_proto::from_read_buffered(&mut buffered, byte_order);
+            for _ in 0..100 {
+                assert_eq!(
+                    Header::read(&mut *buffered, byte_order, &mut ()).unwrap(),
+                    Header::read(&mut *unbuffered, byte_order, &mut ()).unwrap(),
+                );
+            }
+        }
+        assert!(unbuffered.calls >= 100);
+        assert!(buffered.calls <= 2);
+    }
+}
@@ -39,6 +39,8 @@ struct IPv4 {
 }
 
 #[test]
+// Writes out zero bits to mirror the layout of the header.
+#[allow(clippy::identity_op)]
 fn can_encode_decode_ipv4() {
     let raw = [
         0b0100_0000 // Version: 4
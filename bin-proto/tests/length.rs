@@ -0,0 +1,85 @@
+use bin_proto::{ByteOrder, MessageStream, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Packet {
+    kind: u8,
+    #[protocol(length = "remaining.saturating_sub(4)")]
+    payload: Vec<u8>,
+    crc: u32,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Records {
+    size: u8,
+    #[protocol(length = "remaining / size as usize")]
+    records: Vec<u16>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Chunk {
+    len: u8,
+    #[protocol(length = "len as usize")]
+    data: Vec<u8>,
+}
+
+#[test]
+fn payload_sized_by_remaining_minus_trailer() {
+    let packet = Packet {
+        kind: 7,
+        payload: vec![1, 2, 3],
+        crc: 0xdead_beef,
+    };
+    let bytes = [7, 1, 2, 3, 0xde, 0xad, 0xbe, 0xef];
+    assert_eq!(packet.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Packet::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        packet
+    );
+}
+
+#[test]
+fn empty_payload() {
+    assert_eq!(
+        Packet::from_bytes(&[7, 0xde, 0xad, 0xbe, 0xef], ByteOrder::BigEndian).unwrap(),
+        Packet {
+            kind: 7,
+            payload: vec![],
+            crc: 0xdead_beef,
+        }
+    );
+}
+
+#[test]
+fn missing_trailer() {
+    assert!(Packet::from_bytes(&[7, 0xde, 0xad], ByteOrder::BigEndian).is_err());
+}
+
+#[test]
+fn length_refers_to_preceding_field() {
+    assert_eq!(
+        Records::from_bytes(&[2, 0, 1, 0, 2], ByteOrder::BigEndian).unwrap(),
+        Records {
+            size: 2,
+            records: vec![1, 2],
+        }
+    );
+}
+
+#[test]
+fn length_without_remaining_reads_only_the_field() {
+    let bytes = [2, 1, 2, 1, 3];
+    let stream = MessageStream::<Chunk>::new(&bytes, ByteOrder::BigEndian);
+    assert_eq!(
+        stream.map(Result::unwrap).collect::<Vec<_>>(),
+        [
+            Chunk {
+                len: 2,
+                data: vec![1, 2],
+            },
+            Chunk {
+                len: 1,
+                data: vec![3],
+            },
+        ]
+    );
+}
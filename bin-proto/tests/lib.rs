@@ -1,14 +1,100 @@
 #![cfg(test)]
 
+#[cfg(test)]
+mod align_before;
+#[cfg(test)]
+mod allocations;
+#[cfg(test)]
+mod bitfield_packing;
+#[cfg(test)]
+mod bits;
+#[cfg(test)]
+mod builder;
+#[cfg(test)]
+mod byte_order;
+#[cfg(test)]
+mod bytes;
+#[cfg(test)]
+mod canonical;
+#[cfg(test)]
+mod checksum;
+#[cfg(test)]
+mod cobs;
+#[cfg(test)]
+mod condition;
+#[cfg(test)]
+mod count;
+#[cfg(test)]
+mod crc_context;
 #[cfg(test)]
 mod ctx;
 #[cfg(test)]
+mod depth;
+#[cfg(test)]
+mod dns;
+#[cfg(test)]
 mod enums;
 #[cfg(test)]
+mod fixed_size;
+#[cfg(test)]
 mod flexible_array_member;
 #[cfg(test)]
+mod float_policy;
+#[cfg(test)]
+mod framed;
+#[cfg(test)]
+mod header;
+#[cfg(test)]
+mod io_adapter;
+#[cfg(test)]
 mod ipv4;
 #[cfg(test)]
+mod length;
+#[cfg(test)]
+mod limited_read;
+#[cfg(test)]
+mod malformed_input;
+#[cfg(test)]
+mod maps;
+#[cfg(test)]
+mod markers;
+#[cfg(test)]
+mod message_stream;
+#[cfg(test)]
+mod offsets;
+#[cfg(test)]
+mod pad_bits;
+#[cfg(test)]
+mod partial_read;
+#[cfg(test)]
+mod png;
+#[cfg(test)]
+mod pod;
+#[cfg(test)]
+mod present_if;
+#[cfg(test)]
+mod present_if_remaining;
+#[cfg(test)]
+mod read_replace;
+#[cfg(test)]
+mod read_skip;
+#[cfg(test)]
+mod remaining;
+#[cfg(test)]
 mod structs;
 #[cfg(test)]
 mod tag;
+#[cfg(test)]
+mod tag_adjust;
+#[cfg(test)]
+mod tag_scale;
+#[cfg(test)]
+mod tcp;
+#[cfg(test)]
+mod total_bits;
+#[cfg(test)]
+mod tuple_fields;
+#[cfg(test)]
+mod validate;
+#[cfg(test)]
+mod version;
@@ -0,0 +1,36 @@
+use bin_proto::{ByteOrder, Error, LimitedRead, ProtocolRead, ProtocolWrite};
+use bitstream_io::{BigEndian, BitReader};
+use std::io;
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Message {
+    #[protocol(tag(type = "u32", write_value = "self.data.len() as u32"))]
+    data: Vec<u8>,
+}
+
+fn read_limited(bytes: &[u8], limit: u64) -> bin_proto::Result<Message> {
+    let mut reader = BitReader::endian(bytes, BigEndian);
+    Message::read(
+        &mut LimitedRead::new(&mut reader, limit),
+        ByteOrder::BigEndian,
+        &mut (),
+    )
+}
+
+#[test]
+fn message_within_limit() {
+    assert_eq!(
+        read_limited(&[0, 0, 0, 2, 7, 8], 6).unwrap(),
+        Message { data: vec![7, 8] }
+    );
+}
+
+#[test]
+fn runaway_message_exceeds_limit() {
+    let mut bytes = vec![0xff, 0xff, 0xff, 0xff];
+    bytes.resize(1024, 0);
+    assert!(matches!(
+        read_limited(&bytes, 64),
+        Err(Error::IO(e)) if e.kind() == io::ErrorKind::InvalidData
+    ));
+}
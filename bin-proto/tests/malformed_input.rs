@@ -0,0 +1,73 @@
+#![cfg(test)]
+
+use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite, VarInt};
+use proptest::prelude::*;
+use std::{collections::BTreeMap, io};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Message {
+    #[protocol(tag(type = "u64", write_value = "self.data.len() as u64"))]
+    data: Vec<u32>,
+}
+
+#[test]
+fn huge_count_fails_without_allocating() {
+    assert!(matches!(
+        Message::from_bytes(&[0xff; 12], ByteOrder::BigEndian),
+        Err(Error::IO(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+    ));
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite)]
+#[protocol(discriminant_type = "u8")]
+enum Body {
+    #[protocol(discriminant = "1")]
+    Text(#[protocol(tag(type = "u16", write_value = "field_0.len() as u16"))] String),
+    #[protocol(discriminant = "2")]
+    Table(#[protocol(tag(type = "u64", write_value = "field_0.len() as u64"))] BTreeMap<u8, u32>),
+    #[protocol(discriminant = "3")]
+    Raw(#[protocol(flexible_array_member)] Vec<u8>),
+}
+
+/// The message read by the fuzz target in `fuzz`.
+#[derive(Debug, ProtocolRead, ProtocolWrite)]
+struct Framed {
+    #[protocol(bits = 4)]
+    version: u8,
+    #[protocol(bits = 4)]
+    flags: u8,
+    #[protocol(tag(type = "VarInt<u32>", write_value = "VarInt(self.ids.len() as u32)"))]
+    ids: Vec<u16>,
+    #[protocol(tag = "flags & 1 != 0")]
+    checksum: Option<[u8; 4]>,
+    body: Body,
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_input_fails_without_panicking(bytes: Vec<u8>) {
+        for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            if let Ok(message) = Framed::from_bytes(&bytes, byte_order) {
+                message.bytes(byte_order).unwrap();
+            }
+        }
+    }
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Text {
+    #[protocol(tag(type = "u8", write_value = "self.text.len() as u8"))]
+    text: String,
+}
+
+#[test]
+fn invalid_utf8_is_distinct_from_io_errors() {
+    assert!(matches!(
+        Text::from_bytes(&[3, b'a', 0xc3, b'b'], ByteOrder::BigEndian),
+        Err(Error::FromUtf8(e)) if e.as_bytes() == [b'a', 0xc3, b'b']
+    ));
+    assert!(matches!(
+        Text::from_bytes(&[3, b'a', b'b'], ByteOrder::BigEndian),
+        Err(Error::IO(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+    ));
+}
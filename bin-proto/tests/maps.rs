@@ -0,0 +1,139 @@
+use bin_proto::{
+    ByteCounted, ByteOrder, Error, KeepFirstMap, ProtocolNoCtx, ProtocolRead, ProtocolWrite,
+    StrictMap,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Counted {
+    #[protocol(write_value = "self.entries.len() as u16")]
+    entry_count: u16,
+    #[protocol(tag = "entry_count")]
+    entries: BTreeMap<u8, u16>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Prefixed {
+    #[protocol(tag(type = "u16", write_value = "self.entries.len() as u16"))]
+    entries: HashMap<u16, bool>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct ByteSized {
+    #[protocol(write_value = "(self.entries.0.len() * 3) as u16")]
+    len: u16,
+    #[protocol(tag = "len")]
+    entries: ByteCounted<BTreeMap<u8, u16>>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Strict {
+    #[protocol(tag(type = "u8", write_value = "self.entries.0.len() as u8"))]
+    entries: StrictMap<HashMap<u8, u8>>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct KeepFirst {
+    #[protocol(tag(type = "u8", write_value = "self.entries.0.len() as u8"))]
+    entries: KeepFirstMap<BTreeMap<u8, u8>>,
+    #[protocol(flexible_array_member)]
+    trailing: KeepFirstMap<BTreeMap<u8, u8>>,
+}
+
+const DUPLICATE_KEYS: [u8; 7] = [3, 1, 10, 2, 20, 1, 30];
+
+fn round_trip<T: ProtocolNoCtx + PartialEq + std::fmt::Debug>(value: &T, bytes: &[u8]) {
+    assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+    assert_eq!(&T::from_bytes(bytes, ByteOrder::BigEndian).unwrap(), value);
+}
+
+#[test]
+fn map_with_entry_count() {
+    round_trip(
+        &Counted {
+            entry_count: 2,
+            entries: BTreeMap::from([(1, 0x0203), (4, 0x0506)]),
+        },
+        &[0, 2, 1, 2, 3, 4, 5, 6],
+    );
+}
+
+#[test]
+fn map_with_zero_entries() {
+    round_trip(
+        &Counted {
+            entry_count: 0,
+            entries: BTreeMap::new(),
+        },
+        &[0, 0],
+    );
+    round_trip(
+        &ByteSized {
+            len: 0,
+            entries: ByteCounted(BTreeMap::new()),
+        },
+        &[0, 0],
+    );
+}
+
+#[test]
+fn hash_map_with_prefixed_count() {
+    round_trip(
+        &Prefixed {
+            entries: HashMap::from([(0x0102, true)]),
+        },
+        &[0, 1, 1, 2, 1],
+    );
+}
+
+#[test]
+fn map_with_byte_length() {
+    round_trip(
+        &ByteSized {
+            len: 6,
+            entries: ByteCounted(BTreeMap::from([(1, 0x0203), (4, 0x0506)])),
+        },
+        &[0, 6, 1, 2, 3, 4, 5, 6],
+    );
+}
+
+#[test]
+fn map_entry_straddling_byte_length() {
+    assert!(matches!(
+        ByteSized::from_bytes(&[0, 4, 1, 2, 3, 4, 5, 6], ByteOrder::BigEndian),
+        Err(bin_proto::Error::IO(e)) if e.kind() == io::ErrorKind::InvalidData
+    ));
+}
+
+#[test]
+fn duplicate_keys_overwrite_by_default() {
+    let read =
+        Counted::from_bytes(&[0, 3, 1, 0, 10, 2, 0, 20, 1, 0, 30], ByteOrder::BigEndian).unwrap();
+    assert_eq!(read.entries, BTreeMap::from([(1, 30), (2, 20)]));
+}
+
+#[test]
+fn strict_map_rejects_duplicate_keys() {
+    assert!(matches!(
+        Strict::from_bytes(&DUPLICATE_KEYS, ByteOrder::BigEndian),
+        Err(Error::DuplicateMapKey { index: 2 })
+    ));
+    round_trip(
+        &Strict {
+            entries: StrictMap(HashMap::from([(1, 10)])),
+        },
+        &[1, 1, 10],
+    );
+}
+
+#[test]
+fn keep_first_map_ignores_duplicate_keys() {
+    let mut bytes = DUPLICATE_KEYS.to_vec();
+    bytes.extend([5, 50, 5, 51]);
+    let read = KeepFirst::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+    assert_eq!(read.entries.0, BTreeMap::from([(1, 10), (2, 20)]));
+    assert_eq!(read.trailing.0, BTreeMap::from([(5, 50)]));
+}
@@ -0,0 +1,70 @@
+use bin_proto::{ByteOrder, FixedSize, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use std::marker::{PhantomData, PhantomPinned};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Plain {
+    id: u8,
+    value: u16,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct WithMarkers<T> {
+    id: u8,
+    unit: (),
+    phantom: PhantomData<T>,
+    value: u16,
+    pinned: PhantomPinned,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+enum MarkerEnum<T> {
+    #[protocol(discriminant = "1")]
+    Value(u8, PhantomData<T>),
+    #[protocol(discriminant = "2")]
+    Unit((), u8),
+}
+
+/// Has no `ProtocolRead` or `ProtocolWrite` impls.
+#[derive(Debug, PartialEq)]
+struct NotProtocol;
+
+#[test]
+fn markers_do_not_affect_struct_encoding() {
+    let plain = Plain {
+        id: 1,
+        value: 0x0203,
+    };
+    let with_markers = WithMarkers::<NotProtocol> {
+        id: 1,
+        unit: (),
+        phantom: PhantomData,
+        value: 0x0203,
+        pinned: PhantomPinned,
+    };
+    let bytes = with_markers.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, plain.bytes(ByteOrder::BigEndian).unwrap());
+    assert_eq!(
+        WithMarkers::<NotProtocol>::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        with_markers
+    );
+}
+
+#[test]
+fn markers_do_not_affect_enum_encoding() {
+    for (value, bytes) in [
+        (MarkerEnum::<NotProtocol>::Value(7, PhantomData), [1, 7]),
+        (MarkerEnum::Unit((), 8), [2, 8]),
+    ] {
+        assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+        assert_eq!(
+            MarkerEnum::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+            value
+        );
+    }
+}
+
+#[test]
+fn markers_are_zero_sized() {
+    assert_eq!(WithMarkers::<NotProtocol>::SIZE_BITS, Plain::SIZE_BITS);
+}
@@ -0,0 +1,88 @@
+use bin_proto::{ByteOrder, Error, MessageStream, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u8")]
+enum Kind {
+    #[protocol(discriminant = "1")]
+    Ping,
+    #[protocol(discriminant = "2")]
+    Pong,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+struct Frame {
+    magic: [u8; 2],
+    kind: Kind,
+    value: u16,
+}
+
+const MAGIC: [u8; 2] = [0xaa, 0x55];
+
+fn frame(kind: Kind, value: u16) -> Frame {
+    Frame {
+        magic: MAGIC,
+        kind,
+        value,
+    }
+}
+
+#[test]
+fn parses_consecutive_messages() {
+    let bytes = [1, 2, 2, 1];
+    let stream = MessageStream::<Kind>::new(&bytes, ByteOrder::BigEndian);
+    assert_eq!(
+        stream.map(Result::unwrap).collect::<Vec<_>>(),
+        [Kind::Ping, Kind::Pong, Kind::Pong, Kind::Ping]
+    );
+}
+
+#[test]
+fn skips_to_next_byte_on_error() {
+    let bytes = [1, 0xff, 2];
+    let mut stream = MessageStream::<Kind>::new(&bytes, ByteOrder::BigEndian);
+    assert_eq!(stream.next().unwrap().unwrap(), Kind::Ping);
+    assert!(matches!(
+        stream.next().unwrap(),
+        Err(Error::UnknownEnumDiscriminant { .. })
+    ));
+    assert_eq!(stream.position(), 16);
+    assert_eq!(stream.next().unwrap().unwrap(), Kind::Pong);
+    assert_eq!(stream.remaining(), 0);
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn resyncs_on_magic() {
+    let bytes = [
+        0xaa, 0x55, 0x01, 0x00, 0x01, // Ping 1
+        0xaa, 0x55, 0x09, 0x00, 0x02, // corrupted kind
+        0xaa, 0x55, 0x02, 0x00, 0x03, // Pong 3
+        0xaa, 0x55, 0x01, 0x00, 0x04, // Ping 4
+    ];
+    let mut stream = MessageStream::<Frame>::new(&bytes, ByteOrder::BigEndian).resync_on(&MAGIC);
+    assert_eq!(stream.next().unwrap().unwrap(), frame(Kind::Ping, 1));
+    assert_eq!(stream.position(), 40);
+    assert!(stream.next().unwrap().is_err());
+    assert_eq!(stream.position(), 80);
+    assert_eq!(stream.next().unwrap().unwrap(), frame(Kind::Pong, 3));
+    assert_eq!(stream.next().unwrap().unwrap(), frame(Kind::Ping, 4));
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn truncated_message_ends_stream() {
+    let bytes = [0xaa, 0x55, 0x01, 0x00, 0x01, 0xaa, 0x55, 0x02];
+    let mut stream = MessageStream::<Frame>::new(&bytes, ByteOrder::BigEndian).resync_on(&MAGIC);
+    assert_eq!(stream.next().unwrap().unwrap(), frame(Kind::Ping, 1));
+    assert!(matches!(stream.next().unwrap(), Err(Error::IO(_))));
+    assert_eq!(stream.remaining(), 0);
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn ends_on_value_reading_no_bits() {
+    let bytes = [1, 2];
+    let mut stream = MessageStream::<()>::new(&bytes, ByteOrder::BigEndian);
+    assert!(matches!(stream.next().unwrap(), Err(Error::IO(_))));
+    assert!(stream.next().is_none());
+}
@@ -0,0 +1,118 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(record_offsets)]
+struct Header {
+    #[protocol(bits = 4)]
+    version: u8,
+    #[protocol(bits = 4)]
+    flags: u8,
+    length: u16,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(record_offsets)]
+struct Packet {
+    header: Header,
+    #[protocol(tag = "header.length as usize")]
+    payload: Vec<u8>,
+    crc: u32,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(record_offsets)]
+struct Pair(u8, #[protocol(bits = 3)] u8);
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(record_offsets)]
+struct Frame {
+    header: Header,
+    #[protocol(bits = 3)]
+    kind: u8,
+    #[protocol(bits = 5)]
+    reserved: u8,
+    #[protocol(tag = "header.length as usize")]
+    payload: Vec<u8>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+struct Unrecorded {
+    value: u8,
+}
+
+#[test]
+fn records_offsets_of_nested_fields() {
+    let packet = Packet {
+        header: Header {
+            version: 4,
+            flags: 2,
+            length: 3,
+        },
+        payload: vec![1, 2, 3],
+        crc: 0xdead_beef,
+    };
+    let (bytes, offsets) = packet.bytes_with_offsets(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, packet.bytes(ByteOrder::BigEndian).unwrap());
+
+    assert_eq!(offsets.get("header.version"), Some(0..4));
+    assert_eq!(offsets.get("header.flags"), Some(4..8));
+    assert_eq!(offsets.get("header.length"), Some(8..24));
+    assert_eq!(offsets.get("header"), Some(0..24));
+    assert_eq!(offsets.get("payload"), Some(24..48));
+    assert_eq!(offsets.get("crc"), Some(48..80));
+    assert_eq!(offsets.get("missing"), None);
+    assert_eq!(
+        offsets.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        [
+            "header.version",
+            "header.flags",
+            "header.length",
+            "header",
+            "payload",
+            "crc"
+        ]
+    );
+}
+
+#[test]
+fn records_offsets_of_unnamed_fields() {
+    let (bytes, offsets) = Pair(1, 5).bytes_with_offsets(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [1, 0b1010_0000]);
+    assert_eq!(offsets.get("0"), Some(0..8));
+    assert_eq!(offsets.get("1"), Some(8..11));
+}
+
+#[test]
+fn records_nothing_without_attribute() {
+    let (bytes, offsets) = Unrecorded { value: 7 }
+        .bytes_with_offsets(ByteOrder::LittleEndian)
+        .unwrap();
+    assert_eq!(bytes, [7]);
+    assert_eq!(offsets.iter().count(), 0);
+}
+
+#[test]
+fn annotates_bytes_with_fields() {
+    let frame = Frame {
+        header: Header {
+            version: 4,
+            flags: 2,
+            length: 2,
+        },
+        kind: 5,
+        reserved: 0,
+        payload: vec![0xab, 0xcd],
+    };
+    let (bytes, offsets) = frame.bytes_with_offsets(ByteOrder::BigEndian).unwrap();
+    assert_eq!(
+        offsets.annotate(&bytes).to_string(),
+        "header [0..24]: 42 00 02
+header.version [0..4]: 42
+header.flags [4..8]: 42
+header.length [8..24]: 00 02
+kind [24..27]: a0
+reserved [27..32]: a0
+payload [32..48]: ab cd
+"
+    );
+}
@@ -0,0 +1,63 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+struct Padded {
+    #[protocol(bits = 3)]
+    low: u8,
+    #[protocol(bits = 3, pad_bits = 2)]
+    high: u8,
+    value: u8,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(bitfield_packing = "lsb0")]
+struct PaddedLsb0 {
+    #[protocol(bits = 3)]
+    low: u8,
+    #[protocol(bits = 3, pad_bits = 2)]
+    high: u8,
+    #[protocol(pad_bits = 8)]
+    value: u8,
+}
+
+#[test]
+fn pad_bits_between_bitfields() {
+    let value = Padded {
+        low: 0b101,
+        high: 0b011,
+        value: 0x42,
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0b1010_0011, 0x42]);
+    assert_eq!(
+        Padded::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn pad_bits_are_discarded_on_read() {
+    assert_eq!(
+        Padded::from_bytes(&[0b1011_1011, 0x42], ByteOrder::BigEndian).unwrap(),
+        Padded {
+            low: 0b101,
+            high: 0b011,
+            value: 0x42,
+        }
+    );
+}
+
+#[test]
+fn pad_bits_in_lsb0_run() {
+    let value = PaddedLsb0 {
+        low: 0b101,
+        high: 0b011,
+        value: 0x42,
+    };
+    let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+    assert_eq!(bytes, [0b0110_0101, 0, 0x42]);
+    assert_eq!(
+        PaddedLsb0::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+        value
+    );
+}
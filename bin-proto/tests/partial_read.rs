@@ -0,0 +1,77 @@
+use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use std::io;
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(partial_read)]
+struct Header {
+    version: u8,
+    #[protocol(write_value = "self.name.len() as u8")]
+    name_len: u8,
+    #[protocol(tag = "name_len as usize")]
+    name: String,
+    flags: u16,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(partial_read)]
+struct Pair(u16, u16);
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(partial_read)]
+struct Packet {
+    id: u8,
+    header: Header,
+}
+
+fn header() -> Header {
+    Header {
+        version: 1,
+        name_len: 3,
+        name: "abc".into(),
+        flags: 0x0102,
+    }
+}
+
+fn fields_read(result: bin_proto::Result<impl std::fmt::Debug>) -> (usize, Error) {
+    match result {
+        Err(Error::PartialRead {
+            fields_read,
+            source,
+        }) => (fields_read, *source),
+        other => panic!("expected a partial read, got {other:?}"),
+    }
+}
+
+#[test]
+fn complete_message_reads() {
+    let bytes = header().bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(
+        Header::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        header()
+    );
+}
+
+#[test]
+fn reports_fields_read_before_truncation() {
+    let bytes = header().bytes(ByteOrder::BigEndian).unwrap();
+    for (len, expected) in [(0, 0), (1, 1), (3, 2), (5, 3), (6, 3)] {
+        let (fields_read, source) =
+            fields_read(Header::from_bytes(&bytes[..len], ByteOrder::BigEndian));
+        assert_eq!(fields_read, expected, "truncated to {len} bytes");
+        assert!(matches!(source, Error::IO(e) if e.kind() == io::ErrorKind::UnexpectedEof));
+    }
+}
+
+#[test]
+fn reports_fields_read_in_tuple_struct() {
+    let (fields_read, _) = fields_read(Pair::from_bytes(&[0, 1, 0], ByteOrder::BigEndian));
+    assert_eq!(fields_read, 1);
+}
+
+#[test]
+fn nested_progress_is_kept() {
+    let (fields_read, source) =
+        fields_read(Packet::from_bytes(&[7, 1, 3, b'a'], ByteOrder::BigEndian));
+    assert_eq!(fields_read, 1);
+    assert!(matches!(source, Error::PartialRead { fields_read: 2, .. }));
+}
@@ -0,0 +1,92 @@
+use bin_proto::{ByteOrder, Error, LengthDelimited, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct ChunkBody {
+    kind: [u8; 4],
+    #[protocol(flexible_array_member)]
+    data: Vec<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Chunk {
+    #[protocol(write_value = "self.body.0.data.len() as u32")]
+    length: u32,
+    // The CRC covers the chunk type and data, but not the length.
+    #[protocol(tag = "length", tag_adjust = 4, checksum = "bin_proto::Crc32")]
+    body: LengthDelimited<ChunkBody>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Png {
+    signature: [u8; 8],
+    #[protocol(flexible_array_member)]
+    chunks: Vec<Chunk>,
+}
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// A 1x1 transparent RGBA image, as commonly embedded in web pages.
+const TRANSPARENT_PIXEL: [u8; 70] = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, // Signature
+    0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52, // IHDR, 13 bytes
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, // 1x1 RGBA
+    0x1f, 0x15, 0xc4, 0x89, // CRC
+    0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, // IDAT, 13 bytes
+    0x78, 0xda, 0x63, 0x64, 0x60, 0xf8, 0x5f, 0x0f, 0x00, 0x02, 0x87, 0x01, 0x80, // zlib
+    0xeb, 0x47, 0xba, 0x92, // CRC
+    0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, // IEND, 0 bytes
+    0xae, 0x42, 0x60, 0x82, // CRC
+];
+
+fn chunk(kind: &[u8; 4], data: &[u8]) -> Chunk {
+    Chunk {
+        length: data.len() as u32,
+        body: LengthDelimited(ChunkBody {
+            kind: *kind,
+            data: data.to_vec(),
+        }),
+    }
+}
+
+fn transparent_pixel() -> Png {
+    Png {
+        signature: SIGNATURE,
+        chunks: vec![
+            chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]),
+            chunk(
+                b"IDAT",
+                &[
+                    0x78, 0xda, 0x63, 0x64, 0x60, 0xf8, 0x5f, 0x0f, 0x00, 0x02, 0x87, 0x01, 0x80,
+                ],
+            ),
+            chunk(b"IEND", &[]),
+        ],
+    }
+}
+
+#[test]
+fn can_decode_png() {
+    assert_eq!(
+        Png::from_bytes(&TRANSPARENT_PIXEL, ByteOrder::BigEndian).unwrap(),
+        transparent_pixel()
+    );
+}
+
+#[test]
+fn can_encode_png() {
+    assert_eq!(
+        transparent_pixel().bytes(ByteOrder::BigEndian).unwrap(),
+        TRANSPARENT_PIXEL
+    );
+}
+
+#[test]
+fn rejects_corrupted_chunk() {
+    let mut raw = TRANSPARENT_PIXEL;
+    // The bit depth of IHDR.
+    raw[24] = 0x10;
+    assert!(matches!(
+        Png::from_bytes(&raw, ByteOrder::BigEndian),
+        Err(Error::ChecksumMismatch)
+    ));
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "bytemuck")]
+
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[repr(C)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable, ProtocolRead, ProtocolWrite,
+)]
+#[protocol(pod)]
+struct Pod {
+    a: u32,
+    b: u16,
+    c: i16,
+    d: f64,
+}
+
+#[derive(Debug, PartialEq, ProtocolRead, ProtocolWrite)]
+struct FieldByField {
+    a: u32,
+    b: u16,
+    c: i16,
+    d: f64,
+}
+
+const POD: Pod = Pod {
+    a: 0x0102_0304,
+    b: 0x0506,
+    c: -2,
+    d: 1.5,
+};
+
+const FIELD_BY_FIELD: FieldByField = FieldByField {
+    a: 0x0102_0304,
+    b: 0x0506,
+    c: -2,
+    d: 1.5,
+};
+
+#[test]
+fn pod_matches_field_by_field() {
+    for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+        assert_eq!(
+            POD.bytes(byte_order).unwrap(),
+            FIELD_BY_FIELD.bytes(byte_order).unwrap()
+        );
+    }
+}
+
+#[test]
+fn pod_roundtrips() {
+    for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+        let bytes = FIELD_BY_FIELD.bytes(byte_order).unwrap();
+        assert_eq!(Pod::from_bytes(&bytes, byte_order).unwrap(), POD);
+    }
+}
+
+#[test]
+fn native_is_memory_copy() {
+    assert_eq!(
+        POD.bytes(ByteOrder::NATIVE).unwrap(),
+        bytemuck::bytes_of(&POD)
+    );
+}
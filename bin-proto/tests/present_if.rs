@@ -0,0 +1,194 @@
+use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Packet {
+    #[protocol(bits = 1)]
+    has_id: bool,
+    #[protocol(bits = 1)]
+    has_length: bool,
+    #[protocol(bits = 6)]
+    reserved: u8,
+    #[protocol(present_if = "has_id")]
+    id: Option<u8>,
+    #[protocol(present_if = "has_length")]
+    length: Option<u16>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+enum Message {
+    #[protocol(discriminant = "1")]
+    Data {
+        flags: u8,
+        #[protocol(present_if = "flags & 0x80 != 0")]
+        sequence: Option<u32>,
+    },
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct SharedFlags {
+    flags: u8,
+    #[protocol(present_if = "flags & 0x01 != 0")]
+    source: Option<u16>,
+    #[protocol(present_if = "flags & 0x02 != 0")]
+    destination: Option<u16>,
+    #[protocol(present_if = "flags & 0x04 != 0")]
+    ttl: Option<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct DerivedFlags {
+    #[protocol(write_value = "u8::from(self.id.is_some())")]
+    flags: u8,
+    #[protocol(present_if = "flags & 0x01 != 0")]
+    id: Option<u16>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct InlineFlags {
+    #[protocol(bits = 1)]
+    first: Option<u8>,
+    #[protocol(bits = 7)]
+    second: Option<u8>,
+}
+
+#[test]
+fn present_fields_are_read_without_flag() {
+    let packet = Packet {
+        has_id: true,
+        has_length: true,
+        reserved: 0,
+        id: Some(7),
+        length: Some(0x0102),
+    };
+    let bytes = packet.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0b1100_0000, 7, 0x01, 0x02]);
+    assert_eq!(
+        Packet::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        packet
+    );
+}
+
+#[test]
+fn absent_fields_take_no_space() {
+    let packet = Packet {
+        has_id: false,
+        has_length: true,
+        reserved: 0,
+        id: None,
+        length: Some(0x0102),
+    };
+    let bytes = packet.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0b0100_0000, 0x01, 0x02]);
+    assert_eq!(
+        Packet::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        packet
+    );
+}
+
+#[test]
+fn present_if_in_enum_variant() {
+    let message = Message::Data {
+        flags: 0x80,
+        sequence: Some(9),
+    };
+    let bytes = message.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [1, 0x80, 0, 0, 0, 9]);
+    assert_eq!(
+        Message::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        message
+    );
+    assert_eq!(
+        Message::from_bytes(&[1, 0x00], ByteOrder::BigEndian).unwrap(),
+        Message::Data {
+            flags: 0,
+            sequence: None
+        }
+    );
+}
+
+#[test]
+fn write_fails_when_presence_mismatches() {
+    let missing = Packet {
+        has_id: true,
+        has_length: false,
+        reserved: 0,
+        id: None,
+        length: None,
+    };
+    assert!(matches!(
+        missing.bytes(ByteOrder::BigEndian),
+        Err(Error::PresenceMismatch { field: "id" })
+    ));
+
+    let unexpected = Message::Data {
+        flags: 0,
+        sequence: Some(1),
+    };
+    assert!(matches!(
+        unexpected.bytes(ByteOrder::BigEndian),
+        Err(Error::PresenceMismatch { field: "sequence" })
+    ));
+}
+
+#[test]
+fn shared_flags_byte_drives_presence() {
+    let value = SharedFlags {
+        flags: 0x05,
+        source: Some(0x0102),
+        destination: None,
+        ttl: Some(64),
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0x05, 0x01, 0x02, 64]);
+    assert_eq!(
+        SharedFlags::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+    assert_eq!(
+        SharedFlags::from_bytes(&[0x02, 0x03, 0x04], ByteOrder::BigEndian).unwrap(),
+        SharedFlags {
+            flags: 0x02,
+            source: None,
+            destination: Some(0x0304),
+            ttl: None,
+        }
+    );
+}
+
+#[test]
+fn write_value_drives_presence() {
+    let value = DerivedFlags {
+        flags: 0,
+        id: Some(0x0102),
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0x01, 0x01, 0x02]);
+    assert_eq!(
+        DerivedFlags::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        DerivedFlags {
+            flags: 0x01,
+            id: Some(0x0102),
+        }
+    );
+
+    let value = DerivedFlags {
+        flags: 0x01,
+        id: None,
+    };
+    assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), [0x00]);
+}
+
+#[test]
+fn bits_on_option_is_its_flag_width() {
+    let value = InlineFlags {
+        first: Some(0x7f),
+        second: None,
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0xbf, 0x80]);
+    assert_eq!(
+        InlineFlags::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
@@ -0,0 +1,64 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct WithTrailer {
+    id: u8,
+    #[protocol(present_if_remaining)]
+    trailer: Option<u32>,
+}
+
+#[test]
+fn reads_present_trailer() {
+    assert_eq!(
+        WithTrailer::from_bytes(&[0x01, 0x12, 0x34, 0x56, 0x78], ByteOrder::BigEndian).unwrap(),
+        WithTrailer {
+            id: 1,
+            trailer: Some(0x1234_5678)
+        }
+    );
+    assert_eq!(
+        WithTrailer::from_bytes(&[0x01, 0x12, 0x34, 0x56, 0x78], ByteOrder::LittleEndian).unwrap(),
+        WithTrailer {
+            id: 1,
+            trailer: Some(0x7856_3412)
+        }
+    );
+}
+
+#[test]
+fn reads_absent_trailer() {
+    assert_eq!(
+        WithTrailer::from_bytes(&[0x01], ByteOrder::BigEndian).unwrap(),
+        WithTrailer {
+            id: 1,
+            trailer: None
+        }
+    );
+}
+
+#[test]
+fn fails_on_truncated_trailer() {
+    assert!(WithTrailer::from_bytes(&[0x01, 0x12, 0x34], ByteOrder::BigEndian).is_err());
+}
+
+#[test]
+fn writes_trailer() {
+    assert_eq!(
+        WithTrailer {
+            id: 1,
+            trailer: Some(0x1234_5678)
+        }
+        .bytes(ByteOrder::BigEndian)
+        .unwrap(),
+        vec![0x01, 0x12, 0x34, 0x56, 0x78]
+    );
+    assert_eq!(
+        WithTrailer {
+            id: 1,
+            trailer: None
+        }
+        .bytes(ByteOrder::BigEndian)
+        .unwrap(),
+        vec![0x01]
+    );
+}
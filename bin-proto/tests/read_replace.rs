@@ -0,0 +1,100 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use bitstream_io::{BigEndian, BitReader};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Header {
+    #[protocol(write_value = "self.name.len() as u8")]
+    name_len: u8,
+    #[protocol(tag = "name_len")]
+    name: String,
+}
+
+#[derive(Debug, Default, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Message {
+    header: Header,
+    #[protocol(bits = 4)]
+    kind: u8,
+    #[protocol(bits = 4)]
+    flags: u8,
+    #[protocol(tag(type = "u8", write_value = "self.data.len() as u8"))]
+    data: Vec<u16>,
+    #[protocol(tag(type = "u8", write_value = "self.attributes.len() as u8"))]
+    attributes: BTreeMap<u8, u8>,
+    #[protocol(flexible_array_member)]
+    trailer: Vec<u8>,
+}
+
+fn message(name: &str, data: &[u16], attributes: &[(u8, u8)], trailer: &[u8]) -> Message {
+    Message {
+        header: Header {
+            name_len: name.len() as u8,
+            name: name.into(),
+        },
+        kind: 3,
+        flags: 5,
+        data: data.to_vec(),
+        attributes: attributes.iter().copied().collect(),
+        trailer: trailer.to_vec(),
+    }
+}
+
+fn read_replace(value: &mut Message, bytes: &[u8]) -> bin_proto::Result<()> {
+    value.read_replace(
+        &mut BitReader::endian(bytes, BigEndian),
+        ByteOrder::BigEndian,
+        &mut (),
+    )
+}
+
+#[test]
+fn replaces_every_field() {
+    let first = message("first", &[1, 2, 3, 4], &[(1, 1), (2, 2)], &[9, 9, 9]);
+    let second = message("2nd", &[5], &[(3, 3)], &[]);
+
+    let mut value = Message::default();
+    read_replace(&mut value, &first.bytes(ByteOrder::BigEndian).unwrap()).unwrap();
+    assert_eq!(value, first);
+    read_replace(&mut value, &second.bytes(ByteOrder::BigEndian).unwrap()).unwrap();
+    assert_eq!(value, second);
+}
+
+#[test]
+fn reuses_allocations() {
+    let first = message("first", &[1, 2, 3, 4], &[], &[]);
+    let second = message("2nd", &[5, 6], &[], &[]);
+
+    let mut value = Message::default();
+    read_replace(&mut value, &first.bytes(ByteOrder::BigEndian).unwrap()).unwrap();
+    let data = value.data.as_ptr();
+    let name = value.header.name.as_ptr();
+    read_replace(&mut value, &second.bytes(ByteOrder::BigEndian).unwrap()).unwrap();
+    assert_eq!(value, second);
+    assert_eq!(value.data.as_ptr(), data);
+    assert_eq!(value.header.name.as_ptr(), name);
+}
+
+#[test]
+fn nothing_leaks_after_failed_read() {
+    let first = message("first", &[1, 2, 3, 4], &[(1, 1)], &[9]);
+    let second = message("2nd", &[5], &[], &[7]);
+
+    let mut value = Message::default();
+    read_replace(&mut value, &first.bytes(ByteOrder::BigEndian).unwrap()).unwrap();
+    let truncated = second.bytes(ByteOrder::BigEndian).unwrap();
+    assert!(read_replace(&mut value, &truncated[..6]).is_err());
+    read_replace(&mut value, &second.bytes(ByteOrder::BigEndian).unwrap()).unwrap();
+    assert_eq!(value, second);
+}
+
+#[test]
+fn matches_read() {
+    let value = message("name", &[1, 2], &[(4, 5)], &[6]);
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    let mut replaced = message("other", &[7, 8, 9], &[(1, 2), (3, 4)], &[5, 6, 7]);
+    read_replace(&mut replaced, &bytes).unwrap();
+    assert_eq!(
+        replaced,
+        Message::from_bytes(&bytes, ByteOrder::BigEndian).unwrap()
+    );
+}
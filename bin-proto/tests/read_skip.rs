@@ -0,0 +1,74 @@
+#![cfg(test)]
+
+use bin_proto::{ByteOrder, FixedSize, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+/// Version 1 of a record, which had a `u32` timestamp between `id` and
+/// `flags`, and a trailing `u8` checksum.
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct RecordV1 {
+    id: u16,
+    timestamp: u32,
+    flags: u8,
+    checksum: u8,
+}
+
+/// Version 2 of the record, without the timestamp and checksum.
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct RecordV2 {
+    id: u16,
+    #[protocol(read_skip = 4)]
+    flags: u8,
+    #[protocol(read_skip = 1)]
+    end: (),
+}
+
+#[test]
+fn reads_old_format_into_new_shape() {
+    let old = RecordV1 {
+        id: 0x0102,
+        timestamp: 0xdead_beef,
+        flags: 0x80,
+        checksum: 0x55,
+    }
+    .bytes(ByteOrder::BigEndian)
+    .unwrap();
+    assert_eq!(
+        RecordV2::from_bytes(&old, ByteOrder::BigEndian).unwrap(),
+        RecordV2 {
+            id: 0x0102,
+            flags: 0x80,
+            end: (),
+        }
+    );
+}
+
+#[test]
+fn writes_zeros_in_place_of_skipped_bytes() {
+    let bytes = RecordV2 {
+        id: 0x0102,
+        flags: 0x80,
+        end: (),
+    }
+    .bytes(ByteOrder::BigEndian)
+    .unwrap();
+    assert_eq!(bytes, [0x01, 0x02, 0, 0, 0, 0, 0x80, 0]);
+    assert_eq!(
+        RecordV1::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        RecordV1 {
+            id: 0x0102,
+            timestamp: 0,
+            flags: 0x80,
+            checksum: 0,
+        }
+    );
+}
+
+#[test]
+fn skipped_bytes_count_towards_fixed_size() {
+    assert_eq!(RecordV2::SIZE_BITS, RecordV1::SIZE_BITS);
+}
+
+#[test]
+fn truncated_skip_fails() {
+    assert!(RecordV2::from_bytes(&[0x01, 0x02, 0, 0], ByteOrder::BigEndian).is_err());
+}
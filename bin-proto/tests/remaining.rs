@@ -0,0 +1,98 @@
+use bin_proto::{ByteOrder, LengthDelimited, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Packet {
+    kind: u8,
+    #[protocol(remaining)]
+    payload: Vec<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Boxed(u16, #[protocol(remaining)] Box<[u8]>);
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Unaligned {
+    #[protocol(bits = 4)]
+    flags: u8,
+    #[protocol(remaining)]
+    payload: Vec<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Envelope {
+    #[protocol(tag(type = "u8", write_value = "self.packet.0.payload.len() as u8 + 1"))]
+    packet: LengthDelimited<Packet>,
+    trailer: u16,
+}
+
+#[test]
+fn reads_remaining_bytes() {
+    assert_eq!(
+        Packet::from_bytes(&[0x01, 0x02, 0x03], ByteOrder::BigEndian).unwrap(),
+        Packet {
+            kind: 1,
+            payload: vec![2, 3]
+        }
+    );
+    assert_eq!(
+        Packet::from_bytes(&[0x01], ByteOrder::BigEndian).unwrap(),
+        Packet {
+            kind: 1,
+            payload: vec![]
+        }
+    );
+    assert_eq!(
+        Boxed::from_bytes(&[0x00, 0x01, 0x02], ByteOrder::BigEndian).unwrap(),
+        Boxed(1, Box::new([2]))
+    );
+}
+
+#[test]
+fn reads_unaligned_remaining_bytes() {
+    assert_eq!(
+        Unaligned::from_bytes(&[0x12, 0x34, 0x56], ByteOrder::BigEndian).unwrap(),
+        Unaligned {
+            flags: 1,
+            payload: vec![0x23, 0x45],
+        }
+    );
+}
+
+#[test]
+fn writes_remaining_bytes() {
+    assert_eq!(
+        Packet {
+            kind: 1,
+            payload: vec![2, 3]
+        }
+        .bytes(ByteOrder::BigEndian)
+        .unwrap(),
+        vec![0x01, 0x02, 0x03]
+    );
+}
+
+#[test]
+fn remaining_stops_at_enclosing_length() {
+    let envelope = Envelope {
+        packet: LengthDelimited(Packet {
+            kind: 1,
+            payload: vec![2, 3],
+        }),
+        trailer: 0xabcd,
+    };
+    let bytes = vec![0x03, 0x01, 0x02, 0x03, 0xab, 0xcd];
+    assert_eq!(envelope.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+    assert_eq!(
+        Envelope::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        envelope
+    );
+}
+
+#[test]
+fn reads_large_payload() {
+    let mut bytes = vec![0x01];
+    bytes.extend((0..=u8::MAX).cycle().take(1 << 20));
+
+    let packet = Packet::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+    assert_eq!(packet.payload, bytes[1..]);
+}
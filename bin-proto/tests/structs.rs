@@ -15,6 +15,8 @@ pub struct BizBong(u8, u8, pub u8);
 #[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
 pub struct PartyInTheFront;
 
+// Only checks that it derives.
+#[allow(dead_code)]
 #[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
 #[protocol(ctx = "()")]
 pub struct NamedFieldsWithGenerics<A: ProtocolRead + ProtocolWrite, D: ProtocolRead + ProtocolWrite>
@@ -23,6 +25,8 @@ pub struct NamedFieldsWithGenerics<A: ProtocolRead + ProtocolWrite, D: ProtocolR
     pub del: D,
 }
 
+// Only checks that it derives.
+#[allow(dead_code)]
 #[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
 #[protocol(ctx = "Ctx")]
 pub struct UnnamedFieldsWithGenerics<
@@ -31,6 +35,8 @@ pub struct UnnamedFieldsWithGenerics<
     D: ProtocolRead<Ctx> + ProtocolWrite<Ctx>,
 >(A, D, PhantomData<Ctx>);
 
+// Only checks that it derives.
+#[allow(dead_code)]
 #[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
 #[protocol(ctx = "()")]
 pub struct StructWithExistingBoundedGenerics<
@@ -40,6 +46,8 @@ pub struct StructWithExistingBoundedGenerics<
 }
 
 #[test]
+// Spells the byte as the character it encodes.
+#[allow(clippy::char_lit_as_u8)]
 fn named_fields_are_correctly_written() {
     assert_eq!(
         vec![3, '2' as u8, 1],
@@ -54,6 +62,8 @@ fn named_fields_are_correctly_written() {
 }
 
 #[test]
+// Spells the byte as the character it encodes.
+#[allow(clippy::char_lit_as_u8)]
 fn named_fields_are_correctly_read() {
     assert_eq!(
         Foobar {
@@ -94,6 +104,49 @@ fn unit_structs_are_correctly_read() {
     );
 }
 
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq)]
+pub struct Mixed {
+    #[protocol(bits = 4)]
+    version: u8,
+    #[protocol(bits = 12)]
+    flags: u16,
+    #[protocol(tag(type = "u16", write_value = "self.name.len() as u16"))]
+    name: String,
+    values: [u32; 2],
+    #[protocol(flexible_array_member)]
+    payload: Vec<u8>,
+}
+
+#[test]
+fn bit_len_matches_bytes() {
+    let value = Mixed {
+        version: 4,
+        flags: 0x123,
+        name: "mixed".into(),
+        values: [1, 2],
+        payload: vec![1, 2, 3],
+    };
+    let bit_len = value.bit_len(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bit_len, 16 + 16 + 5 * 8 + 64 + 3 * 8);
+    assert_eq!(
+        bit_len,
+        value.bytes(ByteOrder::BigEndian).unwrap().len() as u64 * 8
+    );
+}
+
+#[test]
+fn bit_len_excludes_padding() {
+    #[derive(ProtocolRead, ProtocolWrite)]
+    struct Nibble {
+        #[protocol(bits = 4)]
+        value: u8,
+    }
+
+    let value = Nibble { value: 1 };
+    assert_eq!(value.bit_len(ByteOrder::BigEndian).unwrap(), 4);
+    assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap().len(), 1);
+}
+
 #[test]
 fn ipv4() {
     #[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
@@ -107,3 +160,59 @@ fn ipv4() {
         IPv4Header { version: 4 }
     )
 }
+
+#[cfg(feature = "proptest")]
+mod proptests {
+    use super::*;
+    use bin_proto::testing;
+    use proptest::prelude::*;
+
+    impl Arbitrary for Foobar {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            any::<(u8, u8, u8)>()
+                .prop_map(|(a, b, c)| Self { a, b, c })
+                .boxed()
+        }
+    }
+
+    impl Arbitrary for BizBong {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            any::<(u8, u8, u8)>()
+                .prop_map(|(a, b, c)| Self(a, b, c))
+                .boxed()
+        }
+    }
+
+    impl Arbitrary for PartyInTheFront {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            any::<()>().prop_map(|()| Self).boxed()
+        }
+    }
+
+    #[test]
+    fn named_fields_round_trip() {
+        testing::roundtrip::<Foobar>(ByteOrder::BigEndian);
+        testing::reparse::<Foobar>(ByteOrder::BigEndian);
+    }
+
+    #[test]
+    fn unnamed_fields_round_trip() {
+        testing::roundtrip::<BizBong>(ByteOrder::LittleEndian);
+        testing::reparse::<BizBong>(ByteOrder::LittleEndian);
+    }
+
+    #[test]
+    fn unit_structs_round_trip() {
+        testing::roundtrip::<PartyInTheFront>(ByteOrder::BigEndian);
+        testing::reparse::<PartyInTheFront>(ByteOrder::BigEndian);
+    }
+}
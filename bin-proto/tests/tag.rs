@@ -1,5 +1,8 @@
-use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite, VarInt};
+use std::collections::{BinaryHeap, LinkedList};
 
+// Only checks that it derives.
+#[allow(dead_code)]
 #[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
 pub struct Prefix {
     pub reason_length: u8,
@@ -162,3 +165,130 @@ fn can_write_prepended_length_prefix_3_elements() {
         ],
     );
 }
+
+#[derive(ProtocolRead, ProtocolWrite, Debug)]
+pub struct PrependedCollections {
+    #[protocol(tag(type = "u8", write_value = "self.list.len() as u8"))]
+    pub list: LinkedList<u16>,
+    #[protocol(tag(type = "u8", write_value = "self.heap.len() as u8"))]
+    pub heap: BinaryHeap<u16>,
+}
+
+#[test]
+fn linked_list_and_binary_heap_round_trip() {
+    let value = PrependedCollections {
+        list: LinkedList::from([3, 1, 2]),
+        heap: BinaryHeap::from([3, 1, 2]),
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes[..7], [3, 0, 3, 0, 1, 0, 2]);
+    assert_eq!(bytes[7], 3);
+
+    let read_back = PrependedCollections::from_bytes(&bytes, ByteOrder::BigEndian).unwrap();
+    assert_eq!(read_back.list, value.list);
+    assert_eq!(read_back.heap.into_sorted_vec(), [1, 2, 3]);
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct EmptyPayloads {
+    #[protocol(tag(type = "u8", write_value = "self.name.len() as u8"))]
+    pub name: String,
+    #[protocol(tag(type = "u8", write_value = "self.data.len() as u8"))]
+    pub data: Vec<u16>,
+    #[protocol(flexible_array_member)]
+    pub rest: Vec<u8>,
+}
+
+#[test]
+fn empty_payloads_round_trip() {
+    let value = EmptyPayloads {
+        name: String::new(),
+        data: Vec::new(),
+        rest: Vec::new(),
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0, 0]);
+    assert_eq!(
+        EmptyPayloads::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Failing;
+
+impl<Ctx> ProtocolRead<Ctx> for Failing {
+    fn read(_: &mut dyn bin_proto::BitRead, _: ByteOrder, _: &mut Ctx) -> bin_proto::Result<Self> {
+        Err(bin_proto::Error::Other(Box::new(std::fmt::Error)))
+    }
+}
+
+impl<Ctx> ProtocolWrite<Ctx> for Failing {
+    fn write(
+        &self,
+        _: &mut dyn bin_proto::BitWrite,
+        _: ByteOrder,
+        _: &mut Ctx,
+    ) -> bin_proto::Result<()> {
+        Err(bin_proto::Error::Other(Box::new(std::fmt::Error)))
+    }
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct WithFailing {
+    pub count: u8,
+    #[protocol(tag = "count")]
+    pub items: Vec<Failing>,
+}
+
+#[test]
+fn zero_items_of_failing_type_are_never_read() {
+    assert_eq!(
+        WithFailing::from_bytes(&[0], ByteOrder::BigEndian).unwrap(),
+        WithFailing {
+            count: 0,
+            items: Vec::new()
+        }
+    );
+}
+
+#[test]
+fn failing_item_read_returns_error() {
+    assert!(matches!(
+        WithFailing::from_bytes(&[1], ByteOrder::BigEndian),
+        Err(bin_proto::Error::Other(_))
+    ));
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct VarIntPrefixed {
+    #[protocol(tag(type = "VarInt<u32>", write_value = "VarInt(self.name.len() as u32)"))]
+    pub name: String,
+    pub flags: u8,
+}
+
+#[test]
+fn varint_prefixed_string_round_trips() {
+    let short = VarIntPrefixed {
+        name: "bin".into(),
+        flags: 1,
+    };
+    let bytes = short.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, b"\x03bin\x01");
+    assert_eq!(
+        VarIntPrefixed::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        short
+    );
+
+    let long = VarIntPrefixed {
+        name: "x".repeat(300),
+        flags: 2,
+    };
+    let bytes = long.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes[..2], [0xac, 0x02]);
+    assert_eq!(bytes.len(), 2 + 300 + 1);
+    assert_eq!(
+        VarIntPrefixed::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        long
+    );
+}
@@ -0,0 +1,132 @@
+use bin_proto::{ByteOrder, Error, LengthDelimited, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct IncludesSelf {
+    #[protocol(tag(type = "u16", write_value = "self.data.len() as u16"), tag_adjust = -2)]
+    pub data: Vec<u8>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct LengthMinusOne {
+    #[protocol(
+        tag(type = "u8", write_value = "self.data.len() as u8"),
+        tag_adjust = 1
+    )]
+    pub data: Vec<u8>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct ExternalTag {
+    #[protocol(write_value = "self.data.len() as u8 + 1")]
+    pub length: u8,
+    pub flag: bool,
+    #[protocol(tag = "length", tag_adjust = -1)]
+    pub data: Vec<u8>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct RecordBody {
+    pub kind: u8,
+    #[protocol(flexible_array_member)]
+    pub value: Vec<u8>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct Record {
+    // The length counts the whole record, including its own two bytes.
+    #[protocol(
+        tag(type = "u16", write_value = "1 + self.body.0.value.len() as u16"),
+        tag_adjust = -2
+    )]
+    pub body: LengthDelimited<RecordBody>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct Records {
+    pub first: Record,
+    pub second: Record,
+}
+
+#[test]
+fn negative_adjust_round_trips() {
+    let value = IncludesSelf {
+        data: vec![7, 8, 9],
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0, 5, 7, 8, 9]);
+    assert_eq!(
+        IncludesSelf::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn positive_adjust_round_trips() {
+    let value = LengthMinusOne { data: vec![7, 8] };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [1, 7, 8]);
+    assert_eq!(
+        LengthMinusOne::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn external_tag_adjust_round_trips() {
+    let value = ExternalTag {
+        length: 3,
+        flag: true,
+        data: vec![7, 8],
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [3, 1, 7, 8]);
+    assert_eq!(
+        ExternalTag::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn adjust_underflow_fails_to_read() {
+    assert!(matches!(
+        IncludesSelf::from_bytes(&[0, 1], ByteOrder::BigEndian),
+        Err(Error::TagConvert)
+    ));
+    assert!(matches!(
+        ExternalTag::from_bytes(&[0, 0], ByteOrder::BigEndian),
+        Err(Error::TagConvert)
+    ));
+}
+
+#[test]
+fn adjust_underflow_fails_to_write() {
+    let value = LengthMinusOne { data: vec![] };
+    assert!(matches!(
+        value.bytes(ByteOrder::BigEndian),
+        Err(Error::TagConvert)
+    ));
+}
+
+#[test]
+fn inclusive_record_lengths_delimit_records() {
+    let value = Records {
+        first: Record {
+            body: LengthDelimited(RecordBody {
+                kind: 1,
+                value: vec![7, 8],
+            }),
+        },
+        second: Record {
+            body: LengthDelimited(RecordBody {
+                kind: 2,
+                value: vec![],
+            }),
+        },
+    };
+    let bytes = value.bytes(ByteOrder::LittleEndian).unwrap();
+    assert_eq!(bytes, [5, 0, 1, 7, 8, 3, 0, 2]);
+    assert_eq!(
+        Records::from_bytes(&bytes, ByteOrder::LittleEndian).unwrap(),
+        value
+    );
+}
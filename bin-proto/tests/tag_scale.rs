@@ -0,0 +1,80 @@
+use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct WordCount {
+    #[protocol(
+        tag(type = "u16", write_value = "self.data.len() as u16"),
+        tag_scale = 2
+    )]
+    pub data: Vec<u8>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct ExternalWords {
+    #[protocol(write_value = "(self.data.len() / 4) as u8")]
+    pub words: u8,
+    #[protocol(tag = "words", tag_scale = 4)]
+    pub data: Vec<u8>,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+pub struct WordsIncludingHeader {
+    // Counts 32-bit words, one more than the data occupies.
+    #[protocol(
+        tag(type = "u8", write_value = "self.data.len() as u8"),
+        tag_scale = 4,
+        tag_adjust = -4
+    )]
+    pub data: Vec<u8>,
+}
+
+#[test]
+fn prepended_words_round_trip() {
+    let value = WordCount {
+        data: vec![1, 2, 3, 4, 5, 6],
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0, 3, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(
+        WordCount::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn external_words_round_trip() {
+    let value = ExternalWords {
+        words: 2,
+        data: vec![1, 2, 3, 4, 5, 6, 7, 8],
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [2, 1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(
+        ExternalWords::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn scale_applies_before_adjust() {
+    let value = WordsIncludingHeader {
+        data: vec![1, 2, 3, 4],
+    };
+    let bytes = value.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [2, 1, 2, 3, 4]);
+    assert_eq!(
+        WordsIncludingHeader::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn rejects_length_not_a_multiple_of_scale() {
+    assert!(matches!(
+        WordCount {
+            data: vec![1, 2, 3]
+        }
+        .bytes(ByteOrder::BigEndian),
+        Err(Error::TagConvert)
+    ));
+}
@@ -0,0 +1,123 @@
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Flags {
+    #[protocol(bits = 1)]
+    cwr: bool,
+    #[protocol(bits = 1)]
+    ece: bool,
+    #[protocol(bits = 1)]
+    urg: bool,
+    #[protocol(bits = 1)]
+    ack: bool,
+    #[protocol(bits = 1)]
+    psh: bool,
+    #[protocol(bits = 1)]
+    rst: bool,
+    #[protocol(bits = 1)]
+    syn: bool,
+    #[protocol(bits = 1)]
+    fin: bool,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct TcpSegment {
+    source_port: u16,
+    destination_port: u16,
+    sequence_number: u32,
+    acknowledgment_number: u32,
+    #[protocol(bits = 4)]
+    data_offset: u8,
+    #[protocol(bits = 4)]
+    reserved: u8,
+    flags: Flags,
+    window_size: u16,
+    checksum: u16,
+    urgent_pointer: u16,
+    #[protocol(tag = "(usize::from(data_offset) * 4).saturating_sub(20)")]
+    options: Vec<u8>,
+    #[protocol(flexible_array_member)]
+    payload: Vec<u8>,
+}
+
+fn flags(syn: bool, ack: bool, psh: bool) -> Flags {
+    Flags {
+        cwr: false,
+        ece: false,
+        urg: false,
+        ack,
+        psh,
+        rst: false,
+        syn,
+        fin: false,
+    }
+}
+
+#[test]
+fn can_encode_decode_syn_with_options() {
+    // A SYN from a Linux client to port 80.
+    let raw = [
+        0xe6, 0x7a, // Source port: 59002
+        0x00, 0x50, // Destination port: 80
+        0x5d, 0x2e, 0x9c, 0x3b, // Sequence number
+        0x00, 0x00, 0x00, 0x00, // Acknowledgment number
+        0xa0, // Data offset: 10 words
+        0x02, // Flags: SYN
+        0xfa, 0xf0, // Window size: 64240
+        0xfe, 0x30, // Checksum
+        0x00, 0x00, // Urgent pointer
+        0x02, 0x04, 0x05, 0xb4, // MSS: 1460
+        0x04, 0x02, // SACK permitted
+        0x08, 0x0a, 0x00, 0x2b, 0x4f, 0x1a, 0x00, 0x00, 0x00, 0x00, // Timestamps
+        0x01, // NOP
+        0x03, 0x03, 0x07, // Window scale: 7
+    ];
+    let parsed = TcpSegment {
+        source_port: 59002,
+        destination_port: 80,
+        sequence_number: 0x5d2e_9c3b,
+        acknowledgment_number: 0,
+        data_offset: 10,
+        reserved: 0,
+        flags: flags(true, false, false),
+        window_size: 64240,
+        checksum: 0xfe30,
+        urgent_pointer: 0,
+        options: raw[20..].to_vec(),
+        payload: vec![],
+    };
+    assert_eq!(
+        TcpSegment::from_bytes(&raw, ByteOrder::BigEndian).unwrap(),
+        parsed
+    );
+    assert_eq!(parsed.bytes(ByteOrder::BigEndian).unwrap(), raw);
+}
+
+#[test]
+fn can_encode_decode_segment_with_payload() {
+    // A PSH/ACK carrying the start of an HTTP request, without options.
+    let mut raw = vec![
+        0xe6, 0x7a, 0x00, 0x50, 0x5d, 0x2e, 0x9c, 0x3c, 0x8a, 0x11, 0x04, 0x2e, 0x50, 0x18, 0x01,
+        0xf6, 0x3c, 0x9d, 0x00, 0x00,
+    ];
+    raw.extend_from_slice(b"GET / HTTP/1.1\r\n");
+    let parsed = TcpSegment {
+        source_port: 59002,
+        destination_port: 80,
+        sequence_number: 0x5d2e_9c3c,
+        acknowledgment_number: 0x8a11_042e,
+        data_offset: 5,
+        reserved: 0,
+        flags: flags(false, true, true),
+        window_size: 502,
+        checksum: 0x3c9d,
+        urgent_pointer: 0,
+        options: vec![],
+        payload: b"GET / HTTP/1.1\r\n".to_vec(),
+    };
+    assert_eq!(
+        TcpSegment::from_bytes(&raw, ByteOrder::BigEndian).unwrap(),
+        parsed
+    );
+    assert_eq!(parsed.bytes(ByteOrder::BigEndian).unwrap(), raw);
+}
@@ -0,0 +1,70 @@
+use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(total_bits = 24)]
+struct Header {
+    #[protocol(bits = 3)]
+    version: u8,
+    #[protocol(bits = 13)]
+    fragment_offset: u16,
+    ttl: u8,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(total_bits = 23)]
+struct OffByOne {
+    #[protocol(bits = 3)]
+    version: u8,
+    #[protocol(bits = 13)]
+    fragment_offset: u16,
+    ttl: u8,
+}
+
+#[derive(ProtocolRead, ProtocolWrite, Debug, PartialEq, Eq)]
+#[protocol(bitfield_packing = "lsb0", total_bits = 8)]
+struct Lsb0Flags {
+    #[protocol(bits = 3)]
+    low: u8,
+    #[protocol(bits = 5)]
+    high: u8,
+}
+
+#[test]
+fn correct_total_bits_writes() {
+    let header = Header {
+        version: 4,
+        fragment_offset: 0x0102,
+        ttl: 64,
+    };
+    let bytes = header.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0x81, 0x02, 64]);
+    assert_eq!(
+        Header::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        header
+    );
+}
+
+#[test]
+fn wrong_total_bits_fails_to_write() {
+    let header = OffByOne {
+        version: 4,
+        fragment_offset: 0x0102,
+        ttl: 64,
+    };
+    assert!(matches!(
+        header.bytes(ByteOrder::BigEndian),
+        Err(Error::TotalBitsMismatch {
+            expected: 23,
+            actual: 24
+        })
+    ));
+}
+
+#[test]
+fn total_bits_with_lsb0_packing() {
+    let flags = Lsb0Flags {
+        low: 0b101,
+        high: 0b10001,
+    };
+    assert_eq!(flags.bytes(ByteOrder::BigEndian).unwrap(), [0b1000_1101]);
+}
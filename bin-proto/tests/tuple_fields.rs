@@ -0,0 +1,71 @@
+#![cfg(test)]
+
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Packet(
+    #[protocol(bits = 4)] u8,
+    #[protocol(bits = 4, write_value = "self.2.len() as u8")] u8,
+    #[protocol(tag = "field_1")] Vec<u8>,
+    #[protocol(condition = "field_0 != 0", default = "0")] u8,
+);
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(discriminant_type = "u8")]
+enum Message {
+    #[protocol(discriminant = "1")]
+    Data(
+        #[protocol(bits = 3)] u8,
+        #[protocol(bits = 5, write_value = "field_2.len() as u8")] u8,
+        #[protocol(tag = "field_1")] Vec<u8>,
+    ),
+    #[protocol(discriminant = "2")]
+    Trailer(
+        #[protocol(write_value = "field_1.len() as u8")] u8,
+        #[protocol(length = "remaining - 1")] Vec<u8>,
+        u8,
+    ),
+}
+
+fn round_trip<T: ProtocolNoCtx + PartialEq + std::fmt::Debug>(value: &T, bytes: &[u8]) {
+    assert_eq!(value.bytes(ByteOrder::BigEndian).unwrap(), bytes);
+    assert_eq!(&T::from_bytes(bytes, ByteOrder::BigEndian).unwrap(), value);
+}
+
+#[test]
+fn tuple_struct_with_bits_and_length_prefix() {
+    round_trip(
+        &Packet(0x3, 2, vec![0xaa, 0xbb], 0x7),
+        &[0x32, 0xaa, 0xbb, 0x07],
+    );
+    round_trip(&Packet(0, 1, vec![0xaa], 0), &[0x01, 0xaa]);
+}
+
+#[test]
+fn tuple_variant_with_bits_and_length_prefix() {
+    round_trip(
+        &Message::Data(0b101, 3, vec![1, 2, 3]),
+        &[1, 0b1010_0011, 1, 2, 3],
+    );
+}
+
+#[test]
+fn tuple_variant_with_remaining_length() {
+    round_trip(&Message::Trailer(2, vec![5, 6], 0xff), &[2, 2, 5, 6, 0xff]);
+}
+
+#[test]
+fn tuple_struct_read_replace() {
+    let mut packet = Packet(0, 0, Vec::new(), 0);
+    packet
+        .read_replace(
+            &mut bitstream_io::BitReader::endian(
+                [0x32, 0xaa, 0xbb, 0x07].as_slice(),
+                bitstream_io::BigEndian,
+            ),
+            ByteOrder::BigEndian,
+            &mut (),
+        )
+        .unwrap();
+    assert_eq!(packet, Packet(0x3, 2, vec![0xaa, 0xbb], 0x7));
+}
@@ -0,0 +1,154 @@
+use bin_proto::{ByteOrder, Error, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Frame {
+    #[protocol(bits = 5)]
+    channel: u8,
+    #[protocol(bits = 3)]
+    priority: u8,
+    #[protocol(tag(type = "u8", write_value = "u8::try_from(self.data.len())?"))]
+    data: Vec<u8>,
+}
+
+#[test]
+fn validates_writable_value() {
+    let frame = Frame {
+        channel: 31,
+        priority: 7,
+        data: vec![0; 255],
+    };
+    assert!(frame.validate(ByteOrder::BigEndian).is_ok());
+}
+
+#[test]
+fn rejects_bits_field_out_of_range() {
+    let frame = Frame {
+        channel: 40,
+        priority: 0,
+        data: Vec::new(),
+    };
+    assert!(matches!(
+        frame.validate(ByteOrder::BigEndian),
+        Err(Error::IO(_))
+    ));
+}
+
+#[test]
+fn rejects_length_prefix_overflow() {
+    let frame = Frame {
+        channel: 0,
+        priority: 0,
+        data: vec![0; 300],
+    };
+    assert!(matches!(
+        frame.validate(ByteOrder::BigEndian),
+        Err(Error::TryFromIntError(_))
+    ));
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Truncating {
+    #[protocol(write_value = "self.data.len() as u8")]
+    len: u8,
+    #[protocol(tag = "len as usize")]
+    data: Vec<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Prefixed {
+    #[protocol(tag(type = "u8", write_value = "self.data.len() as u8"))]
+    data: Vec<u16>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Optional {
+    flags: u8,
+    #[protocol(present_if = "flags & 0x01 != 0")]
+    id: Option<u8>,
+    #[protocol(tag(type = "bool", write_value = "flags & 0x02 != 0"))]
+    extra: Option<u8>,
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+struct Outer {
+    frame: Frame,
+}
+
+#[test]
+fn rejects_truncated_length_field() {
+    let value = Truncating {
+        len: 0,
+        data: vec![0; 300],
+    };
+    assert!(value.bytes(ByteOrder::BigEndian).is_ok());
+    assert!(matches!(
+        value.validate(ByteOrder::BigEndian),
+        Err(Error::TagMismatch {
+            field: "data",
+            len: 300
+        })
+    ));
+}
+
+#[test]
+fn rejects_truncated_length_prefix() {
+    let value = Prefixed { data: vec![0; 256] };
+    assert!(matches!(
+        value.validate(ByteOrder::BigEndian),
+        Err(Error::TagMismatch {
+            field: "data",
+            len: 256
+        })
+    ));
+    assert!(Prefixed { data: vec![0; 255] }
+        .validate(ByteOrder::BigEndian)
+        .is_ok());
+}
+
+#[test]
+fn rejects_options_inconsistent_with_flags() {
+    let consistent = Optional {
+        flags: 0x03,
+        id: Some(1),
+        extra: Some(2),
+    };
+    assert!(consistent.validate(ByteOrder::BigEndian).is_ok());
+
+    let missing = Optional {
+        flags: 0x01,
+        id: None,
+        extra: None,
+    };
+    assert!(matches!(
+        missing.validate(ByteOrder::BigEndian),
+        Err(Error::PresenceMismatch { field: "id" })
+    ));
+
+    let unexpected = Optional {
+        flags: 0x00,
+        id: None,
+        extra: Some(2),
+    };
+    assert!(matches!(
+        unexpected.validate(ByteOrder::BigEndian),
+        Err(Error::TagMismatch {
+            field: "extra",
+            len: 1
+        })
+    ));
+}
+
+#[test]
+fn validates_nested_fields() {
+    let outer = Outer {
+        frame: Frame {
+            channel: 40,
+            priority: 0,
+            data: Vec::new(),
+        },
+    };
+    assert!(matches!(
+        outer.validate(ByteOrder::BigEndian),
+        Err(Error::IO(_))
+    ));
+}
@@ -0,0 +1,93 @@
+use bin_proto::{ByteOrder, Error, ProtocolRead, ProtocolVersion, ProtocolWrite};
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u8")]
+#[protocol(ctx_bounds = "ProtocolVersion")]
+enum Message {
+    #[protocol(discriminant = "1")]
+    Ping,
+    #[protocol(discriminant = "2", removed_in = 2)]
+    LegacyHello(u8),
+    #[protocol(discriminant = "3", added_in = 2)]
+    Hello { id: u16 },
+}
+
+struct Peer {
+    version: u32,
+}
+
+impl ProtocolVersion for Peer {
+    fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+fn peer(version: u32) -> Peer {
+    Peer { version }
+}
+
+fn send(message: &Message, peer: &mut Peer) -> bin_proto::Result<Vec<u8>> {
+    message.bytes_ctx(ByteOrder::BigEndian, peer)
+}
+
+#[test]
+fn peers_exchange_supported_variants() {
+    for (message, mut peer) in [
+        (Message::Ping, peer(1)),
+        (Message::Ping, peer(2)),
+        (Message::LegacyHello(7), peer(1)),
+        (Message::Hello { id: 7 }, peer(2)),
+    ] {
+        let bytes = send(&message, &mut peer).unwrap();
+        assert_eq!(
+            Message::from_bytes_ctx(&bytes, ByteOrder::BigEndian, &mut peer).unwrap(),
+            message
+        );
+    }
+}
+
+#[test]
+fn write_rejects_unsupported_variant() {
+    assert!(matches!(
+        send(&Message::Hello { id: 7 }, &mut peer(1)),
+        Err(Error::VersionMismatch {
+            variant: "Hello",
+            version: 1
+        })
+    ));
+    assert!(matches!(
+        send(&Message::LegacyHello(7), &mut peer(2)),
+        Err(Error::VersionMismatch {
+            variant: "LegacyHello",
+            version: 2
+        })
+    ));
+}
+
+#[test]
+fn read_rejects_unsupported_variant() {
+    let bytes = send(&Message::Hello { id: 7 }, &mut peer(2)).unwrap();
+    assert!(matches!(
+        Message::from_bytes_ctx(&bytes, ByteOrder::BigEndian, &mut peer(1)),
+        Err(Error::VersionMismatch {
+            variant: "Hello",
+            version: 1
+        })
+    ));
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq, Eq)]
+#[protocol(discriminant_type = "u8")]
+#[protocol(ctx = "u32")]
+enum Fixed {
+    #[protocol(discriminant = "1", added_in = 3, removed_in = 5)]
+    Variant,
+}
+
+#[test]
+fn version_range_is_half_open() {
+    for (version, supported) in [(2, false), (3, true), (4, true), (5, false)] {
+        let result = Fixed::Variant.bytes_ctx(ByteOrder::BigEndian, &mut { version });
+        assert_eq!(result.is_ok(), supported);
+    }
+}
@@ -0,0 +1,37 @@
+#![no_main]
+
+use bin_proto::{ByteOrder, ProtocolNoCtx, ProtocolRead, ProtocolWrite, VarInt};
+use libfuzzer_sys::fuzz_target;
+use std::collections::BTreeMap;
+
+#[derive(Debug, ProtocolRead, ProtocolWrite)]
+#[protocol(discriminant_type = "u8")]
+enum Body {
+    #[protocol(discriminant = "1")]
+    Text(#[protocol(tag(type = "u16", write_value = "field_0.len() as u16"))] String),
+    #[protocol(discriminant = "2")]
+    Table(#[protocol(tag(type = "u64", write_value = "field_0.len() as u64"))] BTreeMap<u8, u32>),
+    #[protocol(discriminant = "3")]
+    Raw(#[protocol(flexible_array_member)] Vec<u8>),
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite)]
+struct Message {
+    #[protocol(bits = 4)]
+    version: u8,
+    #[protocol(bits = 4)]
+    flags: u8,
+    #[protocol(tag(type = "VarInt<u32>", write_value = "VarInt(self.ids.len() as u32)"))]
+    ids: Vec<u16>,
+    #[protocol(tag = "flags & 1 != 0")]
+    checksum: Option<[u8; 4]>,
+    body: Body,
+}
+
+fuzz_target!(|data: &[u8]| {
+    for byte_order in [ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+        if let Ok(message) = Message::from_bytes(data, byte_order) {
+            message.bytes(byte_order).unwrap();
+        }
+    }
+});
@@ -0,0 +1,7 @@
+//! Checks that derived code works when `bin-proto` is renamed in `Cargo.toml`
+//! or re-exported from another crate, using `#[protocol(crate = "<path>")]`.
+
+/// A facade re-exporting `bin-proto` under another path.
+pub mod facade {
+    pub use proto as inner;
+}
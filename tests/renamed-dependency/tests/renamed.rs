@@ -0,0 +1,67 @@
+use proto::{ByteOrder, Discriminable, ProtocolNoCtx, ProtocolRead, ProtocolWrite};
+use renamed_dependency::facade;
+
+/// A module named like the crate, which `crate` must not rewrite paths into.
+mod lengths {
+    pub mod bin_proto {
+        pub fn len(payload: &[u8]) -> u8 {
+            payload.len() as u8
+        }
+    }
+}
+
+#[derive(Debug, ProtocolRead, ProtocolWrite, PartialEq)]
+#[protocol(crate = "proto")]
+struct Header {
+    #[protocol(bits = 4)]
+    version: u8,
+    #[protocol(bits = 4)]
+    flags: u8,
+    #[protocol(tag(type = "u8", write_value = "lengths::bin_proto::len(&self.payload)"))]
+    payload: Vec<u8>,
+}
+
+#[derive(Debug, facade::inner::ProtocolRead, facade::inner::ProtocolWrite, PartialEq)]
+#[protocol(crate = "renamed_dependency::facade::inner")]
+#[protocol(discriminant_type = "u8")]
+enum Message {
+    #[protocol(discriminant = "1")]
+    Ping,
+    #[protocol(discriminant = "2")]
+    Data(Header),
+}
+
+#[test]
+fn renamed_crate_struct_round_trips() {
+    let header = Header {
+        version: 1,
+        flags: 2,
+        payload: vec![3, 4],
+    };
+    let bytes = header.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [0x12, 2, 3, 4]);
+    assert_eq!(
+        Header::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        header
+    );
+}
+
+#[test]
+fn facade_crate_enum_round_trips() {
+    let message = Message::Data(Header {
+        version: 1,
+        flags: 0,
+        payload: vec![],
+    });
+    assert_eq!(message.discriminant(), 2);
+    let bytes = message.bytes(ByteOrder::BigEndian).unwrap();
+    assert_eq!(bytes, [2, 0x10, 0]);
+    assert_eq!(
+        Message::from_bytes(&bytes, ByteOrder::BigEndian).unwrap(),
+        message
+    );
+    assert_eq!(
+        Message::from_bytes(&[1], ByteOrder::BigEndian).unwrap(),
+        Message::Ping
+    );
+}